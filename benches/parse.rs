@@ -18,9 +18,17 @@ fn parse_feature_collection_benchmark(c: &mut Criterion) {
         })
     });
 
+    c.bench_function("parse borrowed (countries.geojson)", |b| {
+        b.iter(|| {
+            let fc: geojson::FeatureCollectionRef = serde_json::from_str(geojson_str).unwrap();
+            assert_eq!(fc.features.len(), 180);
+            black_box(fc)
+        })
+    });
+
     c.bench_function("FeatureReader::features (countries.geojson)", |b| {
         b.iter(|| {
-            let feature_reader =
+            let mut feature_reader =
                 geojson::FeatureReader::from_reader(BufReader::new(geojson_str.as_bytes()));
             let mut count = 0;
             for feature in feature_reader.features() {
@@ -40,7 +48,7 @@ fn parse_feature_collection_benchmark(c: &mut Criterion) {
                 geometry: geojson::Geometry,
                 name: String,
             }
-            let feature_reader =
+            let mut feature_reader =
                 geojson::FeatureReader::from_reader(BufReader::new(geojson_str.as_bytes()));
 
             let mut count = 0;
@@ -65,7 +73,7 @@ fn parse_feature_collection_benchmark(c: &mut Criterion) {
                     geometry: geo_types::Geometry,
                     name: String,
                 }
-                let feature_reader =
+                let mut feature_reader =
                     geojson::FeatureReader::from_reader(BufReader::new(geojson_str.as_bytes()));
 
                 let mut count = 0;
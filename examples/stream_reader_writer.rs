@@ -22,7 +22,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         name: String,
     }
 
-    let reader = {
+    let mut reader = {
         let file_reader = BufReader::new(File::open("tests/fixtures/countries.geojson")?);
         FeatureReader::from_reader(file_reader)
     };
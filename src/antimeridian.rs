@@ -0,0 +1,553 @@
+//! Cutting geometries which [cross the antimeridian](https://tools.ietf.org/html/rfc7946#section-3.1.9).
+//!
+//! Positions are assumed to have longitudes within `[-180, 180]`. Consecutive positions more
+//! than 180° of longitude apart are taken to cross the antimeridian, rather than the long way
+//! around the globe.
+use crate::{Bbox, GeometryValue, LineStringType, PolygonType, Position};
+
+fn crosses(a: &Position, b: &Position) -> bool {
+    a.len() >= 2 && b.len() >= 2 && (b[0] - a[0]).abs() > 180.0
+}
+
+/// The position where the segment from `a` to `b` meets the given longitude, interpolating any
+/// other elements the two positions have in common.
+fn interpolate(a: &Position, b: &Position, longitude: f64) -> Position {
+    let t = (longitude - a[0]) / (b[0] - a[0]);
+    let mut position = vec![longitude];
+    position.extend((1..a.len().min(b.len())).map(|i| a[i] + t * (b[i] - a[i])));
+    Position::from(position)
+}
+
+fn shifted(position: &Position, offset: f64) -> Position {
+    let mut position = position.clone();
+    position[0] += offset;
+    position
+}
+
+fn push_distinct(positions: &mut Vec<Position>, position: Position) {
+    if positions.last() != Some(&position) {
+        positions.push(position);
+    }
+}
+
+fn split_line_string(line_string: &[Position]) -> Option<Vec<LineStringType>> {
+    if !line_string
+        .windows(2)
+        .any(|pair| crosses(&pair[0], &pair[1]))
+    {
+        return None;
+    }
+    let mut parts = vec![];
+    let mut part = vec![];
+    for (idx, position) in line_string.iter().enumerate() {
+        if let Some(previous) = idx.checked_sub(1).map(|idx| &line_string[idx])
+            && crosses(previous, position)
+        {
+            // Going east over the antimeridian if we were in the eastern hemisphere
+            let (exit, offset) = if previous[0] > 0.0 {
+                (180.0, 360.0)
+            } else {
+                (-180.0, -360.0)
+            };
+            let crossing = interpolate(previous, &shifted(position, offset), exit);
+            push_distinct(&mut part, crossing.clone());
+            parts.push(std::mem::take(&mut part));
+            part.push(shifted(&crossing, -offset));
+        }
+        push_distinct(&mut part, position.clone());
+    }
+    parts.push(part);
+    parts.retain(|part| part.len() >= 2);
+    Some(parts)
+}
+
+/// Make the longitudes of a closed ring continuous, e.g. `[170, -170]` becomes `[170, 190]`.
+///
+/// Returns `None` if the ring doesn't end where it started, i.e. it circles a pole.
+fn unwrap_ring(ring: &[Position]) -> Option<Vec<Position>> {
+    let mut offset = 0.0;
+    let mut unwrapped: Vec<Position> = Vec::with_capacity(ring.len());
+    for (idx, position) in ring.iter().enumerate() {
+        if idx > 0 {
+            let delta = position[0] - ring[idx - 1][0];
+            if delta > 180.0 {
+                offset -= 360.0;
+            } else if delta < -180.0 {
+                offset += 360.0;
+            }
+        }
+        unwrapped.push(shifted(position, offset));
+    }
+    (offset == 0.0).then_some(unwrapped)
+}
+
+/// Clip an open ring to one side of a meridian, using the Sutherland–Hodgman algorithm.
+fn clip_ring(ring: &[Position], longitude: f64, keep_west: bool) -> Vec<Position> {
+    let inside = |position: &Position| {
+        if keep_west {
+            position[0] <= longitude
+        } else {
+            position[0] >= longitude
+        }
+    };
+    let mut clipped = vec![];
+    for (idx, current) in ring.iter().enumerate() {
+        let previous = &ring[(idx + ring.len() - 1) % ring.len()];
+        match (inside(previous), inside(current)) {
+            (true, true) => clipped.push(current.clone()),
+            (true, false) => clipped.push(interpolate(previous, current, longitude)),
+            (false, true) => {
+                clipped.push(interpolate(previous, current, longitude));
+                clipped.push(current.clone());
+            }
+            (false, false) => {}
+        }
+    }
+    clipped
+}
+
+/// The part of an unwrapped, closed ring which falls within `[-180, 180]` after shifting it by
+/// `offset`, or `None` if there's nothing left.
+fn clip_ring_to_world(ring: &[Position], offset: f64) -> Option<Vec<Position>> {
+    let open: Vec<Position> = ring[..ring.len() - 1]
+        .iter()
+        .map(|position| shifted(position, offset))
+        .collect();
+    let clipped = clip_ring(&clip_ring(&open, -180.0, false), 180.0, true);
+
+    let mut ring = Vec::with_capacity(clipped.len() + 1);
+    for position in clipped {
+        push_distinct(&mut ring, position);
+    }
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if ring.len() < 3 {
+        return None;
+    }
+    ring.push(ring[0].clone());
+    Some(ring)
+}
+
+/// A copy of `ring`, closed if its last position isn't its first.
+fn close_ring(ring: &[Position]) -> Vec<Position> {
+    let mut ring = ring.to_vec();
+    if ring.len() > 1 && ring.first() != ring.last() {
+        ring.push(ring[0].clone());
+    }
+    ring
+}
+
+fn longitude_range(ring: &[Position]) -> (f64, f64) {
+    ring.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max), position| (min.min(position[0]), max.max(position[0])),
+    )
+}
+
+fn split_polygon(polygon: &[Vec<Position>]) -> Option<Vec<PolygonType>> {
+    let exterior = close_ring(polygon.first()?);
+    if exterior.len() < 4 || !exterior.windows(2).any(|pair| crosses(&pair[0], &pair[1])) {
+        return None;
+    }
+    if polygon.iter().flatten().any(|position| position.len() < 2) {
+        return None;
+    }
+    let exterior = unwrap_ring(&exterior)?;
+    let (west, east) = longitude_range(&exterior);
+    let center = (west + east) / 2.0;
+
+    let mut rings = vec![exterior];
+    for hole in &polygon[1..] {
+        let Some(mut hole) = unwrap_ring(&close_ring(hole)).filter(|hole| !hole.is_empty()) else {
+            continue;
+        };
+        // Put the hole on the same side of the antimeridian as the exterior
+        let (hole_west, hole_east) = longitude_range(&hole);
+        let offset = ((center - (hole_west + hole_east) / 2.0) / 360.0).round() * 360.0;
+        hole.iter_mut().for_each(|position| position[0] += offset);
+        rings.push(hole);
+    }
+
+    let offsets = [
+        (west < -180.0).then_some(360.0),
+        Some(0.0),
+        (east > 180.0).then_some(-360.0),
+    ];
+    let polygons = offsets
+        .into_iter()
+        .flatten()
+        .filter_map(|offset| {
+            let mut rings = rings.iter();
+            let exterior = clip_ring_to_world(rings.next()?, offset)?;
+            Some(
+                std::iter::once(exterior)
+                    .chain(rings.filter_map(|hole| clip_ring_to_world(hole, offset)))
+                    .collect(),
+            )
+        })
+        .collect();
+    Some(polygons)
+}
+
+impl GeometryValue {
+    /// Cut any `LineString`, `Polygon`, `MultiLineString` or `MultiPolygon` which crosses the
+    /// antimeridian into parts on either side of it, as a `MultiLineString` or `MultiPolygon`.
+    ///
+    /// Geometries which don't cross the antimeridian are left as they are, as are any polygons
+    /// which surround a pole. This includes any nested `GeometryCollection`s. Positions with
+    /// fewer than two elements never cross the antimeridian, and polygons containing them are
+    /// left as they are.
+    ///
+    /// A polygon whose exterior crosses the antimeridian more than twice may be cut into rings
+    /// which touch themselves along the antimeridian.
+    ///
+    /// [GeoJSON Format Specification § 3.1.9](https://tools.ietf.org/html/rfc7946#section-3.1.9)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let mut flight_path = GeometryValue::new_line_string([[170.0, 40.0], [-170.0, 50.0]]);
+    /// flight_path.split_antimeridian();
+    /// assert_eq!(
+    ///     flight_path,
+    ///     GeometryValue::new_multi_line_string([
+    ///         [[170.0, 40.0], [180.0, 45.0]],
+    ///         [[-180.0, 45.0], [-170.0, 50.0]],
+    ///     ])
+    /// );
+    /// ```
+    pub fn split_antimeridian(&mut self) {
+        match self {
+            GeometryValue::LineString { coordinates } => {
+                if let Some(parts) = split_line_string(coordinates) {
+                    *self = GeometryValue::MultiLineString { coordinates: parts };
+                }
+            }
+            GeometryValue::MultiLineString { coordinates } => {
+                if coordinates.iter().any(|line_string| {
+                    line_string
+                        .windows(2)
+                        .any(|pair| crosses(&pair[0], &pair[1]))
+                }) {
+                    *coordinates = std::mem::take(coordinates)
+                        .into_iter()
+                        .flat_map(|line_string| {
+                            split_line_string(&line_string).unwrap_or_else(|| vec![line_string])
+                        })
+                        .collect();
+                }
+            }
+            GeometryValue::Polygon { coordinates } => {
+                if let Some(polygons) = split_polygon(coordinates) {
+                    *self = GeometryValue::MultiPolygon {
+                        coordinates: polygons,
+                    };
+                }
+            }
+            GeometryValue::MultiPolygon { coordinates } => {
+                *coordinates = std::mem::take(coordinates)
+                    .into_iter()
+                    .flat_map(|polygon| split_polygon(&polygon).unwrap_or_else(|| vec![polygon]))
+                    .collect();
+            }
+            GeometryValue::GeometryCollection { geometries } => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.value.split_antimeridian()),
+            GeometryValue::Point { .. } | GeometryValue::MultiPoint { .. } => {}
+        }
+    }
+
+    /// Compute the bounding box of this geometry, allowing it to cross the antimeridian.
+    ///
+    /// Unlike [`GeometryValue::compute_bbox`], the west edge is placed to leave the largest
+    /// possible gap in longitude between the geometry's positions. If that gap includes the
+    /// antimeridian, the bbox is the same as [`GeometryValue::compute_bbox`], otherwise its west
+    /// edge is greater than its east edge.
+    ///
+    /// [GeoJSON Format Specification § 5.2](https://tools.ietf.org/html/rfc7946#section-5.2)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let fiji = GeometryValue::new_multi_point([[177.0, -17.0], [-179.8, -16.2]]);
+    /// assert_eq!(
+    ///     fiji.compute_antimeridian_bbox(),
+    ///     Some(vec![177.0, -17.0, -179.8, -16.2])
+    /// );
+    /// ```
+    pub fn compute_antimeridian_bbox(&self) -> Option<Bbox> {
+        let mut bbox = self.compute_bbox()?;
+        let mut longitudes: Vec<f64> = self
+            .coords()
+            .filter(|position| position.len() >= 2)
+            .map(|position| position[0])
+            .collect();
+        longitudes.sort_by(f64::total_cmp);
+
+        // The gap "around the back" of the globe, which a bbox with west <= east leaves empty
+        let mut widest_gap = longitudes[0] + 360.0 - longitudes[longitudes.len() - 1];
+        let mut west_east = None;
+        for pair in longitudes.windows(2) {
+            let gap = pair[1] - pair[0];
+            if gap > widest_gap {
+                widest_gap = gap;
+                west_east = Some((pair[1], pair[0]));
+            }
+        }
+        if let Some((west, east)) = west_east {
+            let dimensions = bbox.len() / 2;
+            bbox[0] = west;
+            bbox[dimensions] = east;
+        }
+        Some(bbox)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Geometry;
+
+    #[test]
+    fn line_string_without_crossing() {
+        let original = GeometryValue::new_line_string([[-10.0, 0.0], [170.0, 10.0]]);
+        let mut line_string = original.clone();
+        line_string.split_antimeridian();
+        assert_eq!(line_string, original);
+        assert_eq!(
+            line_string.compute_antimeridian_bbox(),
+            Some(vec![-10.0, 0.0, 170.0, 10.0])
+        );
+    }
+
+    #[test]
+    fn line_string_crossing_twice() {
+        let mut line_string = GeometryValue::new_line_string([
+            [-175.0, 0.0, 10.0],
+            [175.0, 10.0, 20.0],
+            [170.0, 10.0, 20.0],
+            [-170.0, 0.0, 30.0],
+        ]);
+        line_string.split_antimeridian();
+        assert_eq!(
+            line_string,
+            GeometryValue::new_multi_line_string([
+                vec![[-175.0, 0.0, 10.0], [-180.0, 5.0, 15.0]],
+                vec![
+                    [180.0, 5.0, 15.0],
+                    [175.0, 10.0, 20.0],
+                    [170.0, 10.0, 20.0],
+                    [180.0, 5.0, 25.0],
+                ],
+                vec![[-180.0, 5.0, 25.0], [-170.0, 0.0, 30.0]],
+            ])
+        );
+        assert_eq!(
+            line_string.compute_antimeridian_bbox(),
+            Some(vec![170.0, 0.0, 10.0, -170.0, 10.0, 30.0])
+        );
+    }
+
+    #[test]
+    fn line_string_starting_on_antimeridian() {
+        let mut line_string =
+            GeometryValue::new_line_string([[180.0, 0.0], [-170.0, 0.0], [-160.0, 0.0]]);
+        line_string.split_antimeridian();
+        assert_eq!(
+            line_string,
+            GeometryValue::new_multi_line_string([[[-180.0, 0.0], [-170.0, 0.0], [-160.0, 0.0]]])
+        );
+    }
+
+    #[test]
+    fn multi_line_string() {
+        let mut multi_line_string = GeometryValue::new_multi_line_string([
+            vec![[0.0, 0.0], [1.0, 1.0]],
+            vec![[-170.0, 0.0], [170.0, 0.0]],
+        ]);
+        multi_line_string.split_antimeridian();
+        assert_eq!(
+            multi_line_string,
+            GeometryValue::new_multi_line_string([
+                vec![[0.0, 0.0], [1.0, 1.0]],
+                vec![[-170.0, 0.0], [-180.0, 0.0]],
+                vec![[180.0, 0.0], [170.0, 0.0]],
+            ])
+        );
+    }
+
+    #[test]
+    fn polygon() {
+        let mut polygon = GeometryValue::new_polygon([
+            vec![
+                [170.0, -10.0],
+                [-170.0, -10.0],
+                [-170.0, 10.0],
+                [170.0, 10.0],
+                [170.0, -10.0],
+            ],
+            // A hole on the western side of the antimeridian
+            vec![
+                [-178.0, -2.0],
+                [-178.0, 2.0],
+                [-175.0, 2.0],
+                [-175.0, -2.0],
+                [-178.0, -2.0],
+            ],
+        ]);
+        assert!(polygon.is_winding_valid());
+        polygon.split_antimeridian();
+        assert_eq!(
+            polygon,
+            GeometryValue::new_multi_polygon([
+                vec![vec![
+                    [170.0, -10.0],
+                    [180.0, -10.0],
+                    [180.0, 10.0],
+                    [170.0, 10.0],
+                    [170.0, -10.0],
+                ]],
+                vec![
+                    vec![
+                        [-180.0, -10.0],
+                        [-170.0, -10.0],
+                        [-170.0, 10.0],
+                        [-180.0, 10.0],
+                        [-180.0, -10.0],
+                    ],
+                    vec![
+                        [-178.0, -2.0],
+                        [-178.0, 2.0],
+                        [-175.0, 2.0],
+                        [-175.0, -2.0],
+                        [-178.0, -2.0],
+                    ],
+                ],
+            ])
+        );
+        assert!(polygon.is_winding_valid());
+        assert_eq!(
+            polygon.compute_antimeridian_bbox(),
+            Some(vec![170.0, -10.0, -170.0, 10.0])
+        );
+    }
+
+    #[test]
+    fn unclosed_polygon() {
+        let mut polygon = GeometryValue::new_polygon([[
+            [170.0, -10.0],
+            [-170.0, -10.0],
+            [-170.0, 10.0],
+            [170.0, 10.0],
+        ]]);
+        polygon.split_antimeridian();
+        assert_eq!(
+            polygon,
+            GeometryValue::new_multi_polygon([
+                [[
+                    [170.0, -10.0],
+                    [180.0, -10.0],
+                    [180.0, 10.0],
+                    [170.0, 10.0],
+                    [170.0, -10.0],
+                ]],
+                [[
+                    [-180.0, -10.0],
+                    [-170.0, -10.0],
+                    [-170.0, 10.0],
+                    [-180.0, 10.0],
+                    [-180.0, -10.0],
+                ]],
+            ])
+        );
+    }
+
+    #[test]
+    fn multi_polygon() {
+        let square = |west: f64, east: f64| {
+            vec![
+                [west, -10.0],
+                [east, -10.0],
+                [east, 10.0],
+                [west, 10.0],
+                [west, -10.0],
+            ]
+        };
+        let mut multi_polygon = GeometryValue::new_multi_polygon([
+            vec![square(170.0, -170.0)],
+            vec![square(0.0, 10.0)],
+        ]);
+        assert!(multi_polygon.is_winding_valid());
+        multi_polygon.split_antimeridian();
+        assert_eq!(
+            multi_polygon,
+            GeometryValue::new_multi_polygon([
+                vec![square(170.0, 180.0)],
+                vec![square(-180.0, -170.0)],
+                vec![square(0.0, 10.0)],
+            ])
+        );
+        assert!(multi_polygon.is_winding_valid());
+    }
+
+    #[test]
+    fn polygon_around_pole() {
+        let original = GeometryValue::new_polygon([[
+            [-180.0, 80.0],
+            [-60.0, 80.0],
+            [60.0, 80.0],
+            [180.0, 80.0],
+            [-180.0, 80.0],
+        ]]);
+        let mut polygon = original.clone();
+        polygon.split_antimeridian();
+        assert_eq!(polygon, original);
+    }
+
+    #[test]
+    fn short_positions() {
+        let original = GeometryValue::new_line_string([
+            Position::from(vec![170.0, 0.0]),
+            Position::from(vec![]),
+            Position::from(vec![-170.0]),
+        ]);
+        let mut line_string = original.clone();
+        line_string.split_antimeridian();
+        assert_eq!(line_string, original);
+
+        let original = GeometryValue::new_polygon([vec![
+            Position::from(vec![170.0, -10.0]),
+            Position::from(vec![-170.0, -10.0]),
+            Position::from(vec![]),
+            Position::from(vec![170.0, 10.0]),
+            Position::from(vec![170.0, -10.0]),
+        ]]);
+        let mut polygon = original.clone();
+        polygon.split_antimeridian();
+        assert_eq!(polygon, original);
+    }
+
+    #[test]
+    fn geometry_collection() {
+        let mut collection = GeometryValue::new_geometry_collection([
+            Geometry::new_point([179.0, 0.0]),
+            Geometry::new_line_string([[179.0, 0.0], [-179.0, 0.0]]),
+        ]);
+        collection.split_antimeridian();
+        assert_eq!(
+            collection,
+            GeometryValue::new_geometry_collection([
+                Geometry::new_point([179.0, 0.0]),
+                Geometry::new_multi_line_string([
+                    [[179.0, 0.0], [180.0, 0.0]],
+                    [[-180.0, 0.0], [-179.0, 0.0]],
+                ]),
+            ])
+        );
+    }
+}
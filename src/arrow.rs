@@ -0,0 +1,1087 @@
+//! Conversion between GeoJSON features and [Apache Arrow](https://arrow.apache.org) record batches.
+//!
+//! Each feature becomes a row. Its geometry goes in a `geometry` column in a
+//! [GeoArrow](https://geoarrow.org) native layout when every geometry is of the same kind, e.g. all
+//! `Polygon`s, or all `Polygon`s and `MultiPolygon`s, and as WKB otherwise. Its properties go in a
+//! column each, typed by the values of that property:
+//!
+//! | Values                            | Column type                                   |
+//! |-----------------------------------|-----------------------------------------------|
+//! | booleans                          | `Boolean`                                     |
+//! | integers                          | `Int64`                                       |
+//! | numbers                           | `Float64`                                     |
+//! | strings                           | `Utf8`                                        |
+//! | arrays, objects, or a mix of types | `Utf8` as the `arrow.json` extension type     |
+//!
+//! Feature `id`s, bounding boxes and foreign members aren't kept, and a `null` property is the
+//! same as a missing one.
+//!
+//! Every position of a native geometry column has the same number of coordinates, so if some
+//! geometries are 2D and others 3D, the column is WKB instead, which keeps the dimensions of each
+//! geometry. Neither can hold a position with more than 4 coordinates, or a geometry whose positions
+//! have different numbers of coordinates, so rather than drop coordinates these are an error.
+//!
+//! # Examples
+//!
+//! ```
+//! use geojson::FeatureCollection;
+//!
+//! let collection: FeatureCollection = r#"{
+//!     "type": "FeatureCollection",
+//!     "features": [{
+//!         "type": "Feature",
+//!         "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+//!         "properties": { "name": "Dinagat Islands", "population": 127152 }
+//!     }]
+//! }"#.parse().unwrap();
+//!
+//! let batch = collection.to_record_batch().unwrap();
+//! assert_eq!(batch.num_columns(), 3);
+//! assert_eq!(FeatureCollection::from_record_batches([&batch]).unwrap(), collection);
+//! ```
+use crate::{
+    Error, Feature, FeatureCollection, FeatureWriter, Geometry, GeometryValue, JsonObject,
+};
+use crate::{JsonValue, Position, Result, util};
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type, UInt16Type,
+    UInt32Type, UInt64Type,
+};
+use arrow_array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, FixedSizeListArray, Float64Array, Int64Array,
+    ListArray, NullArray, RecordBatch, StringArray,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::ops::Range;
+use std::sync::Arc;
+
+const EXTENSION_NAME: &str = "ARROW:extension:name";
+const EXTENSION_METADATA: &str = "ARROW:extension:metadata";
+const JSON_EXTENSION: &str = "arrow.json";
+const GEOMETRY_COLUMN: &str = "geometry";
+
+/// Converts a stream of features into record batches.
+///
+/// The schema is inferred from the first batch, and every later batch must fit it: a property
+/// which wasn't seen in the first batch, or whose values don't fit its column, is an error. Use
+/// a larger [batch size](RecordBatches::with_batch_size) to infer the schema from more features.
+/// Likewise, a geometry which doesn't fit the first batch's geometry column, e.g. a 3D `Point`
+/// after only 2D ones, is an error.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::FeatureReader;
+/// use geojson::arrow::RecordBatches;
+///
+/// let input = r#"{
+///     "type": "FeatureCollection",
+///     "features": [
+///         { "type": "Feature", "geometry": null, "properties": { "rank": 1 } },
+///         { "type": "Feature", "geometry": null, "properties": { "rank": 2 } },
+///         { "type": "Feature", "geometry": null, "properties": { "rank": 3 } }
+///     ]
+/// }"#;
+///
+/// let mut reader = FeatureReader::from_reader(input.as_bytes());
+/// let batches = RecordBatches::new(reader.features())
+///     .with_batch_size(2)
+///     .collect::<geojson::Result<Vec<_>>>()
+///     .unwrap();
+/// assert_eq!(batches.len(), 2);
+/// assert_eq!(batches[0].schema(), batches[1].schema());
+/// ```
+pub struct RecordBatches<I> {
+    features: I,
+    batch_size: usize,
+    schema: Option<BatchSchema>,
+}
+
+impl<I: Iterator<Item = Result<Feature>>> RecordBatches<I> {
+    /// Convert `features` into batches of up to 8192 features each.
+    pub fn new(features: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            features: features.into_iter(),
+            batch_size: 8192,
+            schema: None,
+        }
+    }
+
+    /// The greatest number of features in each batch. Defaults to `8192`.
+    ///
+    /// # Panics
+    ///
+    /// If `batch_size` is `0`.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch size must be positive");
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+impl<I: Iterator<Item = Result<Feature>>> Iterator for RecordBatches<I> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut features = Vec::with_capacity(self.batch_size);
+        for feature in self.features.by_ref().take(self.batch_size) {
+            match feature {
+                Ok(feature) => features.push(feature),
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        if features.is_empty() {
+            return None;
+        }
+        let schema = match &self.schema {
+            Some(schema) => schema,
+            None => match BatchSchema::infer(&features) {
+                Ok(schema) => self.schema.insert(schema),
+                Err(error) => return Some(Err(error)),
+            },
+        };
+        Some(schema.build(&features))
+    }
+}
+
+impl FeatureCollection {
+    /// Convert the features into a record batch, as described in the [`arrow`](crate::arrow)
+    /// module.
+    pub fn to_record_batch(&self) -> Result<RecordBatch> {
+        BatchSchema::infer(&self.features)?.build(&self.features)
+    }
+
+    /// Read the features of record batches, as written by
+    /// [`FeatureCollection::to_record_batch`] or [`RecordBatches`].
+    ///
+    /// The geometry is read from the first column with a GeoArrow extension type, in any of its
+    /// native layouts, or as WKB or WKT. Every other column is read as properties.
+    pub fn from_record_batches<'a>(
+        batches: impl IntoIterator<Item = &'a RecordBatch>,
+    ) -> Result<Self> {
+        let mut features = vec![];
+        for batch in batches {
+            read_batch(batch, |feature| {
+                features.push(feature);
+                Ok(())
+            })?;
+        }
+        Ok(FeatureCollection::from_iter(features))
+    }
+}
+
+impl<W: Write> FeatureWriter<W> {
+    /// Write the features of a record batch.
+    ///
+    /// See [`FeatureCollection::from_record_batches`].
+    pub fn write_record_batch(&mut self, batch: &RecordBatch) -> Result<()> {
+        read_batch(batch, |feature| self.write_feature(&feature))
+    }
+}
+
+fn schema_error(message: String) -> Error {
+    Error::Arrow(ArrowError::SchemaError(message))
+}
+
+/// The number of coordinates of every non-empty position of `value`, or an error if a geometry
+/// column can't hold them all.
+fn geometry_dimensions(value: &GeometryValue) -> Result<usize> {
+    let dimensions = util::coordinate_dimensions(value);
+    match value.coords().find(|position| position.len() > dimensions) {
+        Some(position) if position.len() > 4 => Err(schema_error(format!(
+            "a position with {} coordinates doesn't fit a geometry column",
+            position.len()
+        ))),
+        Some(position) => Err(schema_error(format!(
+            "`{}` has positions with {dimensions} and {} coordinates",
+            value.type_name(),
+            position.len()
+        ))),
+        None => Ok(dimensions),
+    }
+}
+
+/// The columns of the batches of a stream.
+struct BatchSchema {
+    properties: Vec<(String, ColumnType)>,
+    geometry: GeometryLayout,
+    schema: SchemaRef,
+}
+
+impl BatchSchema {
+    fn infer(features: &[Feature]) -> Result<Self> {
+        let mut properties: Vec<(String, ColumnType)> = vec![];
+        let mut indexes = HashMap::new();
+        for (key, value) in features
+            .iter()
+            .flat_map(|feature| feature.properties.iter().flatten())
+        {
+            let index = *indexes.entry(key.as_str()).or_insert_with(|| {
+                properties.push((key.clone(), ColumnType::Null));
+                properties.len() - 1
+            });
+            properties[index].1 = properties[index].1.merge(value);
+        }
+        if indexes.contains_key(GEOMETRY_COLUMN) {
+            return Err(schema_error(format!(
+                "property `{GEOMETRY_COLUMN}` clashes with the geometry column"
+            )));
+        }
+
+        let values = || {
+            features
+                .iter()
+                .filter_map(|feature| feature.geometry.as_ref())
+                .map(|geometry| &geometry.value)
+        };
+        let dimensions = values()
+            .map(geometry_dimensions)
+            .collect::<Result<Vec<_>>>()?;
+        let kind = values()
+            .map(GeometryKind::of)
+            .reduce(GeometryKind::merge)
+            .unwrap_or(GeometryKind::Wkb);
+        let geometry = GeometryLayout {
+            // Only WKB keeps the dimensions of each geometry
+            kind: if dimensions.iter().min() == dimensions.iter().max() {
+                kind
+            } else {
+                GeometryKind::Wkb
+            },
+            dimensions: dimensions.first().copied().unwrap_or(2),
+        };
+
+        let mut fields: Vec<Field> = properties
+            .iter()
+            .map(|(name, column_type)| column_type.field(name))
+            .collect();
+        fields.push(geometry.field());
+        Ok(Self {
+            properties,
+            geometry,
+            schema: Arc::new(Schema::new(fields)),
+        })
+    }
+
+    fn build(&self, features: &[Feature]) -> Result<RecordBatch> {
+        for feature in features {
+            for key in feature.properties.iter().flatten().map(|(key, _)| key) {
+                if !self.properties.iter().any(|(name, _)| name == key) {
+                    return Err(schema_error(format!(
+                        "property `{key}` isn't in the schema"
+                    )));
+                }
+            }
+        }
+        let mut columns = self
+            .properties
+            .iter()
+            .map(|(name, column_type)| column_type.build(name, features))
+            .collect::<Result<Vec<_>>>()?;
+        columns.push(self.geometry.build(features)?);
+        Ok(RecordBatch::try_new(self.schema.clone(), columns)?)
+    }
+}
+
+/// The type of a property's column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ColumnType {
+    /// Only `null`s
+    Null,
+    Boolean,
+    Int64,
+    Float64,
+    Utf8,
+    /// A mix of types, or arrays or objects, written as JSON
+    Json,
+}
+
+impl ColumnType {
+    /// The type of a column which holds `value` as well as the values of this one.
+    fn merge(self, value: &JsonValue) -> Self {
+        use ColumnType::*;
+        match (self, value) {
+            (_, JsonValue::Null) => self,
+            (Null | Boolean, JsonValue::Bool(_)) => Boolean,
+            (Null | Int64, JsonValue::Number(number)) if number.is_i64() => Int64,
+            (Null | Int64 | Float64, JsonValue::Number(_)) => Float64,
+            (Null | Utf8, JsonValue::String(_)) => Utf8,
+            _ => Json,
+        }
+    }
+
+    fn field(&self, name: &str) -> Field {
+        let data_type = match self {
+            ColumnType::Null => DataType::Null,
+            ColumnType::Boolean => DataType::Boolean,
+            ColumnType::Int64 => DataType::Int64,
+            ColumnType::Float64 => DataType::Float64,
+            ColumnType::Utf8 | ColumnType::Json => DataType::Utf8,
+        };
+        let field = Field::new(name, data_type, true);
+        if *self == ColumnType::Json {
+            field.with_metadata(HashMap::from([(
+                EXTENSION_NAME.to_string(),
+                JSON_EXTENSION.to_string(),
+            )]))
+        } else {
+            field
+        }
+    }
+
+    fn build(&self, name: &str, features: &[Feature]) -> Result<ArrayRef> {
+        fn values<T>(
+            name: &str,
+            features: &[Feature],
+            column_type: ColumnType,
+            convert: impl Fn(&JsonValue) -> Option<T>,
+        ) -> Result<Vec<Option<T>>> {
+            features
+                .iter()
+                .map(|feature| {
+                    match feature.properties.as_ref().and_then(|properties| properties.get(name)) {
+                        None | Some(JsonValue::Null) => Ok(None),
+                        Some(value) => convert(value).map(Some).ok_or_else(|| {
+                            schema_error(format!(
+                                "value `{value}` of property `{name}` doesn't fit its {column_type:?} column"
+                            ))
+                        }),
+                    }
+                })
+                .collect()
+        }
+        Ok(match self {
+            ColumnType::Null => {
+                values(name, features, *self, |_| None::<()>)?;
+                Arc::new(NullArray::new(features.len()))
+            }
+            ColumnType::Boolean => Arc::new(BooleanArray::from(values(
+                name,
+                features,
+                *self,
+                JsonValue::as_bool,
+            )?)),
+            ColumnType::Int64 => Arc::new(Int64Array::from(values(
+                name,
+                features,
+                *self,
+                JsonValue::as_i64,
+            )?)),
+            ColumnType::Float64 => Arc::new(Float64Array::from(values(
+                name,
+                features,
+                *self,
+                JsonValue::as_f64,
+            )?)),
+            ColumnType::Utf8 => {
+                Arc::new(StringArray::from(values(name, features, *self, |value| {
+                    value.as_str().map(str::to_string)
+                })?))
+            }
+            ColumnType::Json => {
+                Arc::new(StringArray::from(values(name, features, *self, |value| {
+                    Some(value.to_string())
+                })?))
+            }
+        })
+    }
+}
+
+/// The GeoArrow geometry types.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GeometryKind {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    Wkb,
+}
+
+impl GeometryKind {
+    fn of(value: &GeometryValue) -> Self {
+        match value {
+            GeometryValue::Point { .. } => GeometryKind::Point,
+            GeometryValue::MultiPoint { .. } => GeometryKind::MultiPoint,
+            GeometryValue::LineString { .. } => GeometryKind::LineString,
+            GeometryValue::MultiLineString { .. } => GeometryKind::MultiLineString,
+            GeometryValue::Polygon { .. } => GeometryKind::Polygon,
+            GeometryValue::MultiPolygon { .. } => GeometryKind::MultiPolygon,
+            GeometryValue::GeometryCollection { .. } => GeometryKind::Wkb,
+        }
+    }
+
+    /// The kind which can hold geometries of both kinds.
+    fn merge(self, other: Self) -> Self {
+        use GeometryKind::*;
+        match (self, other) {
+            _ if self == other => self,
+            (Point | MultiPoint, Point | MultiPoint) => MultiPoint,
+            (LineString | MultiLineString, LineString | MultiLineString) => MultiLineString,
+            (Polygon | MultiPolygon, Polygon | MultiPolygon) => MultiPolygon,
+            _ => Wkb,
+        }
+    }
+
+    fn extension_name(&self) -> &'static str {
+        match self {
+            GeometryKind::Point => "geoarrow.point",
+            GeometryKind::LineString => "geoarrow.linestring",
+            GeometryKind::Polygon => "geoarrow.polygon",
+            GeometryKind::MultiPoint => "geoarrow.multipoint",
+            GeometryKind::MultiLineString => "geoarrow.multilinestring",
+            GeometryKind::MultiPolygon => "geoarrow.multipolygon",
+            GeometryKind::Wkb => "geoarrow.wkb",
+        }
+    }
+
+    fn from_extension_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "geoarrow.point" => GeometryKind::Point,
+            "geoarrow.linestring" => GeometryKind::LineString,
+            "geoarrow.polygon" => GeometryKind::Polygon,
+            "geoarrow.multipoint" => GeometryKind::MultiPoint,
+            "geoarrow.multilinestring" => GeometryKind::MultiLineString,
+            "geoarrow.multipolygon" => GeometryKind::MultiPolygon,
+            "geoarrow.wkb" => GeometryKind::Wkb,
+            _ => return None,
+        })
+    }
+
+    /// The names of the nested lists of a native layout, outermost first.
+    fn list_names(&self) -> &'static [&'static str] {
+        match self {
+            GeometryKind::Point | GeometryKind::Wkb => &[],
+            GeometryKind::LineString => &["vertices"],
+            GeometryKind::MultiPoint => &["points"],
+            GeometryKind::Polygon => &["rings", "vertices"],
+            GeometryKind::MultiLineString => &["linestrings", "vertices"],
+            GeometryKind::MultiPolygon => &["polygons", "rings", "vertices"],
+        }
+    }
+}
+
+/// The layout of a geometry column.
+struct GeometryLayout {
+    kind: GeometryKind,
+    dimensions: usize,
+}
+
+impl GeometryLayout {
+    fn coordinates_field(&self) -> Field {
+        let name = match self.dimensions {
+            2 => "xy",
+            3 => "xyz",
+            _ => "xyzm",
+        };
+        Field::new(name, DataType::Float64, false)
+    }
+
+    fn field(&self) -> Field {
+        let data_type = if self.kind == GeometryKind::Wkb {
+            DataType::Binary
+        } else {
+            let coordinates =
+                DataType::FixedSizeList(Arc::new(self.coordinates_field()), self.dimensions as i32);
+            self.kind
+                .list_names()
+                .iter()
+                .rev()
+                .fold(coordinates, |data_type, name| {
+                    DataType::List(Arc::new(Field::new(*name, data_type, false)))
+                })
+        };
+        Field::new(GEOMETRY_COLUMN, data_type, true).with_metadata(HashMap::from([
+            (
+                EXTENSION_NAME.to_string(),
+                self.kind.extension_name().to_string(),
+            ),
+            (
+                EXTENSION_METADATA.to_string(),
+                r#"{"crs":"OGC:CRS84","crs_type":"authority_code"}"#.to_string(),
+            ),
+        ]))
+    }
+
+    fn build(&self, features: &[Feature]) -> Result<ArrayRef> {
+        if self.kind == GeometryKind::Wkb {
+            let wkb = features
+                .iter()
+                .map(|feature| {
+                    feature
+                        .geometry
+                        .as_ref()
+                        .map(|geometry| {
+                            geometry_dimensions(&geometry.value)?;
+                            geometry.to_wkb()
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>>>()?;
+            return Ok(Arc::new(BinaryArray::from_iter(wkb)));
+        }
+
+        let values = features
+            .iter()
+            .map(|feature| feature.geometry.as_ref().map(|geometry| &geometry.value));
+
+        let mut builder = NativeBuilder {
+            dimensions: self.dimensions,
+            coordinates: vec![],
+            offsets: vec![vec![0]; self.kind.list_names().len()],
+        };
+        let mut validity = vec![];
+        for value in values {
+            validity.push(value.is_some());
+            builder.push(self.kind, value)?;
+        }
+
+        let nulls = Some(NullBuffer::from(validity)).filter(|nulls| nulls.null_count() > 0);
+        let coordinates = Arc::new(Float64Array::from(builder.coordinates));
+        let mut array: ArrayRef = Arc::new(FixedSizeListArray::try_new(
+            Arc::new(self.coordinates_field()),
+            self.dimensions as i32,
+            coordinates,
+            if self.kind == GeometryKind::Point {
+                nulls.clone()
+            } else {
+                None
+            },
+        )?);
+        let names = self.kind.list_names();
+        for (level, offsets) in builder.offsets.into_iter().enumerate().rev() {
+            array = Arc::new(ListArray::try_new(
+                Arc::new(Field::new(names[level], array.data_type().clone(), false)),
+                OffsetBuffer::new(offsets.into()),
+                array,
+                if level == 0 { nulls.clone() } else { None },
+            )?);
+        }
+        Ok(array)
+    }
+}
+
+/// Builds the coordinates and offsets of a native GeoArrow array.
+struct NativeBuilder {
+    dimensions: usize,
+    coordinates: Vec<f64>,
+    /// The offsets of each level of nested lists, outermost first
+    offsets: Vec<Vec<i32>>,
+}
+
+impl NativeBuilder {
+    fn push(&mut self, kind: GeometryKind, value: Option<&GeometryValue>) -> Result<()> {
+        use std::slice::from_ref;
+        match (kind, value) {
+            (GeometryKind::Point, None) => self.push_position(&Position::from(vec![])),
+            (_, None) => self.push_count(0, 0),
+            (GeometryKind::Point, Some(GeometryValue::Point { coordinates })) => {
+                self.push_position(coordinates)
+            }
+            (GeometryKind::LineString, Some(GeometryValue::LineString { coordinates }))
+            | (GeometryKind::MultiPoint, Some(GeometryValue::MultiPoint { coordinates })) => {
+                self.push_positions(0, coordinates)
+            }
+            (GeometryKind::MultiPoint, Some(GeometryValue::Point { coordinates })) => {
+                self.push_positions(0, from_ref(coordinates))
+            }
+            (GeometryKind::Polygon, Some(GeometryValue::Polygon { coordinates }))
+            | (
+                GeometryKind::MultiLineString,
+                Some(GeometryValue::MultiLineString { coordinates }),
+            ) => self.push_lines(0, coordinates),
+            (GeometryKind::MultiLineString, Some(GeometryValue::LineString { coordinates })) => {
+                self.push_lines(0, from_ref(coordinates))
+            }
+            (GeometryKind::MultiPolygon, Some(GeometryValue::MultiPolygon { coordinates })) => {
+                self.push_polygons(0, coordinates)
+            }
+            (GeometryKind::MultiPolygon, Some(GeometryValue::Polygon { coordinates })) => {
+                self.push_polygons(0, from_ref(coordinates))
+            }
+            (_, Some(value)) => Err(schema_error(format!(
+                "`{}` doesn't fit the {} geometry column",
+                value.type_name(),
+                kind.extension_name()
+            ))),
+        }
+    }
+
+    /// Write a position, or `NaN`s for an empty one.
+    fn push_position(&mut self, position: &Position) -> Result<()> {
+        if position.is_empty() {
+            self.coordinates
+                .extend(std::iter::repeat_n(f64::NAN, self.dimensions));
+        } else if position.len() < self.dimensions {
+            return Err(Error::PositionTooShort(position.len()));
+        } else if position.len() > self.dimensions {
+            return Err(schema_error(format!(
+                "a position with {} coordinates doesn't fit the {}D geometry column",
+                position.len(),
+                self.dimensions
+            )));
+        } else {
+            self.coordinates.extend_from_slice(position.as_slice());
+        }
+        Ok(())
+    }
+
+    fn push_count(&mut self, level: usize, count: usize) -> Result<()> {
+        let offsets = &mut self.offsets[level];
+        let offset = i32::try_from(count)
+            .ok()
+            .and_then(|count| offsets.last().unwrap().checked_add(count))
+            .ok_or_else(|| Error::Arrow(ArrowError::OffsetOverflowError(count)))?;
+        offsets.push(offset);
+        Ok(())
+    }
+
+    fn push_positions(&mut self, level: usize, positions: &[Position]) -> Result<()> {
+        self.push_count(level, positions.len())?;
+        positions
+            .iter()
+            .try_for_each(|position| self.push_position(position))
+    }
+
+    fn push_lines(&mut self, level: usize, lines: &[Vec<Position>]) -> Result<()> {
+        self.push_count(level, lines.len())?;
+        lines
+            .iter()
+            .try_for_each(|line| self.push_positions(level + 1, line))
+    }
+
+    fn push_polygons(&mut self, level: usize, polygons: &[Vec<Vec<Position>>]) -> Result<()> {
+        self.push_count(level, polygons.len())?;
+        polygons
+            .iter()
+            .try_for_each(|polygon| self.push_lines(level + 1, polygon))
+    }
+}
+
+fn read_batch(batch: &RecordBatch, mut f: impl FnMut(Feature) -> Result<()>) -> Result<()> {
+    let schema = batch.schema();
+    let geometry_column = schema.fields().iter().position(|field| {
+        field
+            .metadata()
+            .get(EXTENSION_NAME)
+            .is_some_and(|name| name.starts_with("geoarrow."))
+    });
+    for row in 0..batch.num_rows() {
+        let mut properties = JsonObject::new();
+        let mut geometry = None;
+        for (index, (field, column)) in schema.fields().iter().zip(batch.columns()).enumerate() {
+            if Some(index) == geometry_column {
+                geometry = read_geometry(field, column.as_ref(), row)?.map(Geometry::new);
+            } else if let Some(value) = read_property(field, column.as_ref(), row)? {
+                properties.insert(field.name().clone(), value);
+            }
+        }
+        f(Feature {
+            bbox: None,
+            geometry,
+            id: None,
+            properties: Some(properties),
+            foreign_members: None,
+        })?;
+    }
+    Ok(())
+}
+
+fn unsupported(field: &Field) -> Error {
+    Error::Arrow(ArrowError::NotYetImplemented(format!(
+        "reading column `{}` of type {}",
+        field.name(),
+        field.data_type()
+    )))
+}
+
+fn read_property(field: &Field, array: &dyn Array, row: usize) -> Result<Option<JsonValue>> {
+    if array.is_null(row) {
+        return Ok(None);
+    }
+    let is_json = field.metadata().get(EXTENSION_NAME).map(String::as_str) == Some(JSON_EXTENSION);
+    let string = |value: &str| -> Result<JsonValue> {
+        Ok(if is_json {
+            serde_json::from_str(value)?
+        } else {
+            JsonValue::from(value)
+        })
+    };
+    Ok(Some(match array.data_type() {
+        DataType::Null => return Ok(None),
+        DataType::Boolean => JsonValue::from(array.as_boolean().value(row)),
+        DataType::Int8 => JsonValue::from(array.as_primitive::<Int8Type>().value(row)),
+        DataType::Int16 => JsonValue::from(array.as_primitive::<Int16Type>().value(row)),
+        DataType::Int32 => JsonValue::from(array.as_primitive::<Int32Type>().value(row)),
+        DataType::Int64 => JsonValue::from(array.as_primitive::<Int64Type>().value(row)),
+        DataType::UInt8 => JsonValue::from(array.as_primitive::<UInt8Type>().value(row)),
+        DataType::UInt16 => JsonValue::from(array.as_primitive::<UInt16Type>().value(row)),
+        DataType::UInt32 => JsonValue::from(array.as_primitive::<UInt32Type>().value(row)),
+        DataType::UInt64 => JsonValue::from(array.as_primitive::<UInt64Type>().value(row)),
+        DataType::Float32 => JsonValue::from(array.as_primitive::<Float32Type>().value(row)),
+        DataType::Float64 => JsonValue::from(array.as_primitive::<Float64Type>().value(row)),
+        DataType::Utf8 => string(array.as_string::<i32>().value(row))?,
+        DataType::LargeUtf8 => string(array.as_string::<i64>().value(row))?,
+        DataType::Utf8View => string(array.as_string_view().value(row))?,
+        _ => return Err(unsupported(field)),
+    }))
+}
+
+fn read_geometry(field: &Field, array: &dyn Array, row: usize) -> Result<Option<GeometryValue>> {
+    if array.is_null(row) {
+        return Ok(None);
+    }
+    let name = field.metadata().get(EXTENSION_NAME).map(String::as_str);
+    if name == Some("geoarrow.wkt") {
+        let wkt = match array.data_type() {
+            DataType::Utf8 => array.as_string::<i32>().value(row),
+            DataType::LargeUtf8 => array.as_string::<i64>().value(row),
+            DataType::Utf8View => array.as_string_view().value(row),
+            _ => return Err(unsupported(field)),
+        };
+        return GeometryValue::from_wkt(wkt).map(Some);
+    }
+    let Some(kind) = name.and_then(GeometryKind::from_extension_name) else {
+        return Err(unsupported(field));
+    };
+    let error = || unsupported(field);
+    Ok(Some(match kind {
+        GeometryKind::Wkb => {
+            let wkb = match array.data_type() {
+                DataType::Binary => array.as_binary::<i32>().value(row),
+                DataType::LargeBinary => array.as_binary::<i64>().value(row),
+                DataType::BinaryView => array.as_binary_view().value(row),
+                _ => return Err(error()),
+            };
+            Geometry::from_wkb(wkb)?.value
+        }
+        GeometryKind::Point => {
+            GeometryValue::new_point(read_position(array, row).ok_or_else(error)?)
+        }
+        GeometryKind::LineString => {
+            GeometryValue::new_line_string(read_line(array, row).ok_or_else(error)?)
+        }
+        GeometryKind::MultiPoint => {
+            GeometryValue::new_multi_point(read_line(array, row).ok_or_else(error)?)
+        }
+        GeometryKind::Polygon => {
+            GeometryValue::new_polygon(read_lines(array, row).ok_or_else(error)?)
+        }
+        GeometryKind::MultiLineString => {
+            GeometryValue::new_multi_line_string(read_lines(array, row).ok_or_else(error)?)
+        }
+        GeometryKind::MultiPolygon => {
+            let (polygons, range) = list_range(array, row).ok_or_else(error)?;
+            GeometryValue::new_multi_polygon(
+                range
+                    .map(|index| read_lines(polygons.as_ref(), index))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(error)?,
+            )
+        }
+    }))
+}
+
+/// The values of a `List` or `LargeList`, and the range of them in the list at `index`.
+fn list_range(array: &dyn Array, index: usize) -> Option<(ArrayRef, Range<usize>)> {
+    if let Some(list) = array.as_list_opt::<i32>() {
+        let offsets = list.value_offsets();
+        Some((
+            list.values().clone(),
+            offsets[index] as usize..offsets[index + 1] as usize,
+        ))
+    } else {
+        let list = array.as_list_opt::<i64>()?;
+        let offsets = list.value_offsets();
+        Some((
+            list.values().clone(),
+            offsets[index] as usize..offsets[index + 1] as usize,
+        ))
+    }
+}
+
+fn read_lines(array: &dyn Array, index: usize) -> Option<Vec<Vec<Position>>> {
+    let (lines, range) = list_range(array, index)?;
+    range
+        .map(|index| read_line(lines.as_ref(), index))
+        .collect()
+}
+
+fn read_line(array: &dyn Array, index: usize) -> Option<Vec<Position>> {
+    let (positions, range) = list_range(array, index)?;
+    range
+        .map(|index| read_position(positions.as_ref(), index))
+        .collect()
+}
+
+/// Read interleaved or separated coordinates. A position of `NaN`s is read as an empty one.
+fn read_position(array: &dyn Array, index: usize) -> Option<Position> {
+    let elements: Vec<f64> = if let Some(list) = array.as_fixed_size_list_opt() {
+        let values = list.values().as_primitive_opt::<Float64Type>()?;
+        let start = list.value_offset(index) as usize;
+        values.values()[start..start + list.value_length() as usize].to_vec()
+    } else {
+        array
+            .as_struct_opt()?
+            .columns()
+            .iter()
+            .map(|column| Some(column.as_primitive_opt::<Float64Type>()?.value(index)))
+            .collect::<Option<_>>()?
+    };
+    if elements.iter().all(|element| element.is_nan()) {
+        Some(Position::from(vec![]))
+    } else {
+        Some(Position::from(elements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn feature(value: Option<GeometryValue>, properties: JsonValue) -> Feature {
+        Feature {
+            geometry: value.map(Geometry::new),
+            properties: properties.as_object().cloned(),
+            ..Feature::default()
+        }
+    }
+
+    fn roundtrip(collection: &FeatureCollection) -> RecordBatch {
+        let batch = collection.to_record_batch().unwrap();
+        assert_eq!(
+            &FeatureCollection::from_record_batches([&batch]).unwrap(),
+            collection
+        );
+        batch
+    }
+
+    fn extension_name(batch: &RecordBatch) -> String {
+        batch
+            .schema()
+            .field_with_name(GEOMETRY_COLUMN)
+            .unwrap()
+            .metadata()[EXTENSION_NAME]
+            .clone()
+    }
+
+    #[test]
+    fn native_layouts() {
+        let point = GeometryValue::new_point([1.0, 2.0]);
+        let line_string = GeometryValue::new_line_string([[1.0, 2.0], [3.0, 4.0]]);
+        let polygon =
+            GeometryValue::new_polygon([[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]);
+        let cases = [
+            (vec![point.clone()], "geoarrow.point"),
+            (vec![line_string.clone()], "geoarrow.linestring"),
+            (vec![polygon.clone()], "geoarrow.polygon"),
+            (
+                vec![
+                    GeometryValue::new_multi_point([[1.0, 2.0], [3.0, 4.0]]),
+                    GeometryValue::new_multi_point(Vec::<Position>::new()),
+                ],
+                "geoarrow.multipoint",
+            ),
+            (
+                vec![GeometryValue::new_multi_line_string([
+                    vec![[1.0, 2.0], [3.0, 4.0]],
+                    vec![[5.0, 6.0], [7.0, 8.0], [9.0, 10.0]],
+                ])],
+                "geoarrow.multilinestring",
+            ),
+            (
+                vec![GeometryValue::new_multi_polygon([
+                    vec![vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]],
+                    vec![
+                        vec![[0.0, 0.0], [9.0, 0.0], [9.0, 9.0], [0.0, 0.0]],
+                        vec![[1.0, 1.0], [2.0, 1.0], [2.0, 2.0], [1.0, 1.0]],
+                    ],
+                ])],
+                "geoarrow.multipolygon",
+            ),
+            (
+                vec![
+                    GeometryValue::new_geometry_collection([Geometry::new(point.clone())]),
+                    point.clone(),
+                ],
+                "geoarrow.wkb",
+            ),
+            (vec![point.clone(), line_string], "geoarrow.wkb"),
+        ];
+        for (values, expected) in cases {
+            let mut collection: FeatureCollection = values
+                .into_iter()
+                .map(|value| feature(Some(value), json!({})))
+                .collect();
+            collection.features.push(feature(None, json!({})));
+            let batch = roundtrip(&collection);
+            assert_eq!(extension_name(&batch), expected);
+            assert_eq!(batch.column(0).null_count(), 1);
+        }
+    }
+
+    #[test]
+    fn promote_to_multi() {
+        let collection = FeatureCollection::from_iter([
+            feature(Some(GeometryValue::new_point([1.0, 2.0, 3.0])), json!({})),
+            feature(
+                Some(GeometryValue::new_multi_point([[4.0, 5.0, 6.0]])),
+                json!({}),
+            ),
+        ]);
+        let batch = collection.to_record_batch().unwrap();
+        assert_eq!(extension_name(&batch), "geoarrow.multipoint");
+        assert_eq!(
+            batch.schema().field(0).data_type().to_string(),
+            "List(non-null FixedSizeList(3 x non-null Float64, field: 'xyz'), field: 'points')"
+        );
+        let features = FeatureCollection::from_record_batches([&batch])
+            .unwrap()
+            .features;
+        assert_eq!(
+            features[0].geometry.as_ref().unwrap().value,
+            GeometryValue::new_multi_point([[1.0, 2.0, 3.0]])
+        );
+    }
+
+    #[test]
+    fn mixed_dimensions() {
+        let collection = FeatureCollection::from_iter([
+            feature(Some(GeometryValue::new_point([1.0, 2.0])), json!({})),
+            feature(Some(GeometryValue::new_point([3.0, 4.0, 5.0])), json!({})),
+        ]);
+        let batch = roundtrip(&collection);
+        assert_eq!(extension_name(&batch), "geoarrow.wkb");
+
+        // The schema is inferred from the first batch, which has only 2D points
+        let features = collection.features.into_iter().map(Ok);
+        let mut batches = RecordBatches::new(features).with_batch_size(1);
+        assert!(batches.next().unwrap().is_ok());
+        assert!(matches!(
+            batches.next(),
+            Some(Err(Error::Arrow(ArrowError::SchemaError(_))))
+        ));
+
+        for value in [
+            GeometryValue::new_point(vec![1.0, 2.0, 3.0, 4.0, 5.0]),
+            GeometryValue::new_line_string([vec![1.0, 2.0], vec![3.0, 4.0, 5.0]]),
+        ] {
+            let collection = FeatureCollection::from_iter([feature(Some(value), json!({}))]);
+            assert!(collection.to_record_batch().is_err());
+        }
+    }
+
+    #[test]
+    fn property_columns() {
+        let collection = FeatureCollection::from_iter([
+            feature(
+                Some(GeometryValue::new_point(Vec::<f64>::new())),
+                json!({
+                    "bool": true,
+                    "int": 1,
+                    "float": 1,
+                    "string": "a",
+                    "mixed": 1,
+                    "object": { "a": [1] },
+                    "null": null
+                }),
+            ),
+            feature(None, json!({ "float": 1.5, "mixed": "b", "int": null })),
+        ]);
+        let batch = collection.to_record_batch().unwrap();
+        let schema = batch.schema();
+        let types: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|field| (field.name().as_str(), field.data_type().clone()))
+            .collect();
+        assert_eq!(
+            types[..7],
+            [
+                ("bool", DataType::Boolean),
+                ("float", DataType::Float64),
+                ("int", DataType::Int64),
+                ("mixed", DataType::Utf8),
+                ("null", DataType::Null),
+                ("object", DataType::Utf8),
+                ("string", DataType::Utf8),
+            ]
+        );
+        assert_eq!(schema.field(3).metadata()[EXTENSION_NAME], JSON_EXTENSION);
+        assert_eq!(schema.field(5).metadata()[EXTENSION_NAME], JSON_EXTENSION);
+
+        let features = FeatureCollection::from_record_batches([&batch])
+            .unwrap()
+            .features;
+        assert_eq!(
+            features[0],
+            feature(
+                Some(GeometryValue::new_point(Vec::<f64>::new())),
+                json!({
+                    "bool": true,
+                    "int": 1,
+                    "float": 1.0,
+                    "string": "a",
+                    "mixed": 1,
+                    "object": { "a": [1] }
+                })
+            )
+        );
+        assert_eq!(
+            features[1],
+            feature(None, json!({ "float": 1.5, "mixed": "b" }))
+        );
+    }
+
+    #[test]
+    fn stream() {
+        let features = [json!({ "a": 1 }), json!({ "a": 2 }), json!({ "a": "c" })]
+            .into_iter()
+            .map(|properties| Ok(feature(None, properties)));
+        let mut batches = RecordBatches::new(features).with_batch_size(2);
+        assert_eq!(batches.next().unwrap().unwrap().num_rows(), 2);
+        assert!(matches!(
+            batches.next(),
+            Some(Err(Error::Arrow(ArrowError::SchemaError(_))))
+        ));
+        assert!(batches.next().is_none());
+
+        let features = [json!({ "a": 1 }), json!({ "b": 2 })]
+            .into_iter()
+            .map(|properties| Ok(feature(None, properties)));
+        let mut batches = RecordBatches::new(features).with_batch_size(1);
+        assert!(batches.next().unwrap().is_ok());
+        assert!(batches.next().unwrap().is_err());
+
+        let features = [feature(None, json!({ "geometry": 1 }))];
+        assert!(
+            FeatureCollection::from_iter(features)
+                .to_record_batch()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn write_record_batch() {
+        let collection = FeatureCollection::from_iter([feature(
+            Some(GeometryValue::new_point([1.0, 2.0])),
+            json!({ "name": "a" }),
+        )]);
+        let batch = collection.to_record_batch().unwrap();
+        let mut output = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut output);
+            writer.write_record_batch(&batch).unwrap();
+        }
+        let written: FeatureCollection = std::str::from_utf8(&output).unwrap().parse().unwrap();
+        assert_eq!(written, collection);
+    }
+}
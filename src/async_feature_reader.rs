@@ -0,0 +1,284 @@
+use crate::collection_scanner::{CollectionMembers, CollectionScanner, ScanEvent};
+use crate::de::feature_from_slice;
+use crate::{Bbox, Feature, JsonObject, Result};
+
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncBufRead, AsyncRead, BufReader};
+
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+/// Asynchronously enumerates individual Features from a GeoJSON FeatureCollection.
+///
+/// This is the async counterpart of [`FeatureReader`](crate::FeatureReader), reading from a
+/// [`tokio::io::AsyncRead`] without buffering the whole collection in memory. Features are
+/// yielded as soon as they've been read. As with `FeatureReader`, the collection's other members
+/// are read as they're encountered: see [`AsyncFeatureReader::bbox`] and
+/// [`AsyncFeatureReader::foreign_members`].
+///
+/// Requires the `tokio` feature.
+///
+/// # Examples
+///
+/// ```
+/// use futures_util::StreamExt;
+/// use geojson::AsyncFeatureReader;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let input = r#"{
+///     "type": "FeatureCollection",
+///     "features": [
+///         { "type": "Feature", "geometry": null, "properties": { "name": "Dinagat Islands" } },
+///         { "type": "Feature", "geometry": null, "properties": { "name": "Neverland" } }
+///     ]
+/// }"#;
+///
+/// let mut reader = AsyncFeatureReader::from_reader(input.as_bytes());
+/// let mut features = reader.features();
+/// let mut names = vec![];
+/// while let Some(feature) = features.next().await {
+///     let feature = feature.expect("valid geojson feature");
+///     names.push(feature.property("name").unwrap().as_str().unwrap().to_string());
+/// }
+/// assert_eq!(names, ["Dinagat Islands", "Neverland"]);
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub struct AsyncFeatureReader<R> {
+    reader: BufReader<R>,
+    scanner: CollectionScanner,
+    members: CollectionMembers,
+    finished: bool,
+}
+
+impl<R: AsyncRead + Unpin> AsyncFeatureReader<R> {
+    /// Create an AsyncFeatureReader from the given `reader`.
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            scanner: CollectionScanner::default(),
+            members: CollectionMembers::default(),
+            finished: false,
+        }
+    }
+
+    /// The [`bbox`](https://tools.ietf.org/html/rfc7946#section-5) of the FeatureCollection, if
+    /// it has one.
+    ///
+    /// As with [`FeatureReader::bbox`](crate::FeatureReader::bbox), a bbox written after the
+    /// `features` array is only available once every feature has been read.
+    pub fn bbox(&self) -> Option<&Bbox> {
+        self.members.bbox.as_ref()
+    }
+
+    /// The [foreign members](https://datatracker.ietf.org/doc/html/rfc7946#section-6.1) of the
+    /// FeatureCollection, if it has any.
+    ///
+    /// As with [`FeatureReader::foreign_members`](crate::FeatureReader::foreign_members),
+    /// members written after the `features` array are only available once every feature has
+    /// been read.
+    pub fn foreign_members(&self) -> Option<&JsonObject> {
+        self.members.foreign_members.as_ref()
+    }
+
+    /// Stream the individual [`Feature`s](Feature) of a FeatureCollection.
+    ///
+    /// If instead you'd like to deserialize directly to your own struct, see
+    /// [`AsyncFeatureReader::deserialize`].
+    pub fn features(&mut self) -> impl Stream<Item = Result<Feature>> + Unpin {
+        self.parse_features(|bytes| Ok(serde_json::from_slice(bytes)?))
+    }
+
+    /// Deserialize the features of a FeatureCollection into your own custom struct using the
+    /// [`serde`](../../serde) crate.
+    ///
+    /// As with [`FeatureReader::deserialize`](crate::FeatureReader::deserialize), your struct's
+    /// `geometry` field is deserialized from each feature's geometry, and its other fields from
+    /// the feature's properties.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures_util::TryStreamExt;
+    /// use geojson::AsyncFeatureReader;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct MyStruct {
+    ///     geometry: geojson::Geometry,
+    ///     name: String,
+    /// }
+    ///
+    /// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+    /// let input = r#"{
+    ///     "type": "FeatureCollection",
+    ///     "features": [{
+    ///         "type": "Feature",
+    ///         "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+    ///         "properties": { "name": "Dinagat Islands" }
+    ///     }]
+    /// }"#;
+    ///
+    /// let mut reader = AsyncFeatureReader::from_reader(input.as_bytes());
+    /// let my_structs: Vec<MyStruct> = reader
+    ///     .deserialize()
+    ///     .try_collect()
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(my_structs[0].name, "Dinagat Islands");
+    /// # });
+    /// ```
+    pub fn deserialize<D: DeserializeOwned>(&mut self) -> impl Stream<Item = Result<D>> + Unpin {
+        self.parse_features(feature_from_slice)
+    }
+
+    fn parse_features<T>(&mut self, parse: fn(&[u8]) -> Result<T>) -> Features<'_, R, T> {
+        Features {
+            reader: self,
+            parse,
+        }
+    }
+}
+
+struct Features<'a, R, T> {
+    reader: &'a mut AsyncFeatureReader<R>,
+    parse: fn(&[u8]) -> Result<T>,
+}
+
+impl<R: AsyncRead + Unpin, T> Stream for Features<'_, R, T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let parse = self.parse;
+        let this = &mut *self.reader;
+        while !this.finished {
+            let available = match ready!(Pin::new(&mut this.reader).poll_fill_buf(cx)) {
+                Ok(available) => available,
+                Err(err) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+            };
+            if available.is_empty() {
+                this.finished = true;
+                return Poll::Ready(this.scanner.finish().err().map(Err));
+            }
+            let (consumed, event) = match this.scanner.feed(available) {
+                Ok(scanned) => scanned,
+                Err(err) => {
+                    this.finished = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            };
+            Pin::new(&mut this.reader).consume(consumed);
+            // Don't wait on any input following the collection
+            this.finished = this.scanner.is_finished();
+            match event {
+                Some(ScanEvent::Feature(bytes)) => return Poll::Ready(Some(parse(&bytes))),
+                Some(ScanEvent::Member { key, value }) => {
+                    if let Err(err) = this.members.add(key, &value) {
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+                None => {}
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, Geometry};
+
+    use futures_util::{StreamExt, TryStreamExt};
+    use serde::Deserialize;
+    use tokio::io::AsyncWriteExt;
+
+    #[derive(Deserialize)]
+    struct MyRecord {
+        geometry: Geometry,
+        name: String,
+        age: u64,
+    }
+
+    fn collection() -> &'static str {
+        r#"{
+            "type": "FeatureCollection",
+            "name": "[ages]",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+                    "properties": { "name": "Dinagat Islands", "age": 123 }
+                },
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "Point", "coordinates": [2.3, 4.5] },
+                    "properties": { "name": "Neverland", "age": 456 }
+                }
+            ],
+            "bbox": [2.3, 4.5, 125.6, 10.1]
+        }"#
+    }
+
+    #[tokio::test]
+    async fn features() {
+        let mut reader = AsyncFeatureReader::from_reader(collection().as_bytes());
+        let features: Vec<Feature> = reader.features().try_collect().await.unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[1].geometry, Some(Geometry::new_point([2.3, 4.5])));
+        assert_eq!(reader.bbox(), Some(&vec![2.3, 4.5, 125.6, 10.1]));
+        assert_eq!(reader.foreign_members().unwrap()["name"], "[ages]");
+    }
+
+    #[tokio::test]
+    async fn wrong_type() {
+        let input = r#"{"type": "Feature", "features": [{"type": "Feature", "properties": null, "geometry": null}]}"#;
+        let mut reader = AsyncFeatureReader::from_reader(input.as_bytes());
+        let results: Vec<_> = reader.features().collect().await;
+        assert!(matches!(
+            results[..],
+            [Err(Error::ExpectedType { .. }), Ok(_)]
+        ));
+    }
+
+    #[tokio::test]
+    async fn deserialize_from_small_chunks() {
+        let (mut sender, receiver) = tokio::io::duplex(3);
+        let write = async move {
+            for chunk in collection().as_bytes().chunks(5) {
+                sender.write_all(chunk).await.unwrap();
+            }
+        };
+        let mut reader = AsyncFeatureReader::from_reader(receiver);
+        let read = reader.deserialize::<MyRecord>().try_collect::<Vec<_>>();
+        let ((), records) = tokio::join!(write, read);
+        let records = records.unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "Dinagat Islands");
+        assert_eq!(records[1].age, 456);
+        assert_eq!(records[1].geometry, Geometry::new_point([2.3, 4.5]));
+    }
+
+    #[tokio::test]
+    async fn invalid_feature_doesnt_end_stream() {
+        let input = r#"{"features": [{"type": "Feature", "geometry": 1}, {"type": "Feature", "properties": null, "geometry": null}]}"#;
+        let mut reader = AsyncFeatureReader::from_reader(input.as_bytes());
+        let results: Vec<_> = reader.features().collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_err());
+        assert!(results[1].is_ok());
+    }
+
+    #[tokio::test]
+    async fn truncated() {
+        let input = r#"{"features": [{"type": "Feature", "properties": null, "geometry": null}"#;
+        let mut reader = AsyncFeatureReader::from_reader(input.as_bytes());
+        let results: Vec<_> = reader.features().collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(results[1], Err(Error::MalformedGeoJson(_))));
+    }
+}
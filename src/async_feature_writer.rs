@@ -0,0 +1,237 @@
+use crate::feature_writer::CollectionEncoder;
+use crate::{Feature, Result, WriteOptions};
+
+use serde::Serialize;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// Asynchronously write Features to a FeatureCollection.
+///
+/// This is the async counterpart of [`FeatureWriter`](crate::FeatureWriter), writing to a
+/// [`tokio::io::AsyncWrite`]. Each feature is serialized into a buffer before being written, so
+/// only one feature is held in memory at a time.
+///
+/// Unlike `FeatureWriter`, the collection can't be closed when the writer is dropped, because
+/// writing requires awaiting. You must call [`AsyncFeatureWriter::finish`] once you've written
+/// every feature, or the output will be incomplete.
+///
+/// Requires the `tokio` feature.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{AsyncFeatureWriter, Feature, FeatureCollection, Geometry};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut output: Vec<u8> = vec![];
+/// let mut writer = AsyncFeatureWriter::from_writer(&mut output);
+/// writer.write_foreign_member("name", "Islands").await.unwrap();
+/// writer.write_feature(&Feature::from(Geometry::new_point([125.6, 10.1]))).await.unwrap();
+/// writer.write_feature(&Feature::from(Geometry::new_point([2.3, 4.5]))).await.unwrap();
+/// writer.finish().await.unwrap();
+/// drop(writer);
+///
+/// let collection: FeatureCollection = serde_json::from_slice(&output).unwrap();
+/// assert_eq!(collection.features.len(), 2);
+/// assert_eq!(collection.foreign_members.unwrap()["name"], "Islands");
+/// # });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+pub struct AsyncFeatureWriter<W: AsyncWrite + Unpin> {
+    writer: W,
+    encoder: CollectionEncoder,
+    buffer: Vec<u8>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncFeatureWriter<W> {
+    /// Create an AsyncFeatureWriter from the given `writer`.
+    ///
+    /// To append features from your custom structs, use [`AsyncFeatureWriter::serialize`].
+    ///
+    /// To append features from [`Feature`] use [`AsyncFeatureWriter::write_feature`].
+    ///
+    /// To write a foreign member, use [`AsyncFeatureWriter::write_foreign_member`] before
+    /// appending any features.
+    pub fn from_writer(writer: W) -> Self {
+        Self {
+            writer,
+            encoder: CollectionEncoder::default(),
+            buffer: vec![],
+        }
+    }
+
+    /// Apply the given [`WriteOptions`] to every feature written.
+    ///
+    /// See [`FeatureWriter::with_options`](crate::FeatureWriter::with_options).
+    pub fn with_options(mut self, options: WriteOptions) -> Self {
+        self.encoder.set_options(options);
+        self
+    }
+
+    /// Whether to write a `bbox` member for the FeatureCollection, after its features. Defaults
+    /// to `false`.
+    ///
+    /// See [`FeatureWriter::with_bbox`](crate::FeatureWriter::with_bbox).
+    pub fn with_bbox(mut self, write_bbox: bool) -> Self {
+        self.encoder.set_bbox(write_bbox);
+        self
+    }
+
+    /// Write a [`crate::Feature`] struct to the output stream. If you'd like to
+    /// serialize your own custom structs, see [`AsyncFeatureWriter::serialize`] instead.
+    pub async fn write_feature(&mut self, feature: &Feature) -> Result<()> {
+        let result = self.encoder.write_feature(&mut self.buffer, feature);
+        self.write_buffer(result).await
+    }
+
+    /// Serialize your own custom struct to the features of a FeatureCollection using the
+    /// [`serde`] crate.
+    ///
+    /// As with [`FeatureWriter::serialize`](crate::FeatureWriter::serialize), your struct must
+    /// have a field called `geometry`, and all its other fields are written as the feature's
+    /// properties.
+    pub async fn serialize<S: Serialize>(&mut self, value: &S) -> Result<()> {
+        let result = self.encoder.serialize(&mut self.buffer, value);
+        self.write_buffer(result).await
+    }
+
+    /// Write a [foreign member](https://datatracker.ietf.org/doc/html/rfc7946#section-6.1) to the
+    /// output stream. This must be done before appending any features.
+    pub async fn write_foreign_member<T: ?Sized + Serialize>(
+        &mut self,
+        key: &str,
+        value: &T,
+    ) -> Result<()> {
+        let result = self
+            .encoder
+            .write_foreign_member(&mut self.buffer, key, value);
+        self.write_buffer(result).await
+    }
+
+    /// Writes the closing syntax for the FeatureCollection, and flushes the underlying writer.
+    ///
+    /// This must be called once every feature has been written.
+    pub async fn finish(&mut self) -> Result<()> {
+        let result = self.encoder.finish(&mut self.buffer);
+        self.write_buffer(result).await?;
+        self.flush().await
+    }
+
+    /// Flush the underlying writer buffer.
+    pub async fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush().await?)
+    }
+
+    /// Write whatever was encoded, even if encoding failed part way, just as
+    /// [`FeatureWriter`](crate::FeatureWriter) would have.
+    async fn write_buffer(&mut self, result: Result<()>) -> Result<()> {
+        let written = self.writer.write_all(&self.buffer).await;
+        self.buffer.clear();
+        result?;
+        Ok(written?)
+    }
+}
+
+impl<W: AsyncWrite + Unpin> Drop for AsyncFeatureWriter<W> {
+    fn drop(&mut self) {
+        if !self.encoder.is_finished() {
+            log::error!(
+                "AsyncFeatureWriter was dropped without being finished, so its output is incomplete. Call `AsyncFeatureWriter::finish` after writing every feature."
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AsyncFeatureReader, Error, FeatureCollection, Geometry, JsonValue, Precision};
+
+    use futures_util::TryStreamExt;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct MyRecord {
+        geometry: Geometry,
+        name: String,
+        age: u64,
+    }
+
+    #[tokio::test]
+    async fn write_empty() {
+        let mut buffer: Vec<u8> = vec![];
+        let mut writer = AsyncFeatureWriter::from_writer(&mut buffer);
+        writer.finish().await.unwrap();
+        drop(writer);
+
+        let actual: JsonValue = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(
+            actual,
+            json!({ "type": "FeatureCollection", "features": [] })
+        );
+    }
+
+    #[tokio::test]
+    async fn roundtrip() {
+        let (sender, receiver) = tokio::io::duplex(16);
+        let write = async move {
+            let mut writer = AsyncFeatureWriter::from_writer(sender).with_bbox(true);
+            writer
+                .write_feature(&Feature::from(Geometry::new_point([1.1, 1.2])))
+                .await
+                .unwrap();
+            writer
+                .serialize(&MyRecord {
+                    geometry: Geometry::new_point([2.1, 2.2]),
+                    name: "Jane".to_string(),
+                    age: 22,
+                })
+                .await
+                .unwrap();
+            writer.finish().await.unwrap();
+        };
+        let mut reader = AsyncFeatureReader::from_reader(receiver);
+        let read = reader.features().try_collect::<Vec<_>>();
+        let ((), features) = tokio::join!(write, read);
+        let features = features.unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0].geometry, Some(Geometry::new_point([1.1, 1.2])));
+        assert_eq!(features[1].property("name").unwrap(), "Jane");
+    }
+
+    #[tokio::test]
+    async fn write_foreign_members() {
+        let mut buffer: Vec<u8> = vec![];
+        let mut writer = AsyncFeatureWriter::from_writer(&mut buffer)
+            .with_options(WriteOptions::default().with_precision(Precision::DecimalPlaces(1)))
+            .with_bbox(true);
+        writer
+            .write_foreign_member("extra", &json!({ "a": 1 }))
+            .await
+            .unwrap();
+        writer
+            .write_feature(&Feature::from(Geometry::new_point([1.14, 1.26])))
+            .await
+            .unwrap();
+        assert!(matches!(
+            writer.write_foreign_member("late", "member").await,
+            Err(Error::InvalidWriterState(_))
+        ));
+        writer.finish().await.unwrap();
+        assert!(matches!(
+            writer.finish().await,
+            Err(Error::InvalidWriterState(_))
+        ));
+        drop(writer);
+
+        let collection: FeatureCollection = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(
+            collection.foreign_members.unwrap()["extra"],
+            json!({ "a": 1 })
+        );
+        assert_eq!(
+            collection.features[0].geometry,
+            Some(Geometry::new_point([1.1, 1.3]))
+        );
+        assert_eq!(collection.bbox, Some(vec![1.1, 1.3, 1.1, 1.3]));
+    }
+}
@@ -0,0 +1,384 @@
+//! Detecting and fixing positions written latitude first, i.e. `[latitude, longitude]`, rather
+//! than the longitude first that GeoJSON requires.
+//!
+//! An [`AxisOrderAnalysis`] scores how likely a set of features is to have its axes flipped,
+//! from how many positions only make sense one way round: an ordinate beyond ±90 must be a
+//! longitude, so the data is flipped if it's the second one, and isn't if it's the first.
+//! Positions within ±90 either way can be checked against the
+//! [extent the data is expected in](AxisOrderAnalysis::with_expected_extent).
+//!
+//! Fix flipped data with `swap_xy()`, which swaps the first two elements of every position and
+//! `bbox`.
+//!
+//! ```
+//! use geojson::FeatureCollection;
+//!
+//! // Paris and Montreal, latitude first
+//! let mut collection: FeatureCollection = r#"{
+//!   "type": "FeatureCollection",
+//!   "features": [
+//!     { "type": "Feature", "properties": null,
+//!       "geometry": { "type": "Point", "coordinates": [48.8566, 2.3522] } },
+//!     { "type": "Feature", "properties": null,
+//!       "geometry": { "type": "Point", "coordinates": [45.5019, -73.5674] } }
+//!   ]
+//! }"#.parse().unwrap();
+//!
+//! // Both positions are within ±90 either way round, so there's no evidence without an
+//! // expected extent
+//! assert_eq!(collection.analyze_axis_order().score(), 0.5);
+//!
+//! // Somewhere in the northern hemisphere, west of Berlin
+//! let analysis = geojson::axis_order::AxisOrderAnalysis::new()
+//!     .with_expected_extent([-180.0, 0.0, 13.4, 90.0])
+//!     .with_features(&collection.features);
+//! assert!(analysis.is_likely_flipped());
+//!
+//! collection.swap_xy();
+//! assert!(!collection.analyze_axis_order().is_likely_flipped());
+//! ```
+use crate::visit::{
+    self, GeoJsonVisitorMut, walk_feature_collection_mut, walk_feature_mut, walk_geometry_mut,
+};
+use crate::{
+    Bbox, Feature, FeatureCollection, FeatureReader, GeoJson, Geometry, GeometryValue, Position,
+    Result,
+};
+
+use std::io::Read;
+
+/// Evidence for whether the positions of a set of features are written latitude first.
+///
+/// Build one with [`FeatureCollection::analyze_axis_order`] or
+/// [`FeatureReader::analyze_axis_order`], or add features with
+/// [`AxisOrderAnalysis::with_features`], [`AxisOrderAnalysis::with_reader`] or one at a time with
+/// [`AxisOrderAnalysis::add_feature`]. See the [`axis_order`](crate::axis_order) module for
+/// details.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AxisOrderAnalysis {
+    expected_extent: Option<[f64; 4]>,
+    position_count: usize,
+    /// Positions which only make sense latitude first
+    flipped: usize,
+    /// Positions which only make sense longitude first
+    unflipped: usize,
+}
+
+impl AxisOrderAnalysis {
+    /// An analysis without any positions, or an expected extent.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The extent, `[west, south, east, north]`, the data is expected to be in.
+    ///
+    /// Positions which are within ±90 either way round, so could be either, count as evidence
+    /// if they're only in this extent one way round.
+    pub fn with_expected_extent(mut self, extent: [f64; 4]) -> Self {
+        self.expected_extent = Some(extent);
+        self
+    }
+
+    /// Returns the analysis with the positions of `features` added.
+    pub fn with_features<'a>(mut self, features: impl IntoIterator<Item = &'a Feature>) -> Self {
+        features
+            .into_iter()
+            .for_each(|feature| self.add_feature(feature));
+        self
+    }
+
+    /// Returns the analysis with the positions of the remaining features of `reader` added,
+    /// reading one at a time.
+    pub fn with_reader<R: Read>(mut self, reader: &mut FeatureReader<R>) -> Result<Self> {
+        for feature in reader.features() {
+            self.add_feature(&feature?);
+        }
+        Ok(self)
+    }
+
+    /// Add the positions of `feature`'s geometry, if it has one.
+    pub fn add_feature(&mut self, feature: &Feature) {
+        feature
+            .coords()
+            .for_each(|position| self.add_position(position));
+    }
+
+    /// Add the positions of `geometry`.
+    pub fn add_geometry(&mut self, geometry: &GeometryValue) {
+        geometry
+            .coords()
+            .for_each(|position| self.add_position(position));
+    }
+
+    /// Add a single position. Positions with fewer than two elements are ignored.
+    pub fn add_position(&mut self, position: &Position) {
+        let &[x, y, ..] = position.as_slice() else {
+            return;
+        };
+        self.position_count += 1;
+        let is_valid =
+            |longitude: f64, latitude: f64| longitude.abs() <= 180.0 && latitude.abs() <= 90.0;
+        let is_expected = |longitude: f64, latitude: f64| match self.expected_extent {
+            Some([west, south, east, north]) => {
+                (west..=east).contains(&longitude) && (south..=north).contains(&latitude)
+            }
+            None => false,
+        };
+        let evidence = match (is_valid(x, y), is_valid(y, x)) {
+            (true, false) => Some(false),
+            (false, true) => Some(true),
+            (true, true) => match (is_expected(x, y), is_expected(y, x)) {
+                (true, false) => Some(false),
+                (false, true) => Some(true),
+                _ => None,
+            },
+            (false, false) => None,
+        };
+        match evidence {
+            Some(true) => self.flipped += 1,
+            Some(false) => self.unflipped += 1,
+            None => {}
+        }
+    }
+
+    /// The number of positions added, ignoring any with fewer than two elements.
+    pub fn position_count(&self) -> usize {
+        self.position_count
+    }
+
+    /// The number of positions which only make sense one way round.
+    pub fn evidence_count(&self) -> usize {
+        self.flipped + self.unflipped
+    }
+
+    /// How likely the positions are to be written latitude first, from 0 (surely not) to 1
+    /// (surely): the share of the positions which only make sense one way round that only make
+    /// sense latitude first.
+    ///
+    /// Returns 0.5 if there's no evidence either way.
+    pub fn score(&self) -> f64 {
+        match self.evidence_count() {
+            0 => 0.5,
+            evidence => self.flipped as f64 / evidence as f64,
+        }
+    }
+
+    /// Whether the positions are more likely than not to be written latitude first.
+    pub fn is_likely_flipped(&self) -> bool {
+        self.score() > 0.5
+    }
+}
+
+impl FeatureCollection {
+    /// Analyze whether the features' positions are written latitude first.
+    ///
+    /// To check against an expected extent, see [`AxisOrderAnalysis::with_expected_extent`].
+    pub fn analyze_axis_order(&self) -> AxisOrderAnalysis {
+        AxisOrderAnalysis::new().with_features(&self.features)
+    }
+}
+
+impl<R: Read> FeatureReader<R> {
+    /// Analyze whether the positions of the remaining features are written latitude first,
+    /// reading one at a time.
+    ///
+    /// To check against an expected extent, see [`AxisOrderAnalysis::with_expected_extent`] and
+    /// [`AxisOrderAnalysis::with_reader`].
+    pub fn analyze_axis_order(&mut self) -> Result<AxisOrderAnalysis> {
+        AxisOrderAnalysis::new().with_reader(self)
+    }
+}
+
+/// Swaps the first two elements of every position and bbox.
+struct SwapXy;
+
+impl SwapXy {
+    fn swap_bbox(bbox: &mut Option<Bbox>) {
+        // The first half of a bbox is its minimums, and the second its maximums
+        if let Some(bbox) = bbox
+            && bbox.len() >= 4
+            && bbox.len() % 2 == 0
+        {
+            let half = bbox.len() / 2;
+            bbox.swap(0, 1);
+            bbox.swap(half, half + 1);
+        }
+    }
+}
+
+impl GeoJsonVisitorMut for SwapXy {
+    fn visit_feature_collection_mut(&mut self, collection: &mut FeatureCollection) {
+        Self::swap_bbox(&mut collection.bbox);
+        walk_feature_collection_mut(self, collection);
+    }
+
+    fn visit_feature_mut(&mut self, feature: &mut Feature) {
+        Self::swap_bbox(&mut feature.bbox);
+        walk_feature_mut(self, feature);
+    }
+
+    fn visit_geometry_mut(&mut self, geometry: &mut Geometry) {
+        Self::swap_bbox(&mut geometry.bbox);
+        walk_geometry_mut(self, geometry);
+    }
+
+    fn visit_position_mut(&mut self, position: &mut Position) {
+        if position.len() >= 2 {
+            let x = position[0];
+            position[0] = position[1];
+            position[1] = x;
+        }
+    }
+}
+
+impl GeometryValue {
+    /// Swap the first two elements of every position, e.g. to fix positions written latitude
+    /// first, including the positions and `bbox` of any nested geometries.
+    pub fn swap_xy(&mut self) {
+        visit::walk_geometry_value_mut(&mut SwapXy, self);
+    }
+}
+
+impl Geometry {
+    /// Swap the first two elements of every position and `bbox`, e.g. to fix positions written
+    /// latitude first.
+    pub fn swap_xy(&mut self) {
+        SwapXy.visit_geometry_mut(self);
+    }
+}
+
+impl Feature {
+    /// Swap the first two elements of every position and `bbox`, e.g. to fix positions written
+    /// latitude first.
+    pub fn swap_xy(&mut self) {
+        SwapXy.visit_feature_mut(self);
+    }
+}
+
+impl FeatureCollection {
+    /// Swap the first two elements of every position and `bbox` of the collection and its
+    /// features, e.g. to fix positions written latitude first.
+    pub fn swap_xy(&mut self) {
+        SwapXy.visit_feature_collection_mut(self);
+    }
+}
+
+impl GeoJson {
+    /// Swap the first two elements of every position and `bbox`, e.g. to fix positions written
+    /// latitude first.
+    pub fn swap_xy(&mut self) {
+        visit::walk_geojson_mut(&mut SwapXy, self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(positions: &[[f64; 2]]) -> FeatureCollection {
+        FeatureCollection::from_iter(
+            positions
+                .iter()
+                .map(|&position| Feature::from(Geometry::new_point(position))),
+        )
+    }
+
+    #[test]
+    fn score() {
+        // Only valid longitude first, latitude first, and either way
+        let analysis = points(&[[120.0, 10.0], [10.0, 120.0], [10.0, 20.0], [-100.0, -80.0]])
+            .analyze_axis_order();
+        assert_eq!(analysis.position_count(), 4);
+        assert_eq!(analysis.evidence_count(), 3);
+        assert!((analysis.score() - 1.0 / 3.0).abs() < 1e-12);
+        assert!(!analysis.is_likely_flipped());
+
+        // London is within ±90 either way round
+        let analysis = points(&[[35.0, 139.0], [-33.9, 151.2], [51.5, -0.1]]).analyze_axis_order();
+        assert_eq!(analysis.evidence_count(), 2);
+        assert_eq!(analysis.score(), 1.0);
+        assert!(analysis.is_likely_flipped());
+
+        // Out of range either way
+        let mut analysis = AxisOrderAnalysis::new();
+        analysis.add_position(&[200.0, 200.0].into());
+        analysis.add_position(&vec![1.0].into());
+        assert_eq!(analysis.position_count(), 1);
+        assert_eq!(analysis.score(), 0.5);
+    }
+
+    #[test]
+    fn expected_extent() {
+        // Around Germany
+        let extent = [5.9, 47.3, 15.0, 55.1];
+        let analysis = AxisOrderAnalysis::new()
+            .with_expected_extent(extent)
+            .with_features(&points(&[[52.5, 13.4], [48.1, 11.6], [0.0, 0.0]]).features);
+        assert_eq!(analysis.evidence_count(), 2);
+        assert_eq!(analysis.score(), 1.0);
+
+        let json = serde_json::to_string(&points(&[[13.4, 52.5]])).unwrap();
+        let analysis = FeatureReader::from_reader(json.as_bytes())
+            .analyze_axis_order()
+            .unwrap();
+        // Without the extent there's no evidence
+        assert_eq!(analysis.evidence_count(), 0);
+        let analysis = AxisOrderAnalysis::new()
+            .with_expected_extent(extent)
+            .with_reader(&mut FeatureReader::from_reader(json.as_bytes()))
+            .unwrap();
+        assert_eq!(analysis.evidence_count(), 1);
+        assert!(!analysis.is_likely_flipped());
+    }
+
+    #[test]
+    fn swap_xy() {
+        let mut collection = FeatureCollection::from_iter([
+            Feature::from(
+                Geometry::new_geometry_collection([
+                    Geometry::new_line_string([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]).with_bbox(),
+                    Geometry::new_multi_polygon([[[
+                        [0.0, 10.0],
+                        [0.0, 11.0],
+                        [1.0, 11.0],
+                        [0.0, 10.0],
+                    ]]]),
+                ])
+                .with_bbox(),
+            )
+            .with_bbox(),
+            Feature::default(),
+        ])
+        .with_bbox();
+        let expected = collection.clone();
+
+        collection.swap_xy();
+        assert_eq!(collection.bbox, Some(vec![2.0, 0.0, 11.0, 4.0]));
+        let geometry = collection.features[0].geometry.as_ref().unwrap();
+        assert_eq!(geometry.bbox, Some(vec![2.0, 0.0, 11.0, 4.0]));
+        let GeometryValue::GeometryCollection { geometries } = &geometry.value else {
+            panic!("expected a GeometryCollection");
+        };
+        assert_eq!(
+            geometries[0].value,
+            GeometryValue::new_line_string([[2.0, 1.0, 3.0], [5.0, 4.0, 6.0]])
+        );
+        assert_eq!(geometries[0].bbox, Some(vec![2.0, 1.0, 3.0, 5.0, 4.0, 6.0]));
+        assert_eq!(
+            geometries[1].value,
+            GeometryValue::new_multi_polygon([[[
+                [10.0, 0.0],
+                [11.0, 0.0],
+                [11.0, 1.0],
+                [10.0, 0.0]
+            ]]])
+        );
+        // The same as recomputing the bboxes from the swapped positions
+        assert_eq!(collection.bbox, collection.compute_bbox());
+
+        // Swapping twice changes nothing
+        let mut geojson = GeoJson::from(collection);
+        geojson.swap_xy();
+        assert_eq!(geojson, GeoJson::from(expected));
+    }
+}
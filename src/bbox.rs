@@ -0,0 +1,266 @@
+//! Computing [bounding boxes](https://tools.ietf.org/html/rfc7946#section-5) from coordinates.
+use crate::{Bbox, Feature, FeatureCollection, Geometry, GeometryValue, Position};
+
+/// Accumulates the extent of positions.
+///
+/// The output has 3 dimensions if every position has an altitude, otherwise only 2. Any elements
+/// beyond the third, and any positions with fewer than 2 elements, are ignored.
+#[derive(Debug, Default)]
+pub(crate) struct BboxBuilder {
+    min: [f64; 3],
+    max: [f64; 3],
+    // 0 until the first position has been added
+    dimensions: usize,
+}
+
+impl BboxBuilder {
+    pub(crate) fn add_position(&mut self, position: &Position) {
+        let dimensions = position.len().min(3);
+        if dimensions < 2 {
+            return;
+        }
+        if self.dimensions == 0 {
+            self.min[..dimensions].copy_from_slice(&position[..dimensions]);
+            self.max[..dimensions].copy_from_slice(&position[..dimensions]);
+            self.dimensions = dimensions;
+            return;
+        }
+        self.dimensions = self.dimensions.min(dimensions);
+        for axis in 0..self.dimensions {
+            self.min[axis] = self.min[axis].min(position[axis]);
+            self.max[axis] = self.max[axis].max(position[axis]);
+        }
+    }
+
+    pub(crate) fn add_geometry_value(&mut self, value: &GeometryValue) {
+        value
+            .coords()
+            .for_each(|position| self.add_position(position));
+    }
+
+    pub(crate) fn add_feature(&mut self, feature: &Feature) {
+        if let Some(geometry) = &feature.geometry {
+            self.add_geometry_value(&geometry.value);
+        }
+    }
+
+    /// `None` if no positions were added.
+    pub(crate) fn build(&self) -> Option<Bbox> {
+        let dimensions = self.dimensions;
+        if dimensions == 0 {
+            return None;
+        }
+        let mut bbox = Vec::with_capacity(dimensions * 2);
+        bbox.extend_from_slice(&self.min[..dimensions]);
+        bbox.extend_from_slice(&self.max[..dimensions]);
+        Some(bbox)
+    }
+}
+
+impl GeometryValue {
+    /// Compute the [bounding box](https://tools.ietf.org/html/rfc7946#section-5) of all the
+    /// positions in this geometry, including those of any nested `GeometryCollection`s.
+    ///
+    /// The bbox is 3D (`[west, south, min altitude, east, north, max altitude]`) if every
+    /// position has an altitude, otherwise it's 2D (`[west, south, east, north]`).
+    ///
+    /// Returns `None` for empty geometries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let line_string = GeometryValue::new_line_string([[1.0, 4.0], [3.0, 2.0]]);
+    /// assert_eq!(line_string.compute_bbox(), Some(vec![1.0, 2.0, 3.0, 4.0]));
+    ///
+    /// let point_3d = GeometryValue::new_point([1.0, 2.0, 3.0]);
+    /// assert_eq!(point_3d.compute_bbox(), Some(vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0]));
+    /// ```
+    pub fn compute_bbox(&self) -> Option<Bbox> {
+        let mut builder = BboxBuilder::default();
+        builder.add_geometry_value(self);
+        builder.build()
+    }
+}
+
+impl Geometry {
+    /// Compute the bounding box of this geometry's coordinates, ignoring any existing `bbox`.
+    ///
+    /// See [`GeometryValue::compute_bbox`].
+    pub fn compute_bbox(&self) -> Option<Bbox> {
+        self.value.compute_bbox()
+    }
+
+    /// Set `bbox` to the [computed](Self::compute_bbox) bounding box.
+    pub fn update_bbox(&mut self) {
+        self.bbox = self.compute_bbox();
+    }
+
+    /// Returns this geometry with `bbox` set to the [computed](Self::compute_bbox) bounding box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::Geometry;
+    ///
+    /// let geometry = Geometry::new_multi_point([[1.0, 4.0], [3.0, 2.0]]).with_bbox();
+    /// assert_eq!(geometry.bbox, Some(vec![1.0, 2.0, 3.0, 4.0]));
+    /// ```
+    pub fn with_bbox(mut self) -> Self {
+        self.update_bbox();
+        self
+    }
+}
+
+impl Feature {
+    /// Compute the bounding box of this feature's geometry, ignoring any existing `bbox`.
+    ///
+    /// Returns `None` if the feature has no geometry. See [`GeometryValue::compute_bbox`].
+    pub fn compute_bbox(&self) -> Option<Bbox> {
+        self.geometry.as_ref().and_then(Geometry::compute_bbox)
+    }
+
+    /// Set `bbox` to the [computed](Self::compute_bbox) bounding box.
+    pub fn update_bbox(&mut self) {
+        self.bbox = self.compute_bbox();
+    }
+
+    /// Returns this feature with `bbox` set to the [computed](Self::compute_bbox) bounding box.
+    pub fn with_bbox(mut self) -> Self {
+        self.update_bbox();
+        self
+    }
+}
+
+impl FeatureCollection {
+    /// Compute the bounding box of the geometries of every feature in this collection, ignoring
+    /// any existing `bbox` members.
+    ///
+    /// The bbox is only 3D if every position of every feature has an altitude. See
+    /// [`GeometryValue::compute_bbox`].
+    pub fn compute_bbox(&self) -> Option<Bbox> {
+        let mut builder = BboxBuilder::default();
+        self.features
+            .iter()
+            .for_each(|feature| builder.add_feature(feature));
+        builder.build()
+    }
+
+    /// Set `bbox` to the [computed](Self::compute_bbox) bounding box.
+    ///
+    /// The `bbox` of each feature is left as is.
+    pub fn update_bbox(&mut self) {
+        self.bbox = self.compute_bbox();
+    }
+
+    /// Returns this collection with `bbox` set to the [computed](Self::compute_bbox) bounding
+    /// box.
+    ///
+    /// The `bbox` of each feature is left as is. To set those too:
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection, Geometry};
+    ///
+    /// let mut collection = FeatureCollection::from_iter([
+    ///     Feature::from(Geometry::new_point([1.0, 2.0])),
+    ///     Feature::from(Geometry::new_point([3.0, 4.0])),
+    /// ])
+    /// .with_bbox();
+    /// collection.features.iter_mut().for_each(Feature::update_bbox);
+    ///
+    /// assert_eq!(collection.bbox, Some(vec![1.0, 2.0, 3.0, 4.0]));
+    /// assert_eq!(collection.features[1].bbox, Some(vec![3.0, 4.0, 3.0, 4.0]));
+    /// ```
+    pub fn with_bbox(mut self) -> Self {
+        self.update_bbox();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            GeometryValue::new_line_string(Vec::<Position>::new()).compute_bbox(),
+            None
+        );
+        assert_eq!(
+            GeometryValue::new_geometry_collection(Vec::<Geometry>::new()).compute_bbox(),
+            None
+        );
+        assert_eq!(Feature::default().compute_bbox(), None);
+        assert_eq!(FeatureCollection::new([]).compute_bbox(), None);
+    }
+
+    #[test]
+    fn polygon() {
+        let polygon = Geometry::new_polygon([
+            vec![[0.0, 0.0], [10.0, 0.0], [10.0, 5.0], [0.0, 0.0]],
+            vec![[1.0, 1.0], [2.0, 1.0], [2.0, 2.0], [1.0, 1.0]],
+        ]);
+        assert_eq!(polygon.compute_bbox(), Some(vec![0.0, 0.0, 10.0, 5.0]));
+    }
+
+    #[test]
+    fn mixed_dimensions() {
+        let line_string_3d = GeometryValue::new_line_string([[1.0, 2.0, 3.0], [-1.0, 5.0, -3.0]]);
+        assert_eq!(
+            line_string_3d.compute_bbox(),
+            Some(vec![-1.0, 2.0, -3.0, 1.0, 5.0, 3.0])
+        );
+
+        // Dropping down to 2D as soon as any position lacks an altitude
+        let mixed = GeometryValue::new_multi_point([
+            Position::from([1.0, 2.0, 3.0]),
+            Position::from([4.0, 5.0]),
+            Position::from([0.0, 9.0, 1.0]),
+        ]);
+        assert_eq!(mixed.compute_bbox(), Some(vec![0.0, 2.0, 4.0, 9.0]));
+
+        // Measures beyond the altitude are ignored
+        let point_4d = GeometryValue::new_point([1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(
+            point_4d.compute_bbox(),
+            Some(vec![1.0, 2.0, 3.0, 1.0, 2.0, 3.0])
+        );
+    }
+
+    #[test]
+    fn nested_geometry_collection() {
+        let collection = Geometry::new_geometry_collection([
+            Geometry::new_point([5.0, 5.0]),
+            Geometry::new_geometry_collection([
+                Geometry::new_multi_polygon([vec![vec![
+                    [-10.0, 0.0],
+                    [0.0, 0.0],
+                    [0.0, 20.0],
+                    [-10.0, 0.0],
+                ]]]),
+                Geometry::new_multi_line_string([vec![[30.0, -1.0], [31.0, 1.0]]]),
+            ]),
+        ])
+        .with_bbox();
+        assert_eq!(collection.bbox, Some(vec![-10.0, -1.0, 31.0, 20.0]));
+    }
+
+    #[test]
+    fn feature_collection_ignores_existing_bboxes() {
+        let mut stale = Feature::from(Geometry::new_point([1.0, 1.0]));
+        stale.bbox = Some(vec![-100.0, -100.0, 100.0, 100.0]);
+        let collection = FeatureCollection::new([
+            stale,
+            Feature::default(),
+            Feature::from(Geometry::new_point([2.0, 3.0])),
+        ])
+        .with_bbox();
+        assert_eq!(collection.bbox, Some(vec![1.0, 1.0, 2.0, 3.0]));
+        assert_eq!(
+            collection.features[0].bbox,
+            Some(vec![-100.0, -100.0, 100.0, 100.0])
+        );
+    }
+}
@@ -0,0 +1,511 @@
+//! GeoJSON types which borrow from their input, rather than allocating.
+use crate::feature::Id;
+use crate::geometry::deserialize::{Coordinates, GeometryType, geometry_value};
+use crate::{Bbox, Feature, FeatureCollection, Geometry, GeometryValue, JsonObject, Result};
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Unexpected, Visitor};
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A JSON object whose keys borrow from the input, and whose values are left unparsed.
+///
+/// Keys are only copied if they contain escape sequences. Each value can be parsed on demand,
+/// e.g. with [`serde_json::from_str`] on [`RawValue::get`].
+pub type JsonObjectRef<'a> = BTreeMap<Cow<'a, str>, &'a RawValue>;
+
+/// A [`Geometry`] which borrows its foreign members from the input.
+///
+/// Coordinates are parsed just as they are for a `Geometry`. The members of a
+/// `GeometryCollection` are parsed as owned `Geometry`s.
+///
+/// Convert to a `Geometry` with [`Geometry::try_from`].
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Geometry, GeometryRef};
+///
+/// let input = r#"{"type": "Point", "coordinates": [1.0, 2.0], "source": "survey"}"#;
+/// let geometry: GeometryRef = serde_json::from_str(input).unwrap();
+/// assert_eq!(geometry.foreign_members.as_ref().unwrap()["source"].get(), r#""survey""#);
+///
+/// let geometry = Geometry::try_from(geometry).unwrap();
+/// assert_eq!(geometry.foreign_members.unwrap()["source"], "survey");
+/// ```
+#[derive(Clone, Debug)]
+pub struct GeometryRef<'a> {
+    /// Bounding Box
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    pub bbox: Option<Bbox>,
+    pub value: GeometryValue,
+    /// Foreign Members
+    ///
+    /// [GeoJSON Format Specification § 6](https://tools.ietf.org/html/rfc7946#section-6.1)
+    pub foreign_members: Option<JsonObjectRef<'a>>,
+}
+
+/// A [`Feature`] which borrows its properties and foreign members from the input.
+///
+/// Properties are left unparsed, so reading a large file of features only pays to parse the
+/// properties that are actually used. Deserialize it from a `&str` or `&[u8]`, e.g. a memory-mapped
+/// file, with [`serde_json::from_str`] or [`serde_json::from_slice`].
+///
+/// Convert to a `Feature` with [`Feature::try_from`].
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Feature, FeatureRef};
+///
+/// let input = r#"{
+///     "type": "Feature",
+///     "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+///     "properties": { "name": "Dinagat Islands", "population": 127152 }
+/// }"#;
+///
+/// let feature: FeatureRef = serde_json::from_str(input).unwrap();
+/// let population: u64 = serde_json::from_str(feature.property("population").unwrap().get()).unwrap();
+/// assert_eq!(population, 127152);
+///
+/// let feature = Feature::try_from(feature).unwrap();
+/// assert_eq!(feature.property("name").unwrap(), "Dinagat Islands");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FeatureRef<'a> {
+    /// Bounding Box
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    pub bbox: Option<Bbox>,
+    /// Geometry
+    ///
+    /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
+    pub geometry: Option<GeometryRef<'a>>,
+    /// Identifier
+    ///
+    /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
+    pub id: Option<Id>,
+    /// Properties
+    ///
+    /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
+    pub properties: Option<JsonObjectRef<'a>>,
+    /// Foreign Members
+    ///
+    /// [GeoJSON Format Specification § 6](https://tools.ietf.org/html/rfc7946#section-6.1)
+    pub foreign_members: Option<JsonObjectRef<'a>>,
+}
+
+impl<'a> FeatureRef<'a> {
+    /// Return the unparsed value of the property with the given `key`, if there is one.
+    pub fn property(&self, key: impl AsRef<str>) -> Option<&'a RawValue> {
+        self.properties.as_ref()?.get(key.as_ref()).copied()
+    }
+}
+
+/// A [`FeatureCollection`] whose features borrow from the input.
+///
+/// See [`FeatureRef`]. Convert to a `FeatureCollection` with [`FeatureCollection::try_from`].
+#[derive(Clone, Debug, Default)]
+pub struct FeatureCollectionRef<'a> {
+    /// Bounding Box
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    pub bbox: Option<Bbox>,
+    pub features: Vec<FeatureRef<'a>>,
+    /// Foreign Members
+    ///
+    /// [GeoJSON Format Specification § 6](https://tools.ietf.org/html/rfc7946#section-6.1)
+    pub foreign_members: Option<JsonObjectRef<'a>>,
+}
+
+fn to_json_object(object: JsonObjectRef) -> Result<JsonObject> {
+    object
+        .into_iter()
+        .map(|(key, value)| Ok((key.into_owned(), serde_json::from_str(value.get())?)))
+        .collect()
+}
+
+impl TryFrom<GeometryRef<'_>> for Geometry {
+    type Error = crate::Error;
+
+    fn try_from(geometry: GeometryRef) -> Result<Self> {
+        Ok(Geometry {
+            bbox: geometry.bbox,
+            value: geometry.value,
+            foreign_members: geometry.foreign_members.map(to_json_object).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<FeatureRef<'_>> for Feature {
+    type Error = crate::Error;
+
+    fn try_from(feature: FeatureRef) -> Result<Self> {
+        Ok(Feature {
+            bbox: feature.bbox,
+            geometry: feature.geometry.map(Geometry::try_from).transpose()?,
+            id: feature.id,
+            properties: feature.properties.map(to_json_object).transpose()?,
+            foreign_members: feature.foreign_members.map(to_json_object).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<FeatureCollectionRef<'_>> for FeatureCollection {
+    type Error = crate::Error;
+
+    fn try_from(collection: FeatureCollectionRef) -> Result<Self> {
+        Ok(FeatureCollection {
+            bbox: collection.bbox,
+            features: collection
+                .features
+                .into_iter()
+                .map(Feature::try_from)
+                .collect::<Result<_>>()?,
+            foreign_members: collection.foreign_members.map(to_json_object).transpose()?,
+        })
+    }
+}
+
+/// A string which borrows from the input, unless it contains escape sequences.
+struct CowStr<'a>(Cow<'a, str>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for CowStr<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct CowStrVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for CowStrVisitor<'a> {
+            type Value = CowStr<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E>(self, value: &'de str) -> std::result::Result<Self::Value, E> {
+                Ok(CowStr(Cow::Borrowed(value)))
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E> {
+                Ok(CowStr(Cow::Owned(value.to_string())))
+            }
+
+            fn visit_string<E>(self, value: String) -> std::result::Result<Self::Value, E> {
+                Ok(CowStr(Cow::Owned(value)))
+            }
+        }
+
+        deserializer.deserialize_str(CowStrVisitor(PhantomData))
+    }
+}
+
+struct ObjectRef<'a>(JsonObjectRef<'a>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for ObjectRef<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct ObjectRefVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for ObjectRefVisitor<'a> {
+            type Value = ObjectRef<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut object = JsonObjectRef::new();
+                while let Some(CowStr(key)) = map.next_key()? {
+                    object.insert(key, map.next_value()?);
+                }
+                Ok(ObjectRef(object))
+            }
+        }
+
+        deserializer.deserialize_map(ObjectRefVisitor(PhantomData))
+    }
+}
+
+/// Insert an unrecognized member into `foreign_members`.
+fn add_foreign_member<'de: 'a, 'a, A: MapAccess<'de>>(
+    foreign_members: &mut Option<JsonObjectRef<'a>>,
+    key: Cow<'a, str>,
+    map: &mut A,
+) -> std::result::Result<(), A::Error> {
+    let value: &'a RawValue = map.next_value()?;
+    foreign_members
+        .get_or_insert_with(JsonObjectRef::new)
+        .insert(key, value);
+    Ok(())
+}
+
+/// Check the `type` member of an object.
+fn expect_type<'de, A: MapAccess<'de>>(
+    map: &mut A,
+    expected: &'static str,
+) -> std::result::Result<(), A::Error> {
+    let CowStr(actual) = map.next_value()?;
+    if actual != expected {
+        return Err(de::Error::invalid_value(
+            Unexpected::Str(&actual),
+            &expected,
+        ));
+    }
+    Ok(())
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for GeometryRef<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct GeometryRefVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for GeometryRefVisitor<'a> {
+            type Value = GeometryRef<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Geometry object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut geometry_type: Option<GeometryType> = None;
+                let mut coordinates: Option<Coordinates> = None;
+                let mut geometries: Option<Vec<Geometry>> = None;
+                let mut bbox = None;
+                let mut foreign_members = None;
+                while let Some(CowStr(key)) = map.next_key()? {
+                    match key.as_ref() {
+                        "type" => geometry_type = Some(map.next_value()?),
+                        "coordinates" => coordinates = Some(map.next_value()?),
+                        "geometries" => geometries = Some(map.next_value()?),
+                        "bbox" => bbox = map.next_value()?,
+                        _ => add_foreign_member(&mut foreign_members, key, &mut map)?,
+                    }
+                }
+                let geometry_type =
+                    geometry_type.ok_or_else(|| de::Error::missing_field("type"))?;
+                let value = geometry_value(geometry_type, coordinates, geometries)
+                    .map_err(de::Error::custom)?;
+                Ok(GeometryRef {
+                    bbox,
+                    value,
+                    foreign_members,
+                })
+            }
+        }
+
+        deserializer.deserialize_map(GeometryRefVisitor(PhantomData))
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for FeatureRef<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct FeatureRefVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for FeatureRefVisitor<'a> {
+            type Value = FeatureRef<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Feature object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut has_type = false;
+                let mut feature = FeatureRef::default();
+                while let Some(CowStr(key)) = map.next_key()? {
+                    match key.as_ref() {
+                        "type" => {
+                            expect_type(&mut map, "Feature")?;
+                            has_type = true;
+                        }
+                        "bbox" => feature.bbox = map.next_value()?,
+                        "geometry" => feature.geometry = map.next_value()?,
+                        "id" => feature.id = map.next_value()?,
+                        "properties" => {
+                            feature.properties = map
+                                .next_value::<Option<ObjectRef>>()?
+                                .map(|object| object.0)
+                        }
+                        _ => add_foreign_member(&mut feature.foreign_members, key, &mut map)?,
+                    }
+                }
+                if !has_type {
+                    return Err(de::Error::missing_field("type"));
+                }
+                Ok(feature)
+            }
+        }
+
+        deserializer.deserialize_map(FeatureRefVisitor(PhantomData))
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for FeatureCollectionRef<'a> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct FeatureCollectionRefVisitor<'a>(PhantomData<&'a ()>);
+
+        impl<'de: 'a, 'a> Visitor<'de> for FeatureCollectionRefVisitor<'a> {
+            type Value = FeatureCollectionRef<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("FeatureCollection object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<Self::Value, A::Error> {
+                let mut has_type = false;
+                let mut features = None;
+                let mut bbox = None;
+                let mut foreign_members = None;
+                while let Some(CowStr(key)) = map.next_key()? {
+                    match key.as_ref() {
+                        "type" => {
+                            expect_type(&mut map, "FeatureCollection")?;
+                            has_type = true;
+                        }
+                        "features" => features = Some(map.next_value()?),
+                        "bbox" => bbox = map.next_value()?,
+                        _ => add_foreign_member(&mut foreign_members, key, &mut map)?,
+                    }
+                }
+                if !has_type {
+                    return Err(de::Error::missing_field("type"));
+                }
+                Ok(FeatureCollectionRef {
+                    bbox,
+                    features: features.ok_or_else(|| de::Error::missing_field("features"))?,
+                    foreign_members,
+                })
+            }
+        }
+
+        deserializer.deserialize_map(FeatureCollectionRefVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GeoJson;
+
+    use serde_json::json;
+
+    fn feature_json() -> String {
+        json!({
+            "type": "Feature",
+            "id": "island",
+            "bbox": [125.6, 10.1, 125.6, 10.1],
+            "geometry": {
+                "type": "Point",
+                "coordinates": [125.6, 10.1],
+                "source": { "surveyed": 1971 }
+            },
+            "properties": {
+                "name": "Dinagat Islands",
+                "escaped\nkey": [1, 2, 3],
+                "nested": { "a": null }
+            },
+            "title": "Foreign"
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn borrow_feature() {
+        let input = feature_json();
+        let feature: FeatureRef = serde_json::from_str(&input).unwrap();
+
+        let properties = feature.properties.as_ref().unwrap();
+        let (key, _) = properties.get_key_value("name").unwrap();
+        assert!(matches!(key, Cow::Borrowed(_)));
+        let (key, value) = properties.get_key_value("escaped\nkey").unwrap();
+        assert!(matches!(key, Cow::Owned(_)));
+        assert_eq!(value.get(), "[1,2,3]");
+        assert_eq!(
+            feature.property("name").unwrap().get(),
+            r#""Dinagat Islands""#
+        );
+        assert!(feature.property("missing").is_none());
+
+        let geometry = feature.geometry.as_ref().unwrap();
+        assert_eq!(geometry.value, GeometryValue::new_point([125.6, 10.1]));
+        assert_eq!(
+            geometry.foreign_members.as_ref().unwrap()["source"].get(),
+            r#"{"surveyed":1971}"#
+        );
+        assert_eq!(feature.foreign_members.as_ref().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn to_owned() {
+        let input = feature_json();
+        let expected: Feature = input.parse().unwrap();
+
+        let feature: FeatureRef = serde_json::from_slice(input.as_bytes()).unwrap();
+        assert_eq!(Feature::try_from(feature).unwrap(), expected);
+
+        let collection_json = json!({
+            "type": "FeatureCollection",
+            "features": [serde_json::from_str::<crate::JsonValue>(&input).unwrap()],
+            "name": "islands"
+        })
+        .to_string();
+        let GeoJson::FeatureCollection(expected) = collection_json.parse().unwrap() else {
+            unreachable!()
+        };
+        let collection: FeatureCollectionRef = serde_json::from_str(&collection_json).unwrap();
+        assert_eq!(FeatureCollection::try_from(collection).unwrap(), expected);
+    }
+
+    #[test]
+    fn minimal_feature() {
+        let feature: FeatureRef =
+            serde_json::from_str(r#"{"type": "Feature", "geometry": null, "properties": null}"#)
+                .unwrap();
+        assert!(feature.geometry.is_none());
+        assert!(feature.properties.is_none());
+        assert!(feature.foreign_members.is_none());
+        assert_eq!(Feature::try_from(feature).unwrap(), Feature::default());
+    }
+
+    #[test]
+    fn geometry_collection() {
+        let input = json!({
+            "type": "GeometryCollection",
+            "geometries": [{ "type": "Point", "coordinates": [1.0, 2.0] }]
+        })
+        .to_string();
+        let geometry: GeometryRef = serde_json::from_str(&input).unwrap();
+        assert_eq!(
+            geometry.value,
+            GeometryValue::new_geometry_collection([Geometry::new_point([1.0, 2.0])])
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for input in [
+            json!({ "type": "Point", "geometry": null, "properties": null }),
+            json!({ "geometry": null, "properties": null }),
+            json!({ "type": "Feature", "properties": [] }),
+            json!({ "type": "Feature", "geometry": { "type": "Point", "coordinates": [[1.0, 2.0]] } }),
+        ] {
+            assert!(serde_json::from_str::<FeatureRef>(&input.to_string()).is_err());
+        }
+        assert!(serde_json::from_str::<GeometryRef>(r#"{"coordinates": [1.0, 2.0]}"#).is_err());
+        assert!(
+            serde_json::from_str::<FeatureCollectionRef>(r#"{"type": "FeatureCollection"}"#)
+                .is_err()
+        );
+    }
+}
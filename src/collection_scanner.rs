@@ -0,0 +1,436 @@
+//! Incrementally locate the features and other members of a FeatureCollection in a stream of
+//! bytes.
+//!
+//! The scanner does no IO of its own, so the same scanner drives both blocking and async readers:
+//! feed it whatever bytes are available, and it reports how many it consumed, along with the raw
+//! bytes of each feature or member as soon as it's complete. The values themselves are left to
+//! `serde_json`, so the scanner only needs to track enough of the JSON syntax to find where each
+//! value ends.
+use crate::{Bbox, Error, JsonObject, Result};
+
+/// Something found by [`CollectionScanner::feed`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum ScanEvent {
+    /// The raw JSON of one element of the `features` array.
+    Feature(Vec<u8>),
+    /// A member of the collection other than `features`, and the raw JSON of its value.
+    Member { key: String, value: Vec<u8> },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Position {
+    /// Before the opening `{` of the collection.
+    Start,
+    /// Inside the collection, expecting a member's key, or the closing `}`.
+    ExpectKey { first: bool },
+    /// Inside a member's key.
+    InKey,
+    /// After a member's key.
+    ExpectColon,
+    /// After the `:` following a member's key.
+    ExpectValue,
+    /// Inside the value of a member other than `features`.
+    InValue,
+    /// After a member's value.
+    ExpectCommaOrEnd,
+    /// Inside the `features` array, expecting a feature, or the closing `]`.
+    ExpectFeature { first: bool },
+    /// Inside a feature.
+    InFeature,
+    /// After a feature.
+    ExpectFeatureCommaOrEnd,
+    /// After the closing `}` of the collection.
+    Finished,
+}
+
+/// Finds the end of a single JSON value, one byte at a time.
+#[derive(Default)]
+struct ValueScanner {
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    in_scalar: bool,
+}
+
+enum Step {
+    /// The byte is part of the value, which continues.
+    Continue,
+    /// The byte is the last of the value.
+    Complete,
+    /// The value ended before this byte, which belongs to whatever follows.
+    CompleteBefore,
+}
+
+impl ValueScanner {
+    fn step(&mut self, byte: u8) -> Step {
+        if self.in_string {
+            if self.escaped {
+                self.escaped = false;
+            } else if byte == b'\\' {
+                self.escaped = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+                if self.depth == 0 {
+                    return Step::Complete;
+                }
+            }
+            return Step::Continue;
+        }
+        if self.in_scalar {
+            return match byte {
+                b',' | b'}' | b']' => Step::CompleteBefore,
+                byte if byte.is_ascii_whitespace() => Step::CompleteBefore,
+                _ => Step::Continue,
+            };
+        }
+        match byte {
+            b'"' => self.in_string = true,
+            b'{' | b'[' => self.depth += 1,
+            b'}' | b']' => {
+                // Mismatched brackets are left for serde_json to report
+                self.depth = self.depth.saturating_sub(1);
+                if self.depth == 0 {
+                    return Step::Complete;
+                }
+            }
+            byte if self.depth == 0 && !byte.is_ascii_whitespace() => self.in_scalar = true,
+            _ => {}
+        }
+        Step::Continue
+    }
+}
+
+/// Scans the top level of a FeatureCollection for its members, and the elements of its
+/// `features` array.
+pub(crate) struct CollectionScanner {
+    position: Position,
+    value: ValueScanner,
+    // The key of the current member
+    key: String,
+    // The raw JSON of the current key, value or feature
+    buffer: Vec<u8>,
+    // The offset of the current byte, for error messages
+    offset: usize,
+}
+
+impl Default for CollectionScanner {
+    fn default() -> Self {
+        Self {
+            position: Position::Start,
+            value: ValueScanner::default(),
+            key: String::new(),
+            buffer: vec![],
+            offset: 0,
+        }
+    }
+}
+
+impl CollectionScanner {
+    /// Consume bytes of `input` up to the end of the next feature or member, if there is one.
+    ///
+    /// Returns the number of bytes consumed, and the feature or member if one was completed. Any remaining
+    /// bytes should be passed to the next call.
+    pub(crate) fn feed(&mut self, input: &[u8]) -> Result<(usize, Option<ScanEvent>)> {
+        let mut index = 0;
+        while index < input.len() {
+            let (consumed, event) = match self.position {
+                Position::InValue | Position::InFeature => self.scan_value(&input[index..]),
+                _ => {
+                    let (consumed, event) = self.step(input[index])?;
+                    (usize::from(consumed), event)
+                }
+            };
+            index += consumed;
+            self.offset += consumed;
+            if event.is_some() {
+                return Ok((index, event));
+            }
+        }
+        Ok((input.len(), None))
+    }
+
+    /// Whether the closing `}` of the collection has been reached.
+    pub(crate) fn is_finished(&self) -> bool {
+        self.position == Position::Finished
+    }
+
+    /// Check that the input didn't end before the collection did.
+    pub(crate) fn finish(&self) -> Result<()> {
+        if self.is_finished() {
+            Ok(())
+        } else {
+            Err(self.error("unexpected end of input"))
+        }
+    }
+
+    /// Advance by one byte, returning whether the byte was consumed, and any completed event.
+    fn step(&mut self, byte: u8) -> Result<(bool, Option<ScanEvent>)> {
+        match self.position {
+            Position::Start => match byte {
+                b'{' => self.position = Position::ExpectKey { first: true },
+                byte if byte.is_ascii_whitespace() => {}
+                _ => return Err(self.error("expected a FeatureCollection object")),
+            },
+            Position::ExpectKey { first } => match byte {
+                b'"' => {
+                    self.buffer.clear();
+                    self.buffer.push(byte);
+                    self.value = ValueScanner::default();
+                    self.value.step(byte);
+                    self.position = Position::InKey;
+                }
+                b'}' if first => self.position = Position::Finished,
+                byte if byte.is_ascii_whitespace() => {}
+                _ => return Err(self.error("expected a member name")),
+            },
+            Position::InKey => {
+                self.buffer.push(byte);
+                if let Step::Complete = self.value.step(byte) {
+                    self.key = serde_json::from_slice(&self.buffer)?;
+                    self.position = Position::ExpectColon;
+                }
+            }
+            Position::ExpectColon => match byte {
+                b':' => self.position = Position::ExpectValue,
+                byte if byte.is_ascii_whitespace() => {}
+                _ => return Err(self.error("expected `:`")),
+            },
+            Position::ExpectValue => match byte {
+                byte if byte.is_ascii_whitespace() => {}
+                b',' | b'}' | b']' | b':' => return Err(self.error("expected a value")),
+                b'[' if self.key == "features" => {
+                    self.position = Position::ExpectFeature { first: true };
+                }
+                _ if self.key == "features" => {
+                    return Err(self.error("expected `features` to be an array"));
+                }
+                _ => {
+                    self.buffer.clear();
+                    self.value = ValueScanner::default();
+                    self.position = Position::InValue;
+                    // The value begins with this byte
+                    return Ok((false, None));
+                }
+            },
+            Position::InValue | Position::InFeature => {
+                unreachable!("values are scanned by `scan_value`")
+            }
+            Position::ExpectCommaOrEnd => match byte {
+                b',' => self.position = Position::ExpectKey { first: false },
+                b'}' => self.position = Position::Finished,
+                byte if byte.is_ascii_whitespace() => {}
+                _ => return Err(self.error("expected `,` or `}`")),
+            },
+            Position::ExpectFeature { first } => match byte {
+                b']' if first => self.position = Position::ExpectCommaOrEnd,
+                b',' | b'}' | b']' | b':' => return Err(self.error("expected a feature")),
+                byte if byte.is_ascii_whitespace() => {}
+                _ => {
+                    self.buffer.clear();
+                    self.value = ValueScanner::default();
+                    self.position = Position::InFeature;
+                    // The feature begins with this byte
+                    return Ok((false, None));
+                }
+            },
+            Position::ExpectFeatureCommaOrEnd => match byte {
+                b',' => self.position = Position::ExpectFeature { first: false },
+                b']' => self.position = Position::ExpectCommaOrEnd,
+                byte if byte.is_ascii_whitespace() => {}
+                _ => return Err(self.error("expected `,` or `]`")),
+            },
+            Position::Finished => match byte {
+                byte if byte.is_ascii_whitespace() => {}
+                _ => return Err(self.error("trailing characters after FeatureCollection")),
+            },
+        }
+        Ok((true, None))
+    }
+
+    /// Consume bytes of the current member's value or feature, returning how many were part of it,
+    /// and the completed member or feature if it ended.
+    fn scan_value(&mut self, input: &[u8]) -> (usize, Option<ScanEvent>) {
+        let mut end = 0;
+        let mut complete = false;
+        for &byte in input {
+            match self.value.step(byte) {
+                Step::Continue => end += 1,
+                Step::Complete => {
+                    end += 1;
+                    complete = true;
+                    break;
+                }
+                Step::CompleteBefore => {
+                    complete = true;
+                    break;
+                }
+            }
+        }
+        // Copying whole runs of bytes is much faster than one at a time
+        self.buffer.extend_from_slice(&input[..end]);
+        if !complete {
+            return (end, None);
+        }
+        let event = if self.position == Position::InValue {
+            self.position = Position::ExpectCommaOrEnd;
+            self.take_member()
+        } else {
+            self.position = Position::ExpectFeatureCommaOrEnd;
+            self.take_feature()
+        };
+        (end, Some(event))
+    }
+
+    fn take_member(&mut self) -> ScanEvent {
+        ScanEvent::Member {
+            key: std::mem::take(&mut self.key),
+            value: std::mem::take(&mut self.buffer),
+        }
+    }
+
+    fn take_feature(&mut self) -> ScanEvent {
+        ScanEvent::Feature(std::mem::take(&mut self.buffer))
+    }
+
+    fn error(&self, message: &str) -> Error {
+        let message = format!("{message} at byte {}", self.offset);
+        Error::MalformedGeoJson(serde::de::Error::custom(message))
+    }
+}
+
+/// The members of a FeatureCollection other than its `features`, as reported by
+/// [`ScanEvent::Member`].
+#[derive(Debug, Default)]
+pub(crate) struct CollectionMembers {
+    pub(crate) bbox: Option<Bbox>,
+    pub(crate) foreign_members: Option<JsonObject>,
+}
+
+impl CollectionMembers {
+    /// Record a member, checking that the collection's `type` is a FeatureCollection.
+    pub(crate) fn add(&mut self, key: String, value: &[u8]) -> Result<()> {
+        match key.as_str() {
+            "type" => {
+                let actual: String = serde_json::from_slice(value)?;
+                if actual != "FeatureCollection" {
+                    return Err(Error::ExpectedType {
+                        expected: "FeatureCollection".to_string(),
+                        actual,
+                    });
+                }
+            }
+            "bbox" => self.bbox = serde_json::from_slice(value)?,
+            _ => {
+                let value = serde_json::from_slice(value)?;
+                self.foreign_members
+                    .get_or_insert_with(JsonObject::new)
+                    .insert(key, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_events(input: &[u8], chunk_size: usize) -> Result<Vec<ScanEvent>> {
+        let mut scanner = CollectionScanner::default();
+        let mut events = vec![];
+        for mut chunk in input.chunks(chunk_size) {
+            while !chunk.is_empty() {
+                let (consumed, event) = scanner.feed(chunk)?;
+                chunk = &chunk[consumed..];
+                events.extend(event);
+            }
+        }
+        scanner.finish()?;
+        Ok(events)
+    }
+
+    fn scan(input: &[u8], chunk_size: usize) -> Result<Vec<String>> {
+        Ok(scan_events(input, chunk_size)?
+            .into_iter()
+            .filter_map(|event| match event {
+                ScanEvent::Feature(feature) => Some(String::from_utf8(feature).unwrap()),
+                ScanEvent::Member { .. } => None,
+            })
+            .collect())
+    }
+
+    fn member(key: &str, value: &str) -> ScanEvent {
+        ScanEvent::Member {
+            key: key.to_string(),
+            value: value.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn features_in_any_chunks() {
+        let input = br#"{
+            "type": "FeatureCollection",
+            "name": "[not the features]",
+            "features": [ {"type": "Feature", "properties": {"a]}": "\"}"}}, 1.5 ,{"b":[[]]} ]
+        }"#;
+        for chunk_size in [1, 2, 7, input.len()] {
+            assert_eq!(
+                scan(input, chunk_size).unwrap(),
+                [
+                    r#"{"type": "Feature", "properties": {"a]}": "\"}"}}"#,
+                    "1.5",
+                    r#"{"b":[[]]}"#,
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn members_after_features() {
+        let input = br#"{"features":[{}],"bbox":[1,2,3,4],"type":"FeatureCollection","x":true}"#;
+        assert_eq!(scan(input, 3).unwrap(), ["{}"]);
+    }
+
+    #[test]
+    fn members() {
+        let input = br#"{"type": "FeatureCollection" , "bbox":[1, 2,3,4],"features":[{}], "other\"": [{"x": 1}],"n":-1.5e3}"#;
+        for chunk_size in [1, 5, input.len()] {
+            assert_eq!(
+                scan_events(input, chunk_size).unwrap(),
+                [
+                    member("type", r#""FeatureCollection""#),
+                    member("bbox", "[1, 2,3,4]"),
+                    ScanEvent::Feature(b"{}".to_vec()),
+                    member("other\"", r#"[{"x": 1}]"#),
+                    member("n", "-1.5e3"),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert!(scan(b"{}", 1).unwrap().is_empty());
+        assert!(scan(br#" {"features": [] } "#, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn malformed() {
+        for input in [
+            &b""[..],
+            b"[]",
+            br#"{"features": [{}]"#,
+            br#"{"features": null}"#,
+            br#"{"features": [{},]}"#,
+            br#"{"features" [{}]}"#,
+            br#"{"type": "FeatureCollection",}"#,
+            br#"{"features": []} {}"#,
+        ] {
+            assert!(
+                scan(input, 4).is_err(),
+                "{}",
+                String::from_utf8_lossy(input)
+            );
+        }
+    }
+}
@@ -0,0 +1,538 @@
+//! The `crs` member of the [2008 GeoJSON specification](https://geojson.org/geojson-spec.html#coordinate-reference-system-objects),
+//! which RFC 7946 removed.
+use crate::{
+    BboxUpdate, Error, Feature, FeatureCollection, GeoJson, Geometry, GeometryValue, JsonObject,
+    JsonValue, Position, Result,
+};
+
+use serde_json::json;
+use std::fmt;
+
+/// A coordinate reference system, from the `crs` member of the
+/// [2008 GeoJSON specification](https://geojson.org/geojson-spec.html#coordinate-reference-system-objects).
+///
+/// RFC 7946 removed the `crs` member: coordinates are always WGS 84 longitude and latitude, i.e.
+/// [CRS84](Crs::is_crs84). Older data may still have one, which is parsed into the
+/// `foreign_members` of the object it's on, and can be read with `crs()`.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Crs, FeatureCollection};
+///
+/// let collection: FeatureCollection = r#"{
+///   "type": "FeatureCollection",
+///   "crs": { "type": "name", "properties": { "name": "urn:ogc:def:crs:EPSG::3857" } },
+///   "features": []
+/// }"#.parse().unwrap();
+///
+/// let crs = collection.crs().unwrap().unwrap();
+/// assert_eq!(crs, Crs::Named("urn:ogc:def:crs:EPSG::3857".to_string()));
+/// assert_eq!(crs.epsg_code(), Some(3857));
+/// assert!(!collection.is_crs84());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Crs {
+    /// A CRS identified by name, usually a URN like `urn:ogc:def:crs:EPSG::3857`.
+    Named(String),
+    /// A CRS described by the document at `href`, in a format such as `proj4` or `ogcwkt`.
+    Linked {
+        href: String,
+        link_type: Option<String>,
+    },
+}
+
+impl Crs {
+    /// Whether this is [OGC CRS84](https://www.opengis.net/def/crs/OGC/1.3/CRS84): WGS 84
+    /// longitude and latitude, as RFC 7946 requires.
+    ///
+    /// `EPSG:4326` isn't CRS84, as its axes are latitude then longitude. Linked CRSs are never
+    /// taken to be CRS84.
+    pub fn is_crs84(&self) -> bool {
+        let Crs::Named(name) = self else {
+            return false;
+        };
+        let name = name.to_ascii_lowercase();
+        [
+            "crs84",
+            "ogc:crs84",
+            "urn:ogc:def:crs:ogc::crs84",
+            "urn:ogc:def:crs:ogc:1.3:crs84",
+            "http://www.opengis.net/def/crs/ogc/1.3/crs84",
+            "https://www.opengis.net/def/crs/ogc/1.3/crs84",
+        ]
+        .contains(&name.as_str())
+    }
+
+    /// The [EPSG](https://epsg.org) code of a named CRS, e.g. 3857 for `EPSG:3857`,
+    /// `urn:ogc:def:crs:EPSG::3857` or `http://www.opengis.net/def/crs/EPSG/0/3857`.
+    pub fn epsg_code(&self) -> Option<u32> {
+        let Crs::Named(name) = self else {
+            return None;
+        };
+        let name = name.to_ascii_lowercase();
+        let code = if let Some(code) = name.strip_prefix("epsg:") {
+            code
+        } else if name.starts_with("urn:ogc:def:crs:epsg:") {
+            // The version between the authority and code is optional
+            name.rsplit(':').next()?
+        } else if name.contains("opengis.net/def/crs/epsg/") {
+            name.rsplit('/').next()?
+        } else {
+            return None;
+        };
+        code.parse().ok()
+    }
+}
+
+impl fmt::Display for Crs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Crs::Named(name) => f.write_str(name),
+            Crs::Linked { href, .. } => f.write_str(href),
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for Crs {
+    type Error = Error;
+
+    fn try_from(value: &JsonValue) -> Result<Self> {
+        let invalid = |message: &str| Error::InvalidCrs(message.to_string());
+        let properties = value
+            .get("properties")
+            .and_then(JsonValue::as_object)
+            .ok_or_else(|| invalid("expected an object with `type` and `properties` members"))?;
+        let string = |key: &str| properties.get(key).and_then(JsonValue::as_str);
+        match value.get("type").and_then(JsonValue::as_str) {
+            Some("name") => Ok(Crs::Named(
+                string("name")
+                    .ok_or_else(|| invalid("a named CRS must have a `name` string"))?
+                    .to_string(),
+            )),
+            Some("link") => Ok(Crs::Linked {
+                href: string("href")
+                    .ok_or_else(|| invalid("a linked CRS must have an `href` string"))?
+                    .to_string(),
+                link_type: string("type").map(str::to_string),
+            }),
+            _ => Err(invalid("`type` must be \"name\" or \"link\"")),
+        }
+    }
+}
+
+impl From<&Crs> for JsonValue {
+    fn from(crs: &Crs) -> Self {
+        match crs {
+            Crs::Named(name) => json!({ "type": "name", "properties": { "name": name } }),
+            Crs::Linked {
+                href,
+                link_type: None,
+            } => json!({ "type": "link", "properties": { "href": href } }),
+            Crs::Linked {
+                href,
+                link_type: Some(link_type),
+            } => json!({ "type": "link", "properties": { "href": href, "type": link_type } }),
+        }
+    }
+}
+
+/// The CRS in `foreign_members`, if any.
+fn read_crs(foreign_members: &Option<JsonObject>) -> Result<Option<Crs>> {
+    match foreign_members
+        .as_ref()
+        .and_then(|members| members.get("crs"))
+    {
+        // A null `crs` meant the CRS was unknown
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(crs) => Crs::try_from(crs).map(Some),
+    }
+}
+
+impl FeatureCollection {
+    /// The legacy `crs` member of this collection, if it has one, which applies to every
+    /// feature without a `crs` of its own.
+    ///
+    /// Returns [`Error::InvalidCrs`] if it's malformed. See [`Crs`].
+    pub fn crs(&self) -> Result<Option<Crs>> {
+        read_crs(&self.foreign_members)
+    }
+
+    /// Whether the collection's coordinates are in CRS84, as RFC 7946 requires: it and its
+    /// features have no `crs` member, or only CRS84 ones.
+    pub fn is_crs84(&self) -> bool {
+        is_crs84(&self.foreign_members) && self.features.iter().all(Feature::is_crs84)
+    }
+}
+
+impl Feature {
+    /// The legacy `crs` member of this feature, if it has one.
+    ///
+    /// Returns [`Error::InvalidCrs`] if it's malformed. See [`Crs`].
+    pub fn crs(&self) -> Result<Option<Crs>> {
+        read_crs(&self.foreign_members)
+    }
+
+    /// Whether the feature's coordinates are in CRS84, as RFC 7946 requires: it and its geometry
+    /// have no `crs` member, or only CRS84 ones.
+    ///
+    /// A feature in a [`FeatureCollection`] may still inherit another CRS from the collection.
+    pub fn is_crs84(&self) -> bool {
+        is_crs84(&self.foreign_members) && self.geometry.as_ref().is_none_or(Geometry::is_crs84)
+    }
+}
+
+impl Geometry {
+    /// The legacy `crs` member of this geometry, if it has one.
+    ///
+    /// Returns [`Error::InvalidCrs`] if it's malformed. See [`Crs`].
+    pub fn crs(&self) -> Result<Option<Crs>> {
+        read_crs(&self.foreign_members)
+    }
+
+    /// Whether the geometry's coordinates are in CRS84, as RFC 7946 requires: it, and any
+    /// geometries nested in it, have no `crs` member, or only CRS84 ones.
+    pub fn is_crs84(&self) -> bool {
+        is_crs84(&self.foreign_members)
+            && match &self.value {
+                GeometryValue::GeometryCollection { geometries } => {
+                    geometries.iter().all(Geometry::is_crs84)
+                }
+                _ => true,
+            }
+    }
+}
+
+fn is_crs84(foreign_members: &Option<JsonObject>) -> bool {
+    match read_crs(foreign_members) {
+        Ok(crs) => crs.is_none_or(|crs| crs.is_crs84()),
+        Err(_) => false,
+    }
+}
+
+/// Reprojects positions from a [`Crs`] to CRS84, or returns `None` if it doesn't support the
+/// CRS.
+pub(crate) type Reproject = dyn Fn(&Crs, &Position) -> Option<Position> + Send + Sync;
+
+/// Reproject every object with a `crs` member, other than CRS84, with `reproject`, then remove
+/// the member. Objects without a `crs` member inherit their parent's.
+pub(crate) fn normalize(geojson: &mut GeoJson, reproject: &Reproject) -> Result<()> {
+    match geojson {
+        GeoJson::FeatureCollection(collection) => {
+            let crs = take_crs(&mut collection.foreign_members);
+            let mut reprojected = false;
+            for feature in &mut collection.features {
+                reprojected |= normalize_feature(feature, crs.as_ref(), reproject)?;
+            }
+            if reprojected && collection.bbox.is_some() {
+                collection.update_bbox();
+            }
+        }
+        GeoJson::Feature(feature) => {
+            normalize_feature(feature, None, reproject)?;
+        }
+        GeoJson::Geometry(geometry) => {
+            normalize_geometry(geometry, None, reproject)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remove the CRS from `foreign_members`, if any. A malformed one is left as it is, and ignored.
+fn take_crs(foreign_members: &mut Option<JsonObject>) -> Option<Crs> {
+    let crs = read_crs(foreign_members).ok()?;
+    if let Some(members) = foreign_members {
+        members.remove("crs");
+    }
+    crate::util::normalize_foreign_members(foreign_members);
+    crs
+}
+
+/// Returns whether any coordinates were reprojected.
+fn normalize_feature(
+    feature: &mut Feature,
+    crs: Option<&Crs>,
+    reproject: &Reproject,
+) -> Result<bool> {
+    let own_crs = take_crs(&mut feature.foreign_members);
+    let crs = own_crs.as_ref().or(crs);
+    let Some(geometry) = &mut feature.geometry else {
+        return Ok(false);
+    };
+    let reprojected = normalize_geometry(geometry, crs, reproject)?;
+    if reprojected && feature.bbox.is_some() {
+        feature.update_bbox();
+    }
+    Ok(reprojected)
+}
+
+/// Returns whether any coordinates were reprojected.
+fn normalize_geometry(
+    geometry: &mut Geometry,
+    crs: Option<&Crs>,
+    reproject: &Reproject,
+) -> Result<bool> {
+    let own_crs = take_crs(&mut geometry.foreign_members);
+    let crs = own_crs.as_ref().or(crs);
+    if let GeometryValue::GeometryCollection { geometries } = &mut geometry.value {
+        let mut reprojected = false;
+        for geometry in geometries {
+            reprojected |= normalize_geometry(geometry, crs, reproject)?;
+        }
+        if reprojected && geometry.bbox.is_some() {
+            geometry.update_bbox();
+        }
+        return Ok(reprojected);
+    }
+    match crs {
+        None => Ok(false),
+        Some(crs) if crs.is_crs84() => Ok(false),
+        Some(crs) => {
+            geometry.try_map_coords(BboxUpdate::Recompute, |position| {
+                reproject(crs, position).ok_or_else(|| Error::UnsupportedCrs(crs.clone()))
+            })?;
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseOptions;
+
+    #[test]
+    fn parse() {
+        let named = json!({ "type": "name", "properties": { "name": "EPSG:3857" } });
+        let crs = Crs::try_from(&named).unwrap();
+        assert_eq!(crs.epsg_code(), Some(3857));
+        assert_eq!(JsonValue::from(&crs), named);
+
+        let linked = json!({
+            "type": "link",
+            "properties": { "href": "http://example.com/crs/42", "type": "proj4" }
+        });
+        let crs = Crs::try_from(&linked).unwrap();
+        assert_eq!(
+            crs,
+            Crs::Linked {
+                href: "http://example.com/crs/42".to_string(),
+                link_type: Some("proj4".to_string())
+            }
+        );
+        assert_eq!(JsonValue::from(&crs), linked);
+        assert!(!crs.is_crs84());
+        assert_eq!(crs.epsg_code(), None);
+
+        for invalid in [
+            json!("EPSG:3857"),
+            json!({ "type": "name", "properties": { "href": "EPSG:3857" } }),
+            json!({ "type": "EPSG", "properties": { "code": 3857 } }),
+        ] {
+            assert!(matches!(Crs::try_from(&invalid), Err(Error::InvalidCrs(_))));
+        }
+    }
+
+    #[test]
+    fn names() {
+        for name in [
+            "urn:ogc:def:crs:OGC:1.3:CRS84",
+            "urn:ogc:def:crs:OGC::CRS84",
+            "OGC:CRS84",
+            "http://www.opengis.net/def/crs/OGC/1.3/CRS84",
+        ] {
+            assert!(Crs::Named(name.to_string()).is_crs84(), "{name}");
+        }
+        assert!(!Crs::Named("EPSG:4326".to_string()).is_crs84());
+
+        for (name, code) in [
+            ("EPSG:2154", Some(2154)),
+            ("urn:ogc:def:crs:EPSG::3857", Some(3857)),
+            ("urn:ogc:def:crs:EPSG:6.6:27700", Some(27700)),
+            ("http://www.opengis.net/def/crs/EPSG/0/4326", Some(4326)),
+            ("urn:ogc:def:crs:OGC:1.3:CRS84", None),
+            ("EPSG:abc", None),
+        ] {
+            assert_eq!(Crs::Named(name.to_string()).epsg_code(), code, "{name}");
+        }
+    }
+
+    #[test]
+    fn is_crs84() {
+        let mut collection: FeatureCollection = json!({
+            "type": "FeatureCollection",
+            "features": [{
+                "type": "Feature",
+                "properties": null,
+                "geometry": {
+                    "type": "GeometryCollection",
+                    "crs": { "type": "name", "properties": { "name": "OGC:CRS84" } },
+                    "geometries": [{
+                        "type": "Point",
+                        "coordinates": [1.0, 2.0],
+                        "crs": { "type": "name", "properties": { "name": "EPSG:3857" } }
+                    }]
+                }
+            }]
+        })
+        .to_string()
+        .parse()
+        .unwrap();
+        assert_eq!(collection.crs().unwrap(), None);
+        assert!(!collection.is_crs84());
+
+        let geometry = collection.features[0].geometry.as_mut().unwrap();
+        let GeometryValue::GeometryCollection { geometries } = &mut geometry.value else {
+            unreachable!()
+        };
+        geometries[0].foreign_members = None;
+        assert!(collection.is_crs84());
+    }
+
+    #[test]
+    fn reproject_on_load() {
+        let geojson_str = json!({
+            "type": "FeatureCollection",
+            "crs": { "type": "name", "properties": { "name": "urn:ogc:def:crs:EPSG::3857" } },
+            "bbox": [0.0, 0.0, 200.0, 100.0],
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": null,
+                    "geometry": { "type": "LineString", "coordinates": [[0.0, 0.0], [200.0, 100.0]] }
+                },
+                {
+                    "type": "Feature",
+                    "properties": null,
+                    "crs": { "type": "name", "properties": { "name": "OGC:CRS84" } },
+                    "title": "kept",
+                    "geometry": { "type": "Point", "coordinates": [5.0, 6.0] }
+                }
+            ]
+        })
+        .to_string();
+        // Scaling stands in for a real projection
+        let options = ParseOptions::default().with_reprojection(|crs, position| {
+            (crs.epsg_code() == Some(3857))
+                .then(|| Position::from([position[0] / 100.0, position[1] / 100.0]))
+        });
+        let GeoJson::FeatureCollection(collection) =
+            GeoJson::from_str_with_options(&geojson_str, &options).unwrap()
+        else {
+            panic!("expected a FeatureCollection");
+        };
+        assert_eq!(collection.foreign_members, None);
+        assert_eq!(collection.bbox, Some(vec![0.0, 0.0, 5.0, 6.0]));
+        assert_eq!(
+            collection.features[0].geometry.as_ref().unwrap().value,
+            GeometryValue::new_line_string([[0.0, 0.0], [2.0, 1.0]])
+        );
+        let feature = &collection.features[1];
+        assert_eq!(
+            feature.geometry.as_ref().unwrap().value,
+            GeometryValue::new_point([5.0, 6.0])
+        );
+        assert_eq!(feature.foreign_members.as_ref().unwrap().len(), 1);
+
+        // Without a reprojection the crs is left as is
+        let geojson =
+            GeoJson::from_str_with_options(&geojson_str, &ParseOptions::default()).unwrap();
+        assert!(
+            matches!(geojson, GeoJson::FeatureCollection(collection) if collection.crs().unwrap().is_some())
+        );
+
+        let options = ParseOptions::default().with_reprojection(|_, _| None);
+        let err = GeoJson::from_reader_with_options(geojson_str.as_bytes(), &options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Can't reproject from the coordinate reference system `urn:ogc:def:crs:EPSG::3857`"
+        );
+    }
+
+    #[test]
+    fn reproject_nested_crs() {
+        let crs = json!({ "type": "name", "properties": { "name": "EPSG:3857" } });
+        let options = ParseOptions::default().with_reprojection(|_, position| {
+            Some(Position::from([position[0] / 100.0, position[1] / 100.0]))
+        });
+        let parse =
+            |json: JsonValue| GeoJson::from_str_with_options(&json.to_string(), &options).unwrap();
+
+        // Only a feature has a crs
+        let geojson = parse(json!({
+            "type": "FeatureCollection",
+            "bbox": [0.0, 0.0, 200.0, 100.0],
+            "features": [{
+                "type": "Feature",
+                "crs": crs,
+                "bbox": [0.0, 0.0, 200.0, 100.0],
+                "properties": null,
+                "geometry": { "type": "LineString", "coordinates": [[0.0, 0.0], [200.0, 100.0]] }
+            }]
+        }));
+        let GeoJson::FeatureCollection(collection) = geojson else {
+            unreachable!()
+        };
+        assert_eq!(collection.bbox, Some(vec![0.0, 0.0, 2.0, 1.0]));
+        assert_eq!(collection.features[0].bbox, Some(vec![0.0, 0.0, 2.0, 1.0]));
+
+        // Only a feature's geometry has a crs
+        let geojson = parse(json!({
+            "type": "Feature",
+            "bbox": [0.0, 0.0, 200.0, 100.0],
+            "properties": null,
+            "geometry": {
+                "type": "LineString",
+                "crs": crs,
+                "coordinates": [[0.0, 0.0], [200.0, 100.0]]
+            }
+        }));
+        let GeoJson::Feature(feature) = geojson else {
+            unreachable!()
+        };
+        assert_eq!(feature.bbox, Some(vec![0.0, 0.0, 2.0, 1.0]));
+
+        // Only a geometry within nested GeometryCollections has a crs
+        let geojson = parse(json!({
+            "type": "GeometryCollection",
+            "bbox": [0.0, 0.0, 200.0, 100.0],
+            "geometries": [{
+                "type": "GeometryCollection",
+                "bbox": [0.0, 0.0, 200.0, 100.0],
+                "geometries": [
+                    { "type": "Point", "coordinates": [1.0, 1.0] },
+                    { "type": "Point", "crs": crs, "coordinates": [200.0, 100.0] }
+                ]
+            }]
+        }));
+        let GeoJson::Geometry(geometry) = geojson else {
+            unreachable!()
+        };
+        assert_eq!(geometry.bbox, Some(vec![1.0, 1.0, 2.0, 1.0]));
+        let GeometryValue::GeometryCollection { geometries } = &geometry.value else {
+            unreachable!()
+        };
+        assert_eq!(geometries[0].bbox, Some(vec![1.0, 1.0, 2.0, 1.0]));
+
+        // A malformed crs is kept, and the collection's used instead
+        let geojson = parse(json!({
+            "type": "FeatureCollection",
+            "crs": crs,
+            "features": [{
+                "type": "Feature",
+                "crs": { "type": "name" },
+                "properties": null,
+                "geometry": { "type": "Point", "coordinates": [200.0, 100.0] }
+            }]
+        }));
+        let GeoJson::FeatureCollection(collection) = geojson else {
+            unreachable!()
+        };
+        let feature = &collection.features[0];
+        assert!(feature.crs().is_err());
+        assert_eq!(
+            feature.geometry.as_ref().unwrap().value,
+            GeometryValue::new_point([2.0, 1.0])
+        );
+    }
+}
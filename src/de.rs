@@ -82,14 +82,14 @@
 //!     ...
 //! }
 //! ```
-use crate::{Feature, FeatureReader, JsonValue, Result};
+use crate::{Feature, JsonValue, Result};
 
 use std::convert::{TryFrom, TryInto};
 use std::fmt::Formatter;
 use std::io::Read;
 use std::marker::PhantomData;
 
-use serde::de::{Deserialize, Deserializer, Error, IntoDeserializer};
+use serde::de::{Deserialize, DeserializeOwned, Deserializer, Error, IntoDeserializer};
 
 /// Deserialize a GeoJSON FeatureCollection into your custom structs.
 ///
@@ -300,7 +300,8 @@ fn deserialize_error_msg<'de, D: Deserializer<'de>>(
 pub fn deserialize_features_from_feature_collection(
     feature_collection_reader: impl Read,
 ) -> impl Iterator<Item = Result<Feature>> {
-    FeatureReader::from_reader(feature_collection_reader).features()
+    #[allow(deprecated)]
+    crate::FeatureIterator::new(feature_collection_reader)
 }
 
 /// Deserialize a single GeoJSON Feature into your custom struct.
@@ -399,6 +400,17 @@ where
     Ok(deserializer.deserialize_map(visitor)?)
 }
 
+/// Deserialize a single GeoJSON Feature from `bytes` into `T`, like [`deserialize_single_feature`].
+pub(crate) fn feature_from_slice<T>(bytes: &[u8]) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let record = deserializer.deserialize_map(FeatureVisitor::new())?;
+    deserializer.end()?;
+    Ok(record)
+}
+
 struct FeatureVisitor<D> {
     _marker: PhantomData<D>,
 }
@@ -1,10 +1,14 @@
 //! Module for all GeoJSON-related errors
+use crate::Crs;
 use crate::Feature;
+use crate::StrictViolation;
+use crate::WkbErrorKind;
 use crate::geometry::deserialize::GeometryType;
 use thiserror::Error;
 
 /// Errors which can occur when encoding, decoding, and converting GeoJSON
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error("invalid writer state: {0}")]
     InvalidWriterState(&'static str),
@@ -36,6 +40,41 @@ pub enum Error {
     ExpectedType { expected: String, actual: String },
     #[error("A position must contain two or more elements, but got `{0}`")]
     PositionTooShort(usize),
+    #[error("Strict mode violation at line {line}, column {column}: {violation}")]
+    StrictModeViolation {
+        violation: StrictViolation,
+        line: usize,
+        column: usize,
+    },
+    #[error("Can't write the non-finite coordinate `{0}`")]
+    NonFiniteCoordinate(f64),
+    #[error("Invalid WKT at byte {offset}: {message}")]
+    InvalidWkt { message: String, offset: usize },
+    #[error("Invalid WKB at byte {offset}: {kind}")]
+    InvalidWkb { kind: WkbErrorKind, offset: usize },
+    #[error("Can't write {0} elements in WKB, which allows at most {max}", max = u32::MAX)]
+    WkbTooManyElements(usize),
+    #[error("TopoJSON arc index `{0}` is out of bounds")]
+    TopoJsonArcOutOfBounds(i32),
+    #[error("A TopoJSON topology can't have more than {max} arcs", max = i32::MAX)]
+    TopoJsonTooManyArcs,
+    #[error("Invalid encoded polyline at byte {offset}: {message}")]
+    InvalidPolyline { message: String, offset: usize },
+    #[error("Encoded polyline precision {0} is more than the 15 decimal places of an f64")]
+    InvalidPolylinePrecision(u32),
+    #[error("Can't encode the coordinate `{coordinate:?}` of position {index} as a polyline")]
+    UnencodablePolyline { coordinate: f64, index: usize },
+    #[error("Invalid vector tile at byte {offset}: {message}")]
+    InvalidMvt { message: String, offset: usize },
+    #[error("Invalid property schema: {0}")]
+    InvalidPropertySchema(String),
+    #[error("Invalid `crs` member: {0}")]
+    InvalidCrs(String),
+    #[error("Can't reproject from the coordinate reference system `{0}`")]
+    UnsupportedCrs(Crs),
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {0}")]
+    Arrow(arrow_schema::ArrowError),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -46,6 +85,13 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+#[cfg(feature = "arrow")]
+impl From<arrow_schema::ArrowError> for Error {
+    fn from(error: arrow_schema::ArrowError) -> Self {
+        Self::Arrow(error)
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
         Self::Io(error)
@@ -13,7 +13,7 @@
 // limitations under the License.
 #![allow(deprecated)]
 
-use crate::{Feature, Result};
+use crate::{Feature, FeatureReader, Result};
 
 use serde::Deserialize;
 use std::io;
@@ -30,78 +30,23 @@ use std::marker::PhantomData;
 /// This has the benefit of not having to wait until the end of the
 /// stream to get results, and avoids having to allocate memory for the complete collection.
 ///
-/// Based on example code found at <https://github.com/serde-rs/serde/issues/903#issuecomment-297488118>.
-///
 /// [GeoJSON Format Specification § 3.3](https://datatracker.ietf.org/doc/html/rfc7946#section-3.3)
 pub struct FeatureIterator<'de, R, D = Feature> {
-    reader: R,
-    state: State,
+    reader: FeatureReader<R>,
     output: PhantomData<D>,
     lifetime: PhantomData<&'de ()>,
 }
 
-#[allow(clippy::enum_variant_names)]
-#[derive(Debug, Copy, Clone)]
-enum State {
-    BeforeFeatures,
-    DuringFeatures,
-    AfterFeatures,
-}
-
-impl<R, D> FeatureIterator<'_, R, D> {
+impl<R: io::Read, D> FeatureIterator<'_, R, D> {
     pub fn new(reader: R) -> Self {
         FeatureIterator {
-            reader,
-            state: State::BeforeFeatures,
+            reader: FeatureReader::from_reader(reader),
             output: PhantomData,
             lifetime: PhantomData,
         }
     }
 }
 
-impl<R, D> FeatureIterator<'_, R, D>
-where
-    R: io::Read,
-{
-    fn seek_to_next_feature(&mut self) -> Result<bool> {
-        let mut next_bytes = [0];
-        loop {
-            self.reader.read_exact(&mut next_bytes)?;
-            let next_byte = next_bytes[0] as char;
-            if next_byte.is_whitespace() {
-                continue;
-            }
-
-            match (self.state, next_byte) {
-                (State::BeforeFeatures, '[') => {
-                    self.state = State::DuringFeatures;
-                    return Ok(true);
-                }
-                (State::BeforeFeatures, _) => {
-                    continue;
-                }
-                (State::DuringFeatures, ',') => {
-                    return Ok(true);
-                }
-                (State::DuringFeatures, ']') => {
-                    self.state = State::AfterFeatures;
-                    return Ok(false);
-                }
-                (State::AfterFeatures, _) => {
-                    unreachable!("should not seek if we've already finished processing features")
-                }
-                _ => {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidInput,
-                        format!("next byte: {}", next_byte),
-                    )
-                    .into());
-                }
-            }
-        }
-    }
-}
-
 impl<'de, R, D> Iterator for FeatureIterator<'de, R, D>
 where
     R: io::Read,
@@ -110,20 +55,17 @@ where
     type Item = Result<D>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.seek_to_next_feature() {
-            Ok(true) => {}
-            Ok(false) => return None,
-            Err(err) => {
-                return Some(Err(err));
-            }
-        }
-
-        let de = serde_json::Deserializer::from_reader(&mut self.reader);
-        match de.into_iter().next() {
-            Some(Ok(v)) => Some(Ok(v)),
-            Some(Err(err)) => Some(Err(err.into())),
-            None => None,
-        }
+        let feature = match self.reader.next_feature()? {
+            Ok(feature) => feature,
+            Err(err) => return Some(Err(err)),
+        };
+        // Reading from a slice would tie `D` to the lifetime of `feature`
+        let mut de = serde_json::Deserializer::from_reader(feature.as_slice());
+        Some(
+            D::deserialize(&mut de)
+                .and_then(|value| de.end().map(|()| value))
+                .map_err(Into::into),
+        )
     }
 }
 
@@ -229,6 +171,17 @@ mod tests {
         assert!(fi.next().is_none());
     }
 
+    #[test]
+    fn unquoted_key() {
+        let input = r#"{type: "FeatureCollection", "features": []}"#;
+        let results: Vec<Result<Feature>> =
+            FeatureIterator::new(BufReader::new(input.as_bytes())).collect();
+        assert!(matches!(
+            results[..],
+            [Err(crate::Error::MalformedGeoJson(_))]
+        ));
+    }
+
     mod field_ordering {
         use super::*;
         use crate::Feature;
@@ -237,8 +190,8 @@ mod tests {
         fn type_field_before_features_field() {
             let type_first = r#"
               {
-                type: "FeatureCollection",
-                features: [
+                "type": "FeatureCollection",
+                "features": [
                   {
                     "type": "Feature",
                     "geometry": {
@@ -269,7 +222,7 @@ mod tests {
         fn features_field_before_type_field() {
             let type_first = r#"
               {
-                features: [
+                "features": [
                   {
                     "type": "Feature",
                     "geometry": {
@@ -287,7 +240,7 @@ mod tests {
                     "properties": { }
                   }
                 ],
-                type: "FeatureCollection"
+                "type": "FeatureCollection"
               }
             "#;
             let features: Vec<Feature> =
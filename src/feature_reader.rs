@@ -1,19 +1,71 @@
-use crate::de::deserialize_feature_collection;
-use crate::{Feature, Result};
+use crate::collection_scanner::{CollectionMembers, CollectionScanner, ScanEvent};
+use crate::de::feature_from_slice;
+use crate::{Bbox, Feature, JsonObject, Result};
 
 use serde::de::DeserializeOwned;
 
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 
 /// Enumerates individual Features from a GeoJSON FeatureCollection
+///
+/// The collection's other members, like its `bbox` and any foreign members, are read as they're
+/// encountered, wherever they appear. See [`FeatureReader::bbox`] and
+/// [`FeatureReader::foreign_members`].
 pub struct FeatureReader<R> {
-    reader: R,
+    reader: BufReader<R>,
+    scanner: CollectionScanner,
+    members: CollectionMembers,
+    finished: bool,
 }
 
 impl<R: Read> FeatureReader<R> {
     /// Create a FeatureReader from the given `reader`.
     pub fn from_reader(reader: R) -> Self {
-        Self { reader }
+        Self {
+            reader: BufReader::new(reader),
+            scanner: CollectionScanner::default(),
+            members: CollectionMembers::default(),
+            finished: false,
+        }
+    }
+
+    /// The [`bbox`](https://tools.ietf.org/html/rfc7946#section-5) of the FeatureCollection, if
+    /// it has one.
+    ///
+    /// Members are read as they're reached, so a bbox written after the `features` array is only
+    /// available once every feature has been read.
+    pub fn bbox(&self) -> Option<&Bbox> {
+        self.members.bbox.as_ref()
+    }
+
+    /// The [foreign members](https://datatracker.ietf.org/doc/html/rfc7946#section-6.1) of the
+    /// FeatureCollection, if it has any.
+    ///
+    /// Members are read as they're reached, so foreign members written after the `features`
+    /// array are only available once every feature has been read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::FeatureReader;
+    ///
+    /// let feature_collection_string = r#"{
+    ///     "type": "FeatureCollection",
+    ///     "title": "Islands",
+    ///     "features": [
+    ///         { "type": "Feature", "geometry": { "type": "Point", "coordinates": [125.6, 10.1] }, "properties": null }
+    ///     ],
+    ///     "bbox": [125.6, 10.1, 125.6, 10.1]
+    /// }"#;
+    ///
+    /// let mut feature_reader = FeatureReader::from_reader(feature_collection_string.as_bytes());
+    /// assert_eq!(feature_reader.features().count(), 1);
+    ///
+    /// assert_eq!(feature_reader.foreign_members().unwrap()["title"], "Islands");
+    /// assert_eq!(feature_reader.bbox(), Some(&vec![125.6, 10.1, 125.6, 10.1]));
+    /// ```
+    pub fn foreign_members(&self) -> Option<&JsonObject> {
+        self.members.foreign_members.as_ref()
     }
 
     /// Iterate over the individual [`Feature`s](Feature) of a FeatureCollection.
@@ -48,7 +100,7 @@ impl<R: Read> FeatureReader<R> {
     /// let io_reader = std::io::BufReader::new(feature_collection_string);
     ///
     /// use geojson::FeatureReader;
-    /// let feature_reader = FeatureReader::from_reader(io_reader);
+    /// let mut feature_reader = FeatureReader::from_reader(io_reader);
     /// for feature in feature_reader.features() {
     ///     let feature = feature.expect("valid geojson feature");
     ///
@@ -64,9 +116,11 @@ impl<R: Read> FeatureReader<R> {
     ///     }
     /// }
     /// ```
-    pub fn features(self) -> impl Iterator<Item = Result<Feature>> {
-        #[allow(deprecated)]
-        crate::FeatureIterator::new(self.reader)
+    pub fn features(&mut self) -> impl Iterator<Item = Result<Feature>> {
+        std::iter::from_fn(|| {
+            self.next_feature()
+                .map(|feature| Ok(serde_json::from_slice(&feature?)?))
+        })
     }
 
     /// Deserialize the features of FeatureCollection into your own custom
@@ -127,7 +181,7 @@ impl<R: Read> FeatureReader<R> {
     /// #     age: u64,
     /// # }
     ///
-    /// let feature_reader = FeatureReader::from_reader(io_reader);
+    /// let mut feature_reader = FeatureReader::from_reader(io_reader);
     /// for feature in feature_reader.deserialize::<MyStruct>().unwrap() {
     ///     let my_struct = feature.expect("valid geojson feature");
     ///
@@ -151,14 +205,56 @@ impl<R: Read> FeatureReader<R> {
     ///     age: u64,
     /// }
     /// ```
-    pub fn deserialize<D: DeserializeOwned>(self) -> Result<impl Iterator<Item = Result<D>>> {
-        deserialize_feature_collection(self.reader)
+    pub fn deserialize<D: DeserializeOwned>(&mut self) -> Result<impl Iterator<Item = Result<D>>> {
+        Ok(std::iter::from_fn(|| {
+            self.next_feature()
+                .map(|feature| feature_from_slice(&feature?))
+        }))
+    }
+
+    /// The raw JSON of the next feature, reading any other members of the collection on the way.
+    pub(crate) fn next_feature(&mut self) -> Option<Result<Vec<u8>>> {
+        while !self.finished {
+            let result = match self.scan() {
+                Ok(Some(ScanEvent::Feature(feature))) => return Some(Ok(feature)),
+                Ok(Some(ScanEvent::Member { key, value })) => self.members.add(key, &value),
+                Ok(None) => Ok(()),
+                Err(err) => {
+                    self.finished = true;
+                    Err(err)
+                }
+            };
+            if let Err(err) = result {
+                return Some(Err(err));
+            }
+        }
+        None
+    }
+
+    /// Read up to the next feature or member, or the end of the collection.
+    fn scan(&mut self) -> Result<Option<ScanEvent>> {
+        loop {
+            let available = self.reader.fill_buf()?;
+            if available.is_empty() {
+                self.finished = true;
+                self.scanner.finish()?;
+                return Ok(None);
+            }
+            let (consumed, event) = self.scanner.feed(available)?;
+            self.reader.consume(consumed);
+            // Don't wait on any input following the collection
+            self.finished = self.scanner.is_finished();
+            if event.is_some() || self.finished {
+                return Ok(event);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Error, JsonValue};
 
     use serde::Deserialize;
     use serde_json::json;
@@ -206,7 +302,7 @@ mod tests {
     fn deserialize_into_type() {
         let feature_collection_string = feature_collection_string();
         let mut bytes_reader = feature_collection_string.as_bytes();
-        let feature_reader = FeatureReader::from_reader(&mut bytes_reader);
+        let mut feature_reader = FeatureReader::from_reader(&mut bytes_reader);
 
         let records: Vec<MyRecord> = feature_reader
             .deserialize()
@@ -230,4 +326,67 @@ mod tests {
         assert_eq!(records[1].name, "Neverland");
         assert_eq!(records[1].age, 456);
     }
+
+    #[test]
+    fn members_around_features() {
+        let input = json!({
+            "type": "FeatureCollection",
+            "bbox": [2.3, 4.5, 125.6, 10.1],
+            "before": [[1, 2], { "features": [] }],
+            "features": serde_json::from_str::<JsonValue>(&feature_collection_string()).unwrap()["features"],
+            "after": "]}"
+        })
+        .to_string();
+        let mut feature_reader = FeatureReader::from_reader(input.as_bytes());
+        let features: Vec<Feature> = feature_reader.features().map(Result::unwrap).collect();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[0].property("name").unwrap(), "Dinagat Islands");
+
+        assert_eq!(feature_reader.bbox(), Some(&vec![2.3, 4.5, 125.6, 10.1]));
+        let foreign_members = feature_reader.foreign_members().unwrap();
+        assert_eq!(foreign_members.len(), 2);
+        assert_eq!(
+            foreign_members["before"],
+            json!([[1, 2], { "features": [] }])
+        );
+        assert_eq!(foreign_members["after"], "]}");
+    }
+
+    #[test]
+    fn members_after_features() {
+        let input =
+            r#"{"features": [], "bbox": [1, 2, 3, 4], "type": "FeatureCollection", "x": null}"#;
+        let mut feature_reader = FeatureReader::from_reader(input.as_bytes());
+        // Nothing's been read yet
+        assert_eq!(feature_reader.bbox(), None);
+        assert_eq!(feature_reader.deserialize::<MyRecord>().unwrap().count(), 0);
+        assert_eq!(feature_reader.bbox(), Some(&vec![1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(
+            feature_reader.foreign_members().unwrap()["x"],
+            JsonValue::Null
+        );
+    }
+
+    #[test]
+    fn without_members() {
+        let mut feature_reader = FeatureReader::from_reader(r#"{"features": []}"#.as_bytes());
+        assert_eq!(feature_reader.features().count(), 0);
+        assert_eq!(feature_reader.bbox(), None);
+        assert_eq!(feature_reader.foreign_members(), None);
+    }
+
+    #[test]
+    fn malformed() {
+        let input = r#"{"type": "Feature", "features": []}"#;
+        let results: Vec<_> = FeatureReader::from_reader(input.as_bytes())
+            .features()
+            .collect();
+        assert!(matches!(results[..], [Err(Error::ExpectedType { .. })]));
+
+        let input = r#"{"type": "FeatureCollection", "features": [{"#;
+        let results: Vec<_> = FeatureReader::from_reader(input.as_bytes())
+            .features()
+            .collect();
+        assert!(matches!(results[..], [Err(Error::MalformedGeoJson(_))]));
+    }
 }
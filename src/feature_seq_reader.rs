@@ -0,0 +1,360 @@
+use crate::de::feature_from_slice;
+use crate::{Error, Feature, Result};
+
+use serde::de::DeserializeOwned;
+
+use std::io::{BufRead, BufReader, Read};
+
+/// The record separator which begins each record of a GeoJSON Text Sequence.
+pub(crate) const RECORD_SEPARATOR: u8 = 0x1E;
+
+/// How the records of a sequence of Features are delimited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeqFormat {
+    /// A [GeoJSON Text Sequence](https://tools.ietf.org/html/rfc8142), where each record begins
+    /// with an ASCII record separator (`0x1E`) and ends with a line feed.
+    TextSequence,
+    /// Newline-delimited GeoJSON, where each record is a single line.
+    NewlineDelimited,
+}
+
+/// Enumerates individual Features from a [GeoJSON Text Sequence](https://tools.ietf.org/html/rfc8142)
+/// or newline-delimited GeoJSON.
+///
+/// Unlike [`FeatureReader`](crate::FeatureReader), a record which can't be parsed doesn't end the
+/// sequence. An error is returned for that record, and the following records are read as usual.
+/// To skip invalid records entirely, see [`FeatureSeqReader::with_skip_invalid`].
+///
+/// # Examples
+///
+/// ```
+/// use geojson::FeatureSeqReader;
+///
+/// let text_sequence = "\x1e{\"type\":\"Feature\",\"geometry\":null,\"properties\":{\"name\":\"first\"}}\n\
+///                      \x1e{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coor\n\
+///                      \x1e{\"type\":\"Feature\",\"geometry\":null,\"properties\":{\"name\":\"third\"}}\n";
+///
+/// let features: Vec<_> = FeatureSeqReader::from_reader(text_sequence.as_bytes())
+///     .features()
+///     .collect();
+///
+/// assert_eq!(features.len(), 3);
+/// assert_eq!(features[0].as_ref().unwrap().property("name").unwrap(), "first");
+/// // The truncated record is an error, but doesn't stop the rest from being read
+/// assert!(features[1].is_err());
+/// assert_eq!(features[2].as_ref().unwrap().property("name").unwrap(), "third");
+/// ```
+pub struct FeatureSeqReader<R> {
+    reader: BufReader<R>,
+    format: Option<SeqFormat>,
+    skip_invalid: bool,
+}
+
+impl<R: Read> FeatureSeqReader<R> {
+    /// Create a FeatureSeqReader from the given `reader`.
+    ///
+    /// The format is detected from the input: if it begins with a record separator (`0x1E`),
+    /// it's read as a GeoJSON Text Sequence, otherwise as newline-delimited GeoJSON.
+    pub fn from_reader(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            format: None,
+            skip_invalid: false,
+        }
+    }
+
+    /// Read the input as the given format, rather than detecting it.
+    pub fn with_format(mut self, format: SeqFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Whether to silently skip records which can't be parsed, as allowed by
+    /// [RFC 8142 § 2.4](https://tools.ietf.org/html/rfc8142#section-2.4), rather than returning
+    /// an error for them. Defaults to `false`.
+    ///
+    /// IO errors are always returned.
+    pub fn with_skip_invalid(mut self, skip_invalid: bool) -> Self {
+        self.skip_invalid = skip_invalid;
+        self
+    }
+
+    /// Iterate over the individual [`Feature`s](Feature) of the sequence.
+    ///
+    /// If instead you'd like to deserialize directly to your own struct, see
+    /// [`FeatureSeqReader::deserialize`].
+    pub fn features(self) -> impl Iterator<Item = Result<Feature>> {
+        self.parse_records(|record| Ok(serde_json::from_slice(record)?))
+    }
+
+    /// Deserialize the features of the sequence into your own custom struct using the
+    /// [`serde`](../../serde) crate.
+    ///
+    /// As with [`de::deserialize_feature_collection`](crate::de::deserialize_feature_collection),
+    /// your struct's `geometry` field is deserialized from each feature's geometry, and its other
+    /// fields from the feature's properties.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::FeatureSeqReader;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct MyStruct {
+    ///     geometry: geojson::Geometry,
+    ///     name: String,
+    /// }
+    ///
+    /// let newline_delimited = r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[1.0,2.0]},"properties":{"name":"first"}}
+    /// {"type":"Feature","geometry":{"type":"Point","coordinates":[3.0,4.0]},"properties":{"name":"second"}}
+    /// "#;
+    ///
+    /// let reader = FeatureSeqReader::from_reader(newline_delimited.as_bytes());
+    /// let my_structs: Vec<MyStruct> = reader
+    ///     .deserialize()
+    ///     .collect::<geojson::Result<_>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(my_structs[1].name, "second");
+    /// ```
+    pub fn deserialize<D: DeserializeOwned>(self) -> impl Iterator<Item = Result<D>> {
+        self.parse_records(feature_from_slice)
+    }
+
+    fn parse_records<T>(
+        self,
+        parse: impl Fn(&[u8]) -> Result<T>,
+    ) -> impl Iterator<Item = Result<T>> {
+        let skip_invalid = self.skip_invalid;
+        let records = Records {
+            reader: self.reader,
+            format: self.format,
+            buffer: vec![],
+            finished: false,
+        };
+        records.filter_map(
+            move |record| match record.and_then(|record| parse(&record)) {
+                Err(Error::Io(err)) => Some(Err(Error::Io(err))),
+                Err(err) if skip_invalid => {
+                    log::warn!("skipping invalid record in GeoJSON sequence: {err}");
+                    None
+                }
+                result => Some(result),
+            },
+        )
+    }
+}
+
+/// The raw bytes of each non-empty record of a sequence.
+struct Records<R> {
+    reader: BufReader<R>,
+    format: Option<SeqFormat>,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+fn is_separator(byte: &u8) -> bool {
+    byte.is_ascii_whitespace() || *byte == RECORD_SEPARATOR
+}
+
+impl<R: Read> Records<R> {
+    fn detect_format(&mut self) -> std::io::Result<SeqFormat> {
+        loop {
+            let available = self.reader.fill_buf()?;
+            match available
+                .iter()
+                .position(|byte| !byte.is_ascii_whitespace())
+            {
+                Some(idx) if available[idx] == RECORD_SEPARATOR => {
+                    return Ok(SeqFormat::TextSequence);
+                }
+                Some(_) => return Ok(SeqFormat::NewlineDelimited),
+                // Only whitespace so far, or the end of input
+                None if available.is_empty() => return Ok(SeqFormat::NewlineDelimited),
+                None => {
+                    let len = available.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    fn next_record(&mut self) -> std::io::Result<Option<Vec<u8>>> {
+        let format = match self.format {
+            Some(format) => format,
+            None => {
+                let format = self.detect_format()?;
+                self.format = Some(format);
+                format
+            }
+        };
+        let delimiter = match format {
+            SeqFormat::TextSequence => RECORD_SEPARATOR,
+            SeqFormat::NewlineDelimited => b'\n',
+        };
+        loop {
+            self.buffer.clear();
+            if self.reader.read_until(delimiter, &mut self.buffer)? == 0 {
+                return Ok(None);
+            }
+            let Some(start) = self.buffer.iter().position(|byte| !is_separator(byte)) else {
+                // An empty record, e.g. a blank line
+                continue;
+            };
+            let end = self
+                .buffer
+                .iter()
+                .rposition(|byte| !is_separator(byte))
+                .unwrap_or(start);
+            return Ok(Some(self.buffer[start..=end].to_vec()));
+        }
+    }
+}
+
+impl<R: Read> Iterator for Records<R> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.next_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(err) => {
+                self.finished = true;
+                Some(Err(err.into()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Geometry;
+
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct MyRecord {
+        geometry: crate::Geometry,
+        name: String,
+        age: u64,
+    }
+
+    fn record(name: &str, age: u64) -> String {
+        format!(
+            r#"{{"type":"Feature","geometry":{{"type":"Point","coordinates":[{age}.0,1.0]}},"properties":{{"name":"{name}","age":{age}}}}}"#
+        )
+    }
+
+    fn names(features: impl Iterator<Item = Result<Feature>>) -> Vec<Option<String>> {
+        features
+            .map(|feature| {
+                feature.ok().map(|feature| {
+                    feature
+                        .property("name")
+                        .unwrap()
+                        .as_str()
+                        .unwrap()
+                        .to_string()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn text_sequence() {
+        let input = format!("\x1e{}\n\x1e{}\n", record("Mishka", 12), record("Jane", 22));
+        let features: Vec<Feature> = FeatureSeqReader::from_reader(input.as_bytes())
+            .features()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(features.len(), 2);
+        assert_eq!(features[1].geometry, Some(Geometry::new_point([22.0, 1.0])));
+    }
+
+    #[test]
+    fn newline_delimited() {
+        let input = format!("{}\r\n\n{}", record("Mishka", 12), record("Jane", 22));
+        let records: Vec<MyRecord> = FeatureSeqReader::from_reader(input.as_bytes())
+            .deserialize()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "Mishka");
+        assert_eq!(records[1].age, 22);
+        assert_eq!(records[1].geometry, Geometry::new_point([22.0, 1.0]));
+    }
+
+    #[test]
+    fn empty() {
+        for input in ["", "\n\n", "\x1e\n\x1e"] {
+            assert_eq!(
+                FeatureSeqReader::from_reader(input.as_bytes())
+                    .features()
+                    .count(),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn continue_after_invalid_records() {
+        let input = format!(
+            "\x1e{}\n\x1e{{\"type\": \"Feat\n\x1e[1, 2]\n\x1e{}\n\x1e{{",
+            record("Mishka", 12),
+            record("Jane", 22)
+        );
+        assert_eq!(
+            names(FeatureSeqReader::from_reader(input.as_bytes()).features()),
+            [
+                Some("Mishka".to_string()),
+                None,
+                None,
+                Some("Jane".to_string()),
+                None
+            ]
+        );
+        assert_eq!(
+            names(
+                FeatureSeqReader::from_reader(input.as_bytes())
+                    .with_skip_invalid(true)
+                    .features()
+            ),
+            [Some("Mishka".to_string()), Some("Jane".to_string())]
+        );
+
+        let records: Vec<MyRecord> = FeatureSeqReader::from_reader(input.as_bytes())
+            .with_skip_invalid(true)
+            .deserialize()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+    }
+
+    #[test]
+    fn forced_format() {
+        // Several records per line aren't newline-delimited
+        let input = format!("\x1e{}\x1e{}\n", record("Mishka", 12), record("Jane", 22));
+        assert_eq!(
+            FeatureSeqReader::from_reader(input.as_bytes())
+                .with_format(SeqFormat::NewlineDelimited)
+                .features()
+                .filter(Result::is_ok)
+                .count(),
+            0
+        );
+        assert_eq!(
+            FeatureSeqReader::from_reader(input.as_bytes())
+                .features()
+                .filter(Result::is_ok)
+                .count(),
+            2
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::feature_seq_reader::RECORD_SEPARATOR;
+use crate::ser::{to_feature_writer, to_feature_writer_with_options};
+use crate::{Feature, Result, SeqFormat, WriteOptions};
+
+use serde::Serialize;
+use std::io::Write;
+
+/// Write Features as a [GeoJSON Text Sequence](https://tools.ietf.org/html/rfc8142) or as
+/// newline-delimited GeoJSON.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Feature, FeatureSeqWriter, Geometry, SeqFormat};
+///
+/// let mut output: Vec<u8> = vec![];
+/// {
+///     let mut writer = FeatureSeqWriter::from_writer(&mut output);
+///     writer.write_feature(&Feature::from(Geometry::new_point([1.0, 2.0]))).unwrap();
+/// }
+/// assert_eq!(
+///     output,
+///     b"\x1e{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[1.0,2.0]},\"properties\":null}\n"
+/// );
+///
+/// let mut output: Vec<u8> = vec![];
+/// {
+///     let mut writer = FeatureSeqWriter::from_writer(&mut output)
+///         .with_format(SeqFormat::NewlineDelimited);
+///     writer.write_feature(&Feature::from(Geometry::new_point([1.0, 2.0]))).unwrap();
+/// }
+/// assert_eq!(
+///     output,
+///     b"{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[1.0,2.0]},\"properties\":null}\n"
+/// );
+/// ```
+pub struct FeatureSeqWriter<W: Write> {
+    writer: W,
+    format: SeqFormat,
+    options: WriteOptions,
+}
+
+impl<W: Write> FeatureSeqWriter<W> {
+    /// Create a FeatureSeqWriter from the given `writer`, writing a GeoJSON Text Sequence.
+    ///
+    /// To write newline-delimited GeoJSON instead, use [`FeatureSeqWriter::with_format`].
+    pub fn from_writer(writer: W) -> Self {
+        Self {
+            writer,
+            format: SeqFormat::TextSequence,
+            options: WriteOptions::default(),
+        }
+    }
+
+    /// Write records in the given format.
+    pub fn with_format(mut self, format: SeqFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Apply the given [`WriteOptions`] to every feature written.
+    pub fn with_options(mut self, options: WriteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Write a [`crate::Feature`] struct to the output stream. If you'd like to
+    /// serialize your own custom structs, see [`FeatureSeqWriter::serialize`] instead.
+    pub fn write_feature(&mut self, feature: &Feature) -> Result<()> {
+        self.write_record(|writer, options| options.to_writer(writer, feature))
+    }
+
+    /// Serialize your own custom struct as a feature of the sequence using the [`serde`] crate.
+    ///
+    /// As with [`FeatureWriter::serialize`](crate::FeatureWriter::serialize), your struct must
+    /// have a field called `geometry`, and all its other fields are written as the feature's
+    /// properties.
+    pub fn serialize<S: Serialize>(&mut self, value: &S) -> Result<()> {
+        self.write_record(|writer, options| {
+            if options.is_identity() {
+                to_feature_writer(writer, value)
+            } else {
+                to_feature_writer_with_options(writer, value, options)
+            }
+        })
+    }
+
+    /// Flush the underlying writer buffer.
+    ///
+    /// You shouldn't normally need to call this manually, as the writer will flush itself upon
+    /// being dropped.
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+
+    fn write_record(
+        &mut self,
+        write_feature: impl FnOnce(&mut W, &WriteOptions) -> Result<()>,
+    ) -> Result<()> {
+        if self.format == SeqFormat::TextSequence {
+            self.writer.write_all(&[RECORD_SEPARATOR])?;
+        }
+        write_feature(&mut self.writer, &self.options)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for FeatureSeqWriter<W> {
+    fn drop(&mut self) {
+        _ = self.flush().map_err(|e| {
+            log::error!("FeatureSeqWriter errored while flushing in Drop impl. To handle errors like this, explicitly call `FeatureSeqWriter::flush`. Error: {}", e);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeatureSeqReader, Geometry, Precision};
+
+    #[derive(Serialize)]
+    struct MyRecord {
+        geometry: crate::Geometry,
+        name: String,
+        age: u64,
+    }
+
+    #[test]
+    fn roundtrip() {
+        for format in [SeqFormat::TextSequence, SeqFormat::NewlineDelimited] {
+            let mut buffer: Vec<u8> = vec![];
+            {
+                let mut writer = FeatureSeqWriter::from_writer(&mut buffer).with_format(format);
+                writer
+                    .write_feature(&Feature::from(Geometry::new_point([1.1, 1.2])))
+                    .unwrap();
+                writer
+                    .serialize(&MyRecord {
+                        geometry: Geometry::new_point([2.1, 2.2]),
+                        name: "Jane".to_string(),
+                        age: 22,
+                    })
+                    .unwrap();
+            }
+
+            let features: Vec<Feature> = FeatureSeqReader::from_reader(buffer.as_slice())
+                .features()
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(features.len(), 2);
+            assert_eq!(features[0].geometry, Some(Geometry::new_point([1.1, 1.2])));
+            assert_eq!(features[1].property("name").unwrap(), "Jane");
+        }
+    }
+
+    #[test]
+    fn with_options() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let options = WriteOptions::default().with_precision(Precision::DecimalPlaces(1));
+            let mut writer = FeatureSeqWriter::from_writer(&mut buffer)
+                .with_format(SeqFormat::NewlineDelimited)
+                .with_options(options);
+            writer
+                .serialize(&MyRecord {
+                    geometry: Geometry::new_point([2.14, 2.26]),
+                    name: "Jane".to_string(),
+                    age: 22,
+                })
+                .unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[2.1,2.3]},\"properties\":{\"age\":22,\"name\":\"Jane\"}}\n"
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use crate::ser::to_feature_writer;
-use crate::{Error, Feature, Result};
+use crate::bbox::BboxBuilder;
+use crate::ser::{to_feature, to_feature_writer};
+use crate::{Error, Feature, Result, WriteOptions};
 
 use serde::Serialize;
 use std::io::Write;
@@ -12,53 +13,235 @@ enum State {
     Finished,
 }
 
-/// Write Features to a FeatureCollection
-pub struct FeatureWriter<W: Write> {
-    writer: W,
+/// Writes a FeatureCollection around its features, whichever kind of writer the output goes to.
+pub(crate) struct CollectionEncoder {
     state: State,
+    // `Some` if the collection's bbox should be written after its features
+    bbox: Option<BboxBuilder>,
+    options: WriteOptions,
 }
 
-impl<W: Write> FeatureWriter<W> {
-    /// Create a FeatureWriter from the given `writer`.
-    ///
-    /// To append features from your custom structs, use [`FeatureWriter::serialize`].
-    ///
-    /// To append features from [`Feature`] use [`FeatureWriter::write_feature`].
-    ///
-    /// To write a foreign member, use [`FeatureWriter::write_foreign_member`] before appending any
-    /// features.
-    pub fn from_writer(writer: W) -> Self {
+impl Default for CollectionEncoder {
+    fn default() -> Self {
         Self {
-            writer,
             state: State::New,
+            bbox: None,
+            options: WriteOptions::default(),
         }
     }
+}
 
-    /// Write a [`crate::Feature`] struct to the output stream. If you'd like to
-    /// serialize your own custom structs, see [`FeatureWriter::serialize`] instead.
-    pub fn write_feature(&mut self, feature: &Feature) -> Result<()> {
+impl CollectionEncoder {
+    pub(crate) fn set_options(&mut self, options: WriteOptions) {
+        self.options = options;
+    }
+
+    pub(crate) fn set_bbox(&mut self, write_bbox: bool) {
+        self.bbox = write_bbox.then(BboxBuilder::default);
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.state == State::Finished
+    }
+
+    pub(crate) fn write_feature(&mut self, out: &mut impl Write, feature: &Feature) -> Result<()> {
+        self.begin_feature(
+            out,
+            "cannot write another Feature when writer has already finished",
+        )?;
+        if let Some(bbox) = &mut self.bbox {
+            bbox.add_feature(feature);
+        }
+        self.options.to_writer(out, feature)
+    }
+
+    pub(crate) fn serialize<S: Serialize>(
+        &mut self,
+        out: &mut impl Write,
+        value: &S,
+    ) -> Result<()> {
+        self.begin_feature(
+            out,
+            "cannot serialize another record when writer has already finished",
+        )?;
+        if self.bbox.is_none() && self.options.is_identity() {
+            return to_feature_writer(out, value);
+        }
+        // We need the geometry to compute the bbox, or to apply options
+        let feature = to_feature(value)?;
+        if let Some(bbox) = &mut self.bbox {
+            bbox.add_feature(&feature);
+        }
+        self.options.to_writer(out, &feature)
+    }
+
+    pub(crate) fn write_foreign_member<T: ?Sized + Serialize>(
+        &mut self,
+        out: &mut impl Write,
+        key: &str,
+        value: &T,
+    ) -> Result<()> {
+        match self.state {
+            State::Finished => Err(Error::InvalidWriterState(
+                "cannot write foreign member when writer has already finished",
+            )),
+            State::New => {
+                out.write_all(br#"{ "type": "FeatureCollection", "#)?;
+                write!(out, "\"{key}\": ")?;
+                serde_json::to_writer(&mut *out, value)?;
+                out.write_all(b",")?;
+
+                self.state = State::WritingForeignMembers;
+                Ok(())
+            }
+            State::WritingFeatures => Err(Error::InvalidWriterState(
+                "must write foreign members before any features",
+            )),
+            State::WritingForeignMembers => {
+                write!(out, "\"{key}\": ")?;
+                serde_json::to_writer(&mut *out, value)?;
+                out.write_all(b",")?;
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn finish(&mut self, out: &mut impl Write) -> Result<()> {
         match self.state {
             State::Finished => {
                 return Err(Error::InvalidWriterState(
-                    "cannot write another Feature when writer has already finished",
+                    "cannot finish writer - it's already finished",
                 ));
             }
             State::New => {
-                self.write_prefix()?;
+                self.state = State::Finished;
+                Self::write_prefix(out)?;
+                self.write_suffix(out)?;
+            }
+            State::WritingFeatures | State::WritingForeignMembers => {
+                self.state = State::Finished;
+                self.write_suffix(out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn begin_feature(&mut self, out: &mut impl Write, finished_error: &'static str) -> Result<()> {
+        match self.state {
+            State::Finished => {
+                return Err(Error::InvalidWriterState(finished_error));
+            }
+            State::New => {
+                Self::write_prefix(out)?;
                 self.state = State::WritingFeatures;
             }
             State::WritingFeatures => {
-                self.write_str(",")?;
+                out.write_all(b",")?;
             }
             State::WritingForeignMembers => {
-                self.write_str(r#" "features": ["#)?;
+                out.write_all(br#" "features": ["#)?;
                 self.state = State::WritingFeatures;
             }
         }
-        serde_json::to_writer(&mut self.writer, feature)?;
         Ok(())
     }
 
+    fn write_prefix(out: &mut impl Write) -> Result<()> {
+        out.write_all(br#"{ "type": "FeatureCollection", "features": ["#)?;
+        Ok(())
+    }
+
+    fn write_suffix(&self, out: &mut impl Write) -> Result<()> {
+        out.write_all(b"]")?;
+        if let Some(bbox) = self.bbox.as_ref().and_then(BboxBuilder::build) {
+            out.write_all(br#", "bbox": "#)?;
+            // Rounding each coordinate would round the extremes to the same values
+            serde_json::to_writer(&mut *out, &self.options.rounded(&bbox))?;
+        }
+        out.write_all(b"}")?;
+        Ok(())
+    }
+}
+
+/// Write Features to a FeatureCollection
+pub struct FeatureWriter<W: Write> {
+    writer: W,
+    encoder: CollectionEncoder,
+}
+
+impl<W: Write> FeatureWriter<W> {
+    /// Create a FeatureWriter from the given `writer`.
+    ///
+    /// To append features from your custom structs, use [`FeatureWriter::serialize`].
+    ///
+    /// To append features from [`Feature`] use [`FeatureWriter::write_feature`].
+    ///
+    /// To write a foreign member, use [`FeatureWriter::write_foreign_member`] before appending any
+    /// features.
+    pub fn from_writer(writer: W) -> Self {
+        Self {
+            writer,
+            encoder: CollectionEncoder::default(),
+        }
+    }
+
+    /// Apply the given [`WriteOptions`] to every feature written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureWriter, Geometry, WriteOptions};
+    ///
+    /// let clockwise = Geometry::new_polygon([[[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]]);
+    ///
+    /// let mut output: Vec<u8> = vec![];
+    /// {
+    ///     let options = WriteOptions::default().with_rewind(true);
+    ///     let mut writer = FeatureWriter::from_writer(&mut output).with_options(options);
+    ///     writer.write_feature(&Feature::from(clockwise)).unwrap();
+    /// }
+    ///
+    /// let collection: geojson::FeatureCollection = serde_json::from_slice(&output).unwrap();
+    /// let polygon = &collection.features[0].geometry.as_ref().unwrap().value;
+    /// assert!(polygon.is_winding_valid());
+    /// ```
+    pub fn with_options(mut self, options: WriteOptions) -> Self {
+        self.encoder.set_options(options);
+        self
+    }
+
+    /// Whether to write a [`bbox`](https://tools.ietf.org/html/rfc7946#section-5) member for the
+    /// FeatureCollection, covering the geometries of every feature written. Defaults to `false`.
+    ///
+    /// Because the bbox isn't known until every feature has been written, it's written *after*
+    /// the `features` member. It's omitted if none of the features have any coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Feature, FeatureCollection, FeatureWriter, Geometry};
+    ///
+    /// let mut output: Vec<u8> = vec![];
+    /// {
+    ///     let mut writer = FeatureWriter::from_writer(&mut output).with_bbox(true);
+    ///     writer.write_feature(&Feature::from(Geometry::new_point([1.0, 4.0]))).unwrap();
+    ///     writer.write_feature(&Feature::from(Geometry::new_point([3.0, 2.0]))).unwrap();
+    /// }
+    ///
+    /// let collection: FeatureCollection = serde_json::from_slice(&output).unwrap();
+    /// assert_eq!(collection.bbox, Some(vec![1.0, 2.0, 3.0, 4.0]));
+    /// ```
+    pub fn with_bbox(mut self, write_bbox: bool) -> Self {
+        self.encoder.set_bbox(write_bbox);
+        self
+    }
+
+    /// Write a [`crate::Feature`] struct to the output stream. If you'd like to
+    /// serialize your own custom structs, see [`FeatureWriter::serialize`] instead.
+    pub fn write_feature(&mut self, feature: &Feature) -> Result<()> {
+        self.encoder.write_feature(&mut self.writer, feature)
+    }
+
     /// Serialize your own custom struct to the features of a FeatureCollection using the
     /// [`serde`] crate.
     ///
@@ -158,25 +341,7 @@ impl<W: Write> FeatureWriter<W> {
     /// }
     /// ```
     pub fn serialize<S: Serialize>(&mut self, value: &S) -> Result<()> {
-        match self.state {
-            State::Finished => {
-                return Err(Error::InvalidWriterState(
-                    "cannot serialize another record when writer has already finished",
-                ));
-            }
-            State::New => {
-                self.write_prefix()?;
-                self.state = State::WritingFeatures;
-            }
-            State::WritingFeatures => {
-                self.write_str(",")?;
-            }
-            State::WritingForeignMembers => {
-                self.write_str(r#" "features": ["#)?;
-                self.state = State::WritingFeatures;
-            }
-        }
-        to_feature_writer(&mut self.writer, value)
+        self.encoder.serialize(&mut self.writer, value)
     }
 
     /// Write a [foreign member](https://datatracker.ietf.org/doc/html/rfc7946#section-6.1) to the
@@ -186,29 +351,8 @@ impl<W: Write> FeatureWriter<W> {
         key: &str,
         value: &T,
     ) -> Result<()> {
-        match self.state {
-            State::Finished => Err(Error::InvalidWriterState(
-                "cannot write foreign member when writer has already finished",
-            )),
-            State::New => {
-                self.write_str(r#"{ "type": "FeatureCollection", "#)?;
-                write!(self.writer, "\"{key}\": ")?;
-                serde_json::to_writer(&mut self.writer, value)?;
-                self.write_str(",")?;
-
-                self.state = State::WritingForeignMembers;
-                Ok(())
-            }
-            State::WritingFeatures => Err(Error::InvalidWriterState(
-                "must write foreign members before any features",
-            )),
-            State::WritingForeignMembers => {
-                write!(self.writer, "\"{key}\": ")?;
-                serde_json::to_writer(&mut self.writer, value)?;
-                self.write_str(",")?;
-                Ok(())
-            }
-        }
+        self.encoder
+            .write_foreign_member(&mut self.writer, key, value)
     }
 
     /// Writes the closing syntax for the FeatureCollection.
@@ -216,23 +360,7 @@ impl<W: Write> FeatureWriter<W> {
     /// You shouldn't normally need to call this manually, as the writer will close itself upon
     /// being dropped.
     pub fn finish(&mut self) -> Result<()> {
-        match self.state {
-            State::Finished => {
-                return Err(Error::InvalidWriterState(
-                    "cannot finish writer - it's already finished",
-                ));
-            }
-            State::New => {
-                self.state = State::Finished;
-                self.write_prefix()?;
-                self.write_suffix()?;
-            }
-            State::WritingFeatures | State::WritingForeignMembers => {
-                self.state = State::Finished;
-                self.write_suffix()?;
-            }
-        }
-        Ok(())
+        self.encoder.finish(&mut self.writer)
     }
 
     /// Flush the underlying writer buffer.
@@ -242,24 +370,11 @@ impl<W: Write> FeatureWriter<W> {
     pub fn flush(&mut self) -> Result<()> {
         Ok(self.writer.flush()?)
     }
-
-    fn write_prefix(&mut self) -> Result<()> {
-        self.write_str(r#"{ "type": "FeatureCollection", "features": ["#)
-    }
-
-    fn write_suffix(&mut self) -> Result<()> {
-        self.write_str("]}")
-    }
-
-    fn write_str(&mut self, text: &str) -> Result<()> {
-        self.writer.write_all(text.as_bytes())?;
-        Ok(())
-    }
 }
 
 impl<W: Write> Drop for FeatureWriter<W> {
     fn drop(&mut self) {
-        if self.state != State::Finished {
+        if !self.encoder.is_finished() {
             _ = self.finish().map_err(|e| {
                log::error!("FeatureWriter errored while finishing in Drop impl. To handle errors like this, explicitly call `FeatureWriter::finish`. Error: {}", e);
             });
@@ -473,6 +588,78 @@ mod tests {
         assert_eq!(actual_json, expected)
     }
 
+    #[test]
+    fn write_bbox() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut buffer).with_bbox(true);
+            writer.write_foreign_member("extra", "string").unwrap();
+            writer
+                .write_feature(&Feature::from(Geometry::new_point([1.1, 1.2])))
+                .unwrap();
+            writer.write_feature(&Feature::default()).unwrap();
+            writer
+                .serialize(&MyRecord {
+                    geometry: Geometry::new_line_string([[2.1, 0.2], [-2.1, 0.5]]),
+                    name: "Jane".to_string(),
+                    age: 22,
+                })
+                .unwrap();
+        }
+
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).expect("valid json");
+        assert_eq!(actual_json["bbox"], json!([-2.1, 0.2, 2.1, 1.2]));
+        assert_eq!(actual_json["extra"], json!("string"));
+        assert_eq!(actual_json["features"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            actual_json["features"][2]["properties"],
+            json!({ "name": "Jane", "age": 22 })
+        );
+    }
+
+    #[test]
+    fn serialize_with_options() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let options = WriteOptions::default().with_rewind(true);
+            let mut writer = FeatureWriter::from_writer(&mut buffer).with_options(options);
+            writer
+                .serialize(&MyRecord {
+                    geometry: Geometry::new_polygon([[
+                        [0.0, 0.0],
+                        [1.0, 1.0],
+                        [1.0, 0.0],
+                        [0.0, 0.0],
+                    ]]),
+                    name: "Jane".to_string(),
+                    age: 22,
+                })
+                .unwrap();
+        }
+
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).expect("valid json");
+        assert_eq!(
+            actual_json["features"][0]["geometry"]["coordinates"],
+            json!([[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]])
+        );
+        assert_eq!(
+            actual_json["features"][0]["properties"],
+            json!({ "name": "Jane", "age": 22 })
+        );
+    }
+
+    #[test]
+    fn write_bbox_without_coordinates() {
+        let mut buffer: Vec<u8> = vec![];
+        {
+            let mut writer = FeatureWriter::from_writer(&mut buffer).with_bbox(true);
+            writer.write_feature(&Feature::default()).unwrap();
+        }
+
+        let actual_json: JsonValue = serde_json::from_slice(&buffer).expect("valid json");
+        assert_eq!(actual_json.get("bbox"), None);
+    }
+
     #[cfg(feature = "geo-types")]
     mod test_geo_types {
         use super::*;
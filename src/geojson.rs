@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::errors::{Error, Result};
+use crate::write_options::fmt_with_precision;
 use crate::{Feature, FeatureCollection, Geometry};
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
@@ -167,7 +168,11 @@ impl TryFrom<GeoJson> for FeatureCollection {
 }
 
 impl GeoJson {
-    // Deserialize a GeoJson object from an IO stream of JSON
+    /// Deserialize a GeoJson object from an IO stream of JSON, with the default
+    /// [`ParseOptions`](crate::ParseOptions).
+    ///
+    /// For strict parsing, or reprojection, use
+    /// [`GeoJson::from_reader_with_options`](crate::GeoJson::from_reader_with_options).
     pub fn from_reader<R>(rdr: R) -> serde_json::Result<Self>
     where
         R: std::io::Read,
@@ -176,6 +181,9 @@ impl GeoJson {
     }
 
     /// Convenience wrapper for [serde_json::to_string_pretty()]
+    ///
+    /// To limit the precision of coordinates, or apply other [`WriteOptions`](crate::WriteOptions),
+    /// use [`WriteOptions::to_string_pretty`](crate::WriteOptions::to_string_pretty) instead.
     pub fn to_string_pretty(self) -> Result<String> {
         ::serde_json::to_string_pretty(&self)
             .map_err(Error::MalformedGeoJson)
@@ -183,6 +191,9 @@ impl GeoJson {
     }
 }
 
+/// Parses with the default [`ParseOptions`](crate::ParseOptions). To parse strictly, use
+/// [`GeoJson::from_str_with_options`].
+///
 /// # Example
 ///```
 /// use geojson::GeoJson;
@@ -216,39 +227,31 @@ impl FromStr for GeoJson {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        Ok(serde_json::from_str(s)?)
+        Self::from_str_with_options(s, &crate::ParseOptions::default())
     }
 }
 
 impl fmt::Display for GeoJson {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        fmt_with_precision(self, f)
     }
 }
 
 impl fmt::Display for Feature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        fmt_with_precision(self, f)
     }
 }
 
 impl fmt::Display for Geometry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        fmt_with_precision(self, f)
     }
 }
 
 impl fmt::Display for FeatureCollection {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        fmt_with_precision(self, f)
     }
 }
 
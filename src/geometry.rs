@@ -16,6 +16,7 @@ use std::str::FromStr;
 use std::{convert::TryFrom, fmt};
 
 use crate::errors::{Error, Result};
+use crate::write_options::fmt_with_precision;
 use crate::{Bbox, LineStringType, PointType, PolygonType, Position};
 use crate::{JsonObject, JsonValue};
 use serde::{Deserialize, Serialize};
@@ -155,9 +156,7 @@ impl GeometryValue {
 
 impl fmt::Display for GeometryValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        ::serde_json::to_string(self)
-            .map_err(|_| fmt::Error)
-            .and_then(|s| f.write_str(&s))
+        fmt_with_precision(self, f)
     }
 }
 
@@ -502,115 +501,120 @@ pub(crate) mod deserialize {
         pub(crate) foreign_members: Option<JsonObject>,
     }
 
-    impl TryFrom<RawGeometry> for Geometry {
-        type Error = Error;
+    /// Check that `coordinates` or `geometries` are as expected for `geometry_type`.
+    pub(crate) fn geometry_value(
+        geometry_type: GeometryType,
+        coordinates: Option<Coordinates>,
+        geometries: Option<Vec<Geometry>>,
+    ) -> Result<GeometryValue> {
+        Ok(match (geometry_type, coordinates, geometries) {
+            // Point: ZeroDimensional coordinates
+            (GeometryType::Point, Some(Coordinates::ZeroDimensional(coordinates)), None) => {
+                if coordinates.len() < 2 {
+                    return Err(Error::PositionTooShort(coordinates.len()));
+                }
+                GeometryValue::Point { coordinates }
+            }
+            // Empty Point (coordinates: [] deserializes as OneDimensional([]))
+            (GeometryType::Point, Some(Coordinates::OneDimensional(coordinates)), None)
+                if coordinates.is_empty() =>
+            {
+                return Err(Error::PositionTooShort(0));
+            }
 
-        fn try_from(mut raw: RawGeometry) -> Result<Self> {
-            normalize_foreign_members(&mut raw.foreign_members);
+            // LineString: OneDimensional coordinates (handles empty case too)
+            (GeometryType::LineString, Some(Coordinates::OneDimensional(coordinates)), None) => {
+                GeometryValue::LineString { coordinates }
+            }
 
-            let value = match (raw.r#type, raw.coordinates, raw.geometries) {
-                // Point: ZeroDimensional coordinates
-                (GeometryType::Point, Some(Coordinates::ZeroDimensional(coordinates)), None) => {
-                    if coordinates.len() < 2 {
-                        return Err(Error::PositionTooShort(coordinates.len()));
-                    }
-                    GeometryValue::Point { coordinates }
-                }
-                // Empty Point (coordinates: [] deserializes as OneDimensional([]))
-                (GeometryType::Point, Some(Coordinates::OneDimensional(coordinates)), None)
-                    if coordinates.is_empty() =>
-                {
-                    return Err(Error::PositionTooShort(0));
+            // Polygon: TwoDimensional coordinates
+            (GeometryType::Polygon, Some(Coordinates::TwoDimensional(coordinates)), None) => {
+                GeometryValue::Polygon { coordinates }
+            }
+            // Empty Polygon (coordinates: [] deserializes as OneDimensional([]))
+            (GeometryType::Polygon, Some(Coordinates::OneDimensional(coordinates)), None)
+                if coordinates.is_empty() =>
+            {
+                GeometryValue::Polygon {
+                    coordinates: vec![],
                 }
+            }
 
-                // LineString: OneDimensional coordinates (handles empty case too)
-                (
-                    GeometryType::LineString,
-                    Some(Coordinates::OneDimensional(coordinates)),
-                    None,
-                ) => GeometryValue::LineString { coordinates },
+            // MultiPoint: OneDimensional coordinates (handles empty case too)
+            (GeometryType::MultiPoint, Some(Coordinates::OneDimensional(coordinates)), None) => {
+                GeometryValue::MultiPoint { coordinates }
+            }
 
-                // Polygon: TwoDimensional coordinates
-                (GeometryType::Polygon, Some(Coordinates::TwoDimensional(coordinates)), None) => {
-                    GeometryValue::Polygon { coordinates }
-                }
-                // Empty Polygon (coordinates: [] deserializes as OneDimensional([]))
-                (GeometryType::Polygon, Some(Coordinates::OneDimensional(coordinates)), None)
-                    if coordinates.is_empty() =>
-                {
-                    GeometryValue::Polygon {
-                        coordinates: vec![],
-                    }
+            // MultiLineString: TwoDimensional coordinates
+            (
+                GeometryType::MultiLineString,
+                Some(Coordinates::TwoDimensional(coordinates)),
+                None,
+            ) => GeometryValue::MultiLineString { coordinates },
+            // Empty MultiLineString (coordinates: [] deserializes as OneDimensional([]))
+            (
+                GeometryType::MultiLineString,
+                Some(Coordinates::OneDimensional(coordinates)),
+                None,
+            ) if coordinates.is_empty() => GeometryValue::MultiLineString {
+                coordinates: vec![],
+            },
+
+            // MultiPolygon: ThreeDimensional coordinates
+            (
+                GeometryType::MultiPolygon,
+                Some(Coordinates::ThreeDimensional(coordinates)),
+                None,
+            ) => GeometryValue::MultiPolygon { coordinates },
+            // Empty MultiPolygon (coordinates: [] deserializes as OneDimensional([]))
+            (GeometryType::MultiPolygon, Some(Coordinates::OneDimensional(coordinates)), None)
+                if coordinates.is_empty() =>
+            {
+                GeometryValue::MultiPolygon {
+                    coordinates: vec![],
                 }
+            }
 
-                // MultiPoint: OneDimensional coordinates (handles empty case too)
-                (
-                    GeometryType::MultiPoint,
-                    Some(Coordinates::OneDimensional(coordinates)),
-                    None,
-                ) => GeometryValue::MultiPoint { coordinates },
-
-                // MultiLineString: TwoDimensional coordinates
-                (
-                    GeometryType::MultiLineString,
-                    Some(Coordinates::TwoDimensional(coordinates)),
-                    None,
-                ) => GeometryValue::MultiLineString { coordinates },
-                // Empty MultiLineString (coordinates: [] deserializes as OneDimensional([]))
-                (
-                    GeometryType::MultiLineString,
-                    Some(Coordinates::OneDimensional(coordinates)),
-                    None,
-                ) if coordinates.is_empty() => GeometryValue::MultiLineString {
-                    coordinates: vec![],
-                },
-
-                // MultiPolygon: ThreeDimensional coordinates
-                (
-                    GeometryType::MultiPolygon,
-                    Some(Coordinates::ThreeDimensional(coordinates)),
-                    None,
-                ) => GeometryValue::MultiPolygon { coordinates },
-                // Empty MultiPolygon (coordinates: [] deserializes as OneDimensional([]))
-                (
-                    GeometryType::MultiPolygon,
-                    Some(Coordinates::OneDimensional(coordinates)),
-                    None,
-                ) if coordinates.is_empty() => GeometryValue::MultiPolygon {
-                    coordinates: vec![],
-                },
+            // GeometryCollection: geometries array, no coordinates
+            (GeometryType::GeometryCollection, _, Some(geometries)) => {
+                GeometryValue::GeometryCollection { geometries }
+            }
 
-                // GeometryCollection: geometries array, no coordinates
-                (GeometryType::GeometryCollection, _, Some(geometries)) => {
-                    GeometryValue::GeometryCollection { geometries }
-                }
+            // Invalid combinations
+            (GeometryType::GeometryCollection, _, None) => {
+                return Err(Error::GeometryCollectionWithoutGeometriesKey);
+            }
 
-                // Invalid combinations
-                (GeometryType::GeometryCollection, _, None) => {
-                    return Err(Error::GeometryCollectionWithoutGeometriesKey);
-                }
+            (
+                geometry_type @ (GeometryType::Point
+                | GeometryType::LineString
+                | GeometryType::Polygon
+                | GeometryType::MultiPoint
+                | GeometryType::MultiLineString
+                | GeometryType::MultiPolygon),
+                coords,
+                _,
+            ) => {
+                return if let Some(coords) = coords {
+                    let dimensions = coords.dimensions();
+                    Err(Error::InvalidGeometryDimensions {
+                        geometry_type,
+                        dimensions,
+                    })
+                } else {
+                    Err(Error::GeometryWithoutCoordinatesKey { geometry_type })
+                };
+            }
+        })
+    }
 
-                (
-                    geometry_type @ (GeometryType::Point
-                    | GeometryType::LineString
-                    | GeometryType::Polygon
-                    | GeometryType::MultiPoint
-                    | GeometryType::MultiLineString
-                    | GeometryType::MultiPolygon),
-                    coords,
-                    _,
-                ) => {
-                    return if let Some(coords) = coords {
-                        let dimensions = coords.dimensions();
-                        Err(Error::InvalidGeometryDimensions {
-                            geometry_type,
-                            dimensions,
-                        })
-                    } else {
-                        Err(Error::GeometryWithoutCoordinatesKey { geometry_type })
-                    };
-                }
-            };
+    impl TryFrom<RawGeometry> for Geometry {
+        type Error = Error;
+
+        fn try_from(mut raw: RawGeometry) -> Result<Self> {
+            normalize_foreign_members(&mut raw.foreign_members);
+
+            let value = geometry_value(raw.r#type, raw.coordinates, raw.geometries)?;
 
             Ok(Geometry {
                 bbox: raw.bbox,
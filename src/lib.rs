@@ -238,6 +238,10 @@
 //!     process_geojson(&geojson);
 //! }
 //! ```
+//!
+//! To skip writing this recursion yourself, implement [`GeoJsonVisitor`] instead, or iterate over
+//! every position with [`GeoJson::coords`]. See the [`visit`] module.
+//!
 //! ### Foreign Members
 //!
 //! [Foreign members](https://tools.ietf.org/html/rfc7946#section-6.1) are extra JSON fields
@@ -360,7 +364,9 @@
 //! ### Caveats
 //! - Round-tripping with intermediate processing using the `geo` types may not produce identical output,
 //!   as e.g. outer `Polygon` rings are automatically closed.
-//! - `geojson` attempts to output valid geometries. In particular, it may re-orient `Polygon` rings when serialising.
+//! - `geojson` doesn't re-orient `Polygon` rings when serialising unless asked to, e.g. with
+//!   [`WriteOptions::with_rewind`]. `geo_types` polygons don't enforce the right-hand rule, so
+//!   their converted rings may be wound in either direction.
 //!
 //! The [`geojson_example`](https://github.com/urschrei/geojson_example) and
 //! [`polylabel_cmd`](https://github.com/urschrei/polylabel_cmd/blob/master/src/main.rs) crates contain example
@@ -424,6 +430,38 @@ pub use feature::Feature;
 mod feature_collection;
 pub use feature_collection::FeatureCollection;
 
+mod borrowed;
+pub use borrowed::{FeatureCollectionRef, FeatureRef, GeometryRef, JsonObjectRef};
+
+mod bbox;
+
+mod winding;
+
+mod antimeridian;
+
+mod wkt;
+
+mod wkb;
+pub use wkb::{WkbByteOrder, WkbErrorKind, WkbFlavor, WkbOptions};
+
+mod polyline;
+
+mod map_coords;
+pub use map_coords::BboxUpdate;
+
+mod crs;
+pub use crs::Crs;
+
+pub mod projection;
+
+pub mod topojson;
+
+pub mod mvt;
+
+#[cfg(feature = "arrow")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+pub mod arrow;
+
 mod feature_iterator;
 #[allow(deprecated)]
 #[doc(hidden)]
@@ -432,6 +470,15 @@ pub use feature_iterator::FeatureIterator;
 pub mod errors;
 pub use errors::{Error, Result};
 
+pub mod validation;
+
+pub mod schema;
+
+pub mod visit;
+pub use visit::{GeoJsonVisitor, GeoJsonVisitorMut};
+
+pub mod axis_order;
+
 #[cfg(feature = "geo-types")]
 mod conversion;
 
@@ -441,12 +488,36 @@ pub mod de;
 /// Write your struct to GeoJSON using [`serde`]
 pub mod ser;
 
+mod collection_scanner;
+
 mod feature_reader;
 pub use feature_reader::FeatureReader;
 
 mod feature_writer;
 pub use feature_writer::FeatureWriter;
 
+#[cfg(feature = "tokio")]
+mod async_feature_reader;
+#[cfg(feature = "tokio")]
+pub use async_feature_reader::AsyncFeatureReader;
+
+#[cfg(feature = "tokio")]
+mod async_feature_writer;
+#[cfg(feature = "tokio")]
+pub use async_feature_writer::AsyncFeatureWriter;
+
+mod feature_seq_reader;
+pub use feature_seq_reader::{FeatureSeqReader, SeqFormat};
+
+mod feature_seq_writer;
+pub use feature_seq_writer::FeatureSeqWriter;
+
+mod parse_options;
+pub use parse_options::{ParseOptions, StrictViolation};
+
+mod write_options;
+pub use write_options::{Precision, Writable, WriteOptions};
+
 #[allow(deprecated)]
 #[cfg(feature = "geo-types")]
 pub use conversion::quick_collection;
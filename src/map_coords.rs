@@ -0,0 +1,360 @@
+//! Transforming every position of an object in place, e.g. to reproject, offset or swap axes.
+use crate::{Feature, FeatureCollection, GeoJson, Geometry, GeometryValue, Position};
+
+use std::convert::Infallible;
+
+/// What to do with the `bbox` members of an object when its positions are changed by
+/// `map_coords` or `try_map_coords`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BboxUpdate {
+    /// Leave every `bbox` as is, even though it may no longer match the positions.
+    Keep,
+    /// Remove every `bbox`.
+    Remove,
+    /// Recompute every `bbox` which is present. Objects without a `bbox` don't get one.
+    Recompute,
+}
+
+impl GeometryValue {
+    /// Replace every position of the geometry with the result of `f`, including those of any
+    /// nested `GeometryCollection`s, whose `bbox` members are updated as `bbox` says.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{BboxUpdate, GeometryValue, Position};
+    ///
+    /// let mut line_string = GeometryValue::new_line_string([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    /// line_string.map_coords(BboxUpdate::Remove, |position| {
+    ///     let mut position = position.clone();
+    ///     position[0] += 10.0;
+    ///     position
+    /// });
+    /// assert_eq!(
+    ///     line_string,
+    ///     GeometryValue::new_line_string([[11.0, 2.0, 3.0], [14.0, 5.0, 6.0]])
+    /// );
+    /// ```
+    pub fn map_coords(&mut self, bbox: BboxUpdate, mut f: impl FnMut(&Position) -> Position) {
+        let Ok(()) = self.try_map_coords(bbox, |position| Ok::<_, Infallible>(f(position)));
+    }
+
+    /// Replace every position of the geometry with the result of `f`, stopping at the first
+    /// error.
+    ///
+    /// The geometry is left partly transformed if `f` fails. See [`GeometryValue::map_coords`].
+    pub fn try_map_coords<E>(
+        &mut self,
+        bbox: BboxUpdate,
+        mut f: impl FnMut(&Position) -> Result<Position, E>,
+    ) -> Result<(), E> {
+        try_map_value(self, bbox, &mut f)
+    }
+}
+
+impl Geometry {
+    /// Replace every position of the geometry with the result of `f`, updating `bbox` members as
+    /// `bbox` says.
+    ///
+    /// Foreign members are kept. See [`GeometryValue::map_coords`].
+    pub fn map_coords(&mut self, bbox: BboxUpdate, mut f: impl FnMut(&Position) -> Position) {
+        let Ok(()) = self.try_map_coords(bbox, |position| Ok::<_, Infallible>(f(position)));
+    }
+
+    /// Replace every position of the geometry with the result of `f`, stopping at the first
+    /// error.
+    ///
+    /// The geometry is left partly transformed if `f` fails. See [`Geometry::map_coords`].
+    pub fn try_map_coords<E>(
+        &mut self,
+        bbox: BboxUpdate,
+        mut f: impl FnMut(&Position) -> Result<Position, E>,
+    ) -> Result<(), E> {
+        try_map_geometry(self, bbox, &mut f)
+    }
+}
+
+impl Feature {
+    /// Replace every position of the feature's geometry with the result of `f`, updating `bbox`
+    /// members as `bbox` says.
+    ///
+    /// The `id`, properties and foreign members are kept. See [`GeometryValue::map_coords`].
+    pub fn map_coords(&mut self, bbox: BboxUpdate, mut f: impl FnMut(&Position) -> Position) {
+        let Ok(()) = self.try_map_coords(bbox, |position| Ok::<_, Infallible>(f(position)));
+    }
+
+    /// Replace every position of the feature's geometry with the result of `f`, stopping at the
+    /// first error.
+    ///
+    /// The feature is left partly transformed if `f` fails. See [`Feature::map_coords`].
+    pub fn try_map_coords<E>(
+        &mut self,
+        bbox: BboxUpdate,
+        mut f: impl FnMut(&Position) -> Result<Position, E>,
+    ) -> Result<(), E> {
+        try_map_feature(self, bbox, &mut f)
+    }
+}
+
+impl FeatureCollection {
+    /// Replace every position of every feature with the result of `f`, updating `bbox` members
+    /// as `bbox` says.
+    ///
+    /// The `id`, properties and foreign members of every feature are kept, as are the
+    /// collection's foreign members. See [`GeometryValue::map_coords`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{BboxUpdate, Feature, FeatureCollection, Geometry, Position};
+    ///
+    /// let mut collection = FeatureCollection::from_iter([
+    ///     Feature::from(Geometry::new_point([1.0, 2.0])).with_bbox(),
+    ///     Feature::from(Geometry::new_point([3.0, 4.0])),
+    /// ])
+    /// .with_bbox();
+    ///
+    /// // Swap longitude and latitude
+    /// collection.map_coords(BboxUpdate::Recompute, |position| {
+    ///     Position::from([position[1], position[0]])
+    /// });
+    /// assert_eq!(collection.bbox, Some(vec![2.0, 1.0, 4.0, 3.0]));
+    /// assert_eq!(collection.features[0].bbox, Some(vec![2.0, 1.0, 2.0, 1.0]));
+    /// assert_eq!(collection.features[1].bbox, None);
+    /// ```
+    pub fn map_coords(&mut self, bbox: BboxUpdate, mut f: impl FnMut(&Position) -> Position) {
+        let Ok(()) = self.try_map_coords(bbox, |position| Ok::<_, Infallible>(f(position)));
+    }
+
+    /// Replace every position of every feature with the result of `f`, stopping at the first
+    /// error.
+    ///
+    /// The collection is left partly transformed if `f` fails. See
+    /// [`FeatureCollection::map_coords`].
+    pub fn try_map_coords<E>(
+        &mut self,
+        bbox: BboxUpdate,
+        mut f: impl FnMut(&Position) -> Result<Position, E>,
+    ) -> Result<(), E> {
+        for feature in &mut self.features {
+            try_map_feature(feature, bbox, &mut f)?;
+        }
+        match bbox {
+            BboxUpdate::Keep => {}
+            BboxUpdate::Remove => self.bbox = None,
+            BboxUpdate::Recompute => {
+                if self.bbox.is_some() {
+                    self.update_bbox();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GeoJson {
+    /// Replace every position of the object with the result of `f`, updating `bbox` members as
+    /// `bbox` says.
+    ///
+    /// See [`FeatureCollection::map_coords`], [`Feature::map_coords`] and
+    /// [`Geometry::map_coords`].
+    pub fn map_coords(&mut self, bbox: BboxUpdate, mut f: impl FnMut(&Position) -> Position) {
+        let Ok(()) = self.try_map_coords(bbox, |position| Ok::<_, Infallible>(f(position)));
+    }
+
+    /// Replace every position of the object with the result of `f`, stopping at the first error.
+    ///
+    /// The object is left partly transformed if `f` fails. See [`GeoJson::map_coords`].
+    pub fn try_map_coords<E>(
+        &mut self,
+        bbox: BboxUpdate,
+        f: impl FnMut(&Position) -> Result<Position, E>,
+    ) -> Result<(), E> {
+        match self {
+            GeoJson::FeatureCollection(collection) => collection.try_map_coords(bbox, f),
+            GeoJson::Feature(feature) => feature.try_map_coords(bbox, f),
+            GeoJson::Geometry(geometry) => geometry.try_map_coords(bbox, f),
+        }
+    }
+}
+
+fn try_map_value<E>(
+    value: &mut GeometryValue,
+    bbox: BboxUpdate,
+    f: &mut impl FnMut(&Position) -> Result<Position, E>,
+) -> Result<(), E> {
+    if let GeometryValue::GeometryCollection { geometries } = value {
+        // The nested geometries may have bboxes of their own
+        for geometry in geometries {
+            try_map_geometry(geometry, bbox, f)?;
+        }
+        return Ok(());
+    }
+    for position in value.coords_mut() {
+        *position = f(position)?;
+    }
+    Ok(())
+}
+
+fn try_map_geometry<E>(
+    geometry: &mut Geometry,
+    bbox: BboxUpdate,
+    f: &mut impl FnMut(&Position) -> Result<Position, E>,
+) -> Result<(), E> {
+    try_map_value(&mut geometry.value, bbox, f)?;
+    match bbox {
+        BboxUpdate::Keep => {}
+        BboxUpdate::Remove => geometry.bbox = None,
+        BboxUpdate::Recompute => {
+            if geometry.bbox.is_some() {
+                geometry.update_bbox();
+            }
+        }
+    }
+    Ok(())
+}
+
+fn try_map_feature<E>(
+    feature: &mut Feature,
+    bbox: BboxUpdate,
+    f: &mut impl FnMut(&Position) -> Result<Position, E>,
+) -> Result<(), E> {
+    if let Some(geometry) = &mut feature.geometry {
+        try_map_geometry(geometry, bbox, f)?;
+    }
+    match bbox {
+        BboxUpdate::Keep => {}
+        BboxUpdate::Remove => feature.bbox = None,
+        BboxUpdate::Recompute => {
+            if feature.bbox.is_some() {
+                feature.update_bbox();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JsonObject, JsonValue, feature};
+
+    fn offset(position: &Position) -> Position {
+        let mut position = position.clone();
+        position[0] += 10.0;
+        position
+    }
+
+    fn collection() -> FeatureCollection {
+        let mut foreign_members = JsonObject::new();
+        foreign_members.insert("title".to_string(), JsonValue::from("nested"));
+        let nested = Geometry {
+            foreign_members: Some(foreign_members),
+            ..Geometry::new_point([1.0, 2.0, 3.0]).with_bbox()
+        };
+        let feature = Feature {
+            id: Some(feature::Id::String("a".to_string())),
+            properties: Some(JsonObject::from_iter([(
+                "name".to_string(),
+                JsonValue::from("first"),
+            )])),
+            ..Feature::from(Geometry::new_geometry_collection([
+                nested,
+                Geometry::new_line_string([[4.0, 5.0], [6.0, 7.0]]),
+            ]))
+            .with_bbox()
+        };
+        FeatureCollection::from_iter([feature, Feature::default()]).with_bbox()
+    }
+
+    #[test]
+    fn preserves_members() {
+        let mut mapped = collection();
+        mapped.map_coords(BboxUpdate::Keep, offset);
+
+        let original = collection();
+        assert_eq!(mapped.bbox, original.bbox);
+        let (feature, original_feature) = (&mapped.features[0], &original.features[0]);
+        assert_eq!(feature.id, original_feature.id);
+        assert_eq!(feature.properties, original_feature.properties);
+        assert_eq!(feature.bbox, original_feature.bbox);
+        let GeometryValue::GeometryCollection { geometries } =
+            &feature.geometry.as_ref().unwrap().value
+        else {
+            panic!("expected a GeometryCollection");
+        };
+        assert_eq!(
+            geometries[0].foreign_members.as_ref().unwrap()["title"],
+            "nested"
+        );
+        assert_eq!(
+            geometries[0].value,
+            GeometryValue::new_point([11.0, 2.0, 3.0])
+        );
+        assert_eq!(
+            geometries[1].value,
+            GeometryValue::new_line_string([[14.0, 5.0], [16.0, 7.0]])
+        );
+        assert_eq!(mapped.features[1], original.features[1]);
+    }
+
+    #[test]
+    fn bbox() {
+        let mut removed = GeoJson::from(collection());
+        removed.map_coords(BboxUpdate::Remove, offset);
+        let GeoJson::FeatureCollection(removed) = removed else {
+            unreachable!()
+        };
+        assert_eq!(removed.bbox, None);
+        assert_eq!(removed.features[0].bbox, None);
+        assert_eq!(removed.features[0].geometry.as_ref().unwrap().bbox, None);
+
+        let mut recomputed = collection();
+        recomputed.map_coords(BboxUpdate::Recompute, offset);
+        assert_eq!(recomputed.bbox, Some(vec![11.0, 2.0, 16.0, 7.0]));
+        let feature = &recomputed.features[0];
+        assert_eq!(feature.bbox, Some(vec![11.0, 2.0, 16.0, 7.0]));
+        // Only those which had a bbox get one
+        let geometry = feature.geometry.as_ref().unwrap();
+        assert_eq!(geometry.bbox, None);
+        let GeometryValue::GeometryCollection { geometries } = &geometry.value else {
+            panic!("expected a GeometryCollection");
+        };
+        assert_eq!(
+            geometries[0].bbox,
+            Some(vec![11.0, 2.0, 3.0, 11.0, 2.0, 3.0])
+        );
+        assert_eq!(geometries[1].bbox, None);
+    }
+
+    #[test]
+    fn try_map_coords() {
+        let mut geometry = Geometry::new_multi_point([[1.0, 2.0], [200.0, 0.0], [3.0, 4.0]]);
+        let mut calls = 0;
+        let result = geometry.try_map_coords(BboxUpdate::Remove, |position| {
+            calls += 1;
+            if position[0] > 180.0 {
+                Err(position[0])
+            } else {
+                Ok(offset(position))
+            }
+        });
+        assert_eq!(result, Err(200.0));
+        assert_eq!(calls, 2);
+        assert_eq!(
+            geometry.value,
+            GeometryValue::new_multi_point([[11.0, 2.0], [200.0, 0.0], [3.0, 4.0]])
+        );
+
+        let mut polygon =
+            GeometryValue::new_polygon([[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]]]);
+        assert_eq!(
+            polygon.try_map_coords(BboxUpdate::Remove, |position| Ok::<_, ()>(offset(position))),
+            Ok(())
+        );
+        assert_eq!(
+            polygon.coords().map(|position| position[0]).sum::<f64>(),
+            41.0
+        );
+    }
+}
@@ -0,0 +1,1351 @@
+//! [Mapbox Vector Tiles](https://github.com/mapbox/vector-tile-spec/tree/master/2.1).
+//!
+//! A vector tile holds the features within one square of a
+//! [web mercator tile grid](https://wiki.openstreetmap.org/wiki/Slippy_map_tilenames), in
+//! named layers. Use a [`TileEncoder`] to build a tile from GeoJSON [`Feature`]s, and [`decode`]
+//! to turn a tile back into a [`FeatureCollection`] for each layer.
+//!
+//! # Examples
+//!
+//! ```
+//! use geojson::mvt::{TileEncoder, TileId};
+//! use geojson::{Feature, FeatureCollection, Geometry};
+//!
+//! let collection = FeatureCollection::from_iter([
+//!     Feature::from(Geometry::new_point([2.3522, 48.8566])),
+//!     Feature::from(Geometry::new_point([13.4050, 52.5200])),
+//! ]);
+//!
+//! let mut encoder = TileEncoder::new(TileId::new(4, 8, 5));
+//! for feature in &collection {
+//!     encoder.add_feature("cities", feature);
+//! }
+//! let tile: Vec<u8> = encoder.encode();
+//!
+//! let layers = geojson::mvt::decode(&tile, TileId::new(4, 8, 5)).unwrap();
+//! assert_eq!(layers["cities"].features.len(), 2);
+//! ```
+use crate::{
+    Error, Feature, FeatureCollection, Geometry, GeometryValue, JsonObject, JsonValue, Position,
+    Result, feature, projection,
+};
+
+use std::collections::{BTreeMap, HashMap};
+
+/// The address of a tile in the [web mercator tile grid](https://wiki.openstreetmap.org/wiki/Slippy_map_tilenames).
+///
+/// At zoom level `z` the world is split into `2^z × 2^z` tiles, numbered from the north-west
+/// corner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TileId {
+    /// The zoom level.
+    pub z: u8,
+    /// The column of the tile, counting east from the antimeridian.
+    pub x: u32,
+    /// The row of the tile, counting south from the northern edge of the map.
+    pub y: u32,
+}
+
+impl TileId {
+    /// The tile at column `x` and row `y` of zoom level `z`.
+    pub fn new(z: u8, x: u32, y: u32) -> Self {
+        Self { z, x, y }
+    }
+
+    /// Project a WGS84 position into the coordinates of this tile, where `[0, 0]` is its
+    /// north-west corner and `[extent, extent]` its south-east corner.
+    fn project(&self, position: &Position, extent: u32) -> Option<[f64; 2]> {
+        let extent = f64::from(extent);
+        let pixels = projection::to_pixels(position, f64::from(self.z), extent);
+        let &[x, y, ..] = pixels.as_slice() else {
+            return None;
+        };
+        Some([
+            x - f64::from(self.x) * extent,
+            y - f64::from(self.y) * extent,
+        ])
+    }
+
+    /// The inverse of [`TileId::project`].
+    fn unproject(&self, [x, y]: [i32; 2], extent: u32) -> Position {
+        let extent = f64::from(extent);
+        let pixels = Position::from([
+            f64::from(self.x) * extent + f64::from(x),
+            f64::from(self.y) * extent + f64::from(y),
+        ]);
+        projection::from_pixels(&pixels, f64::from(self.z), extent)
+    }
+}
+
+/// Builds a Mapbox Vector Tile from GeoJSON [`Feature`]s.
+///
+/// Each feature's geometry is projected from WGS84 into the tile, clipped to the tile plus a
+/// [buffer](TileEncoder::with_buffer), and rounded to the tile's
+/// [extent](TileEncoder::with_extent). Features which fall entirely outside the buffered tile,
+/// or have no geometry, are left out.
+///
+/// A feature's `id` is kept if it's a non-negative integer, as MVT ids must be. Its
+/// `properties` become the feature's tags: strings, numbers and booleans are kept as they are,
+/// arrays and objects are written as JSON strings, and `null`s are left out.
+///
+/// MVT has no equivalent of a `GeometryCollection`, so its points, lines and polygons are
+/// written as up to three features with the same `id` and tags.
+#[derive(Clone, Debug)]
+pub struct TileEncoder {
+    tile: TileId,
+    extent: u32,
+    buffer: u32,
+    layers: Vec<LayerEncoder>,
+}
+
+impl TileEncoder {
+    /// Create an empty tile for the given address.
+    pub fn new(tile: TileId) -> Self {
+        Self {
+            tile,
+            extent: 4096,
+            buffer: 64,
+            layers: vec![],
+        }
+    }
+
+    /// The size of the tile's coordinate grid. Defaults to `4096`.
+    ///
+    /// A vector tile's extent can't be `0`, so an extent of `0` is treated as `1`.
+    pub fn with_extent(mut self, extent: u32) -> Self {
+        self.extent = extent.max(1);
+        self
+    }
+
+    /// How far, in units of the [extent](TileEncoder::with_extent), geometries extend beyond
+    /// the edge of the tile before being clipped. Defaults to `64`.
+    ///
+    /// A buffer avoids visible seams where lines and polygons are drawn across adjacent tiles.
+    pub fn with_buffer(mut self, buffer: u32) -> Self {
+        self.buffer = buffer;
+        self
+    }
+
+    /// Add `feature` to the layer called `layer`, creating the layer if need be.
+    ///
+    /// Features may come from anywhere, e.g. a [`FeatureCollection`]
+    /// or a [`FeatureReader`](crate::FeatureReader):
+    ///
+    /// ```
+    /// use geojson::FeatureReader;
+    /// use geojson::mvt::{TileEncoder, TileId};
+    ///
+    /// let input = r#"{
+    ///     "type": "FeatureCollection",
+    ///     "features": [{
+    ///         "type": "Feature",
+    ///         "geometry": { "type": "Point", "coordinates": [125.6, 10.1] },
+    ///         "properties": { "name": "Dinagat Islands" }
+    ///     }]
+    /// }"#;
+    ///
+    /// let mut encoder = TileEncoder::new(TileId::new(0, 0, 0));
+    /// let mut reader = FeatureReader::from_reader(input.as_bytes());
+    /// for feature in reader.features() {
+    ///     encoder.add_feature("islands", &feature.unwrap());
+    /// }
+    /// # assert!(!encoder.encode().is_empty());
+    /// ```
+    pub fn add_feature(&mut self, layer: &str, feature: &Feature) {
+        let Some(geometry) = &feature.geometry else {
+            return;
+        };
+        let geometries = self.geometries(&geometry.value);
+        if geometries.iter().all(TileGeometry::is_empty) {
+            return;
+        }
+        let index = match self.layers.iter().position(|encoder| encoder.name == layer) {
+            Some(index) => index,
+            None => {
+                self.layers.push(LayerEncoder::new(layer));
+                self.layers.len() - 1
+            }
+        };
+        let layer = &mut self.layers[index];
+        let tags = layer.tags(feature.properties.iter().flatten());
+        let id = match &feature.id {
+            Some(feature::Id::Number(id)) => id.as_u64(),
+            _ => None,
+        };
+        for geometry in geometries {
+            layer.add_feature(id, &tags, &geometry);
+        }
+    }
+
+    /// Encode the tile as protobuf bytes.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut tile = vec![];
+        for layer in &self.layers {
+            let mut bytes = vec![];
+            write_bytes(&mut bytes, 1, layer.name.as_bytes());
+            bytes.extend_from_slice(&layer.features);
+            for key in &layer.keys {
+                write_bytes(&mut bytes, 3, key.as_bytes());
+            }
+            for value in &layer.values {
+                let mut value_bytes = vec![];
+                value.write(&mut value_bytes);
+                write_bytes(&mut bytes, 4, &value_bytes);
+            }
+            write_varint_field(&mut bytes, 5, u64::from(self.extent));
+            write_varint_field(&mut bytes, 15, 2);
+            write_bytes(&mut tile, 3, &bytes);
+        }
+        tile
+    }
+
+    /// Project, clip and round `value`, grouped into points, lines and polygons.
+    fn geometries(&self, value: &GeometryValue) -> [TileGeometry; 3] {
+        let mut geometries = [
+            TileGeometry::Points(vec![]),
+            TileGeometry::Lines(vec![]),
+            TileGeometry::Polygons(vec![]),
+        ];
+        self.add_geometry(value, &mut geometries);
+        geometries
+    }
+
+    fn add_geometry(&self, value: &GeometryValue, geometries: &mut [TileGeometry; 3]) {
+        let [
+            TileGeometry::Points(points),
+            TileGeometry::Lines(lines),
+            TileGeometry::Polygons(polygons),
+        ] = geometries
+        else {
+            unreachable!("geometries are always points, lines and polygons");
+        };
+        let clip = Clip {
+            min: -f64::from(self.buffer),
+            max: f64::from(self.extent) + f64::from(self.buffer),
+        };
+        match value {
+            GeometryValue::Point { coordinates } => points.extend(self.point(coordinates, &clip)),
+            GeometryValue::MultiPoint { coordinates } => points.extend(
+                coordinates
+                    .iter()
+                    .filter_map(|point| self.point(point, &clip)),
+            ),
+            GeometryValue::LineString { coordinates } => {
+                lines.extend(self.line(coordinates, &clip))
+            }
+            GeometryValue::MultiLineString { coordinates } => {
+                for line in coordinates {
+                    lines.extend(self.line(line, &clip));
+                }
+            }
+            GeometryValue::Polygon { coordinates } => {
+                polygons.extend(self.polygon(coordinates, &clip))
+            }
+            GeometryValue::MultiPolygon { coordinates } => {
+                for polygon in coordinates {
+                    polygons.extend(self.polygon(polygon, &clip));
+                }
+            }
+            GeometryValue::GeometryCollection {
+                geometries: members,
+            } => {
+                for member in members {
+                    self.add_geometry(&member.value, geometries);
+                }
+            }
+        }
+    }
+
+    fn project(&self, positions: &[Position]) -> Vec<[f64; 2]> {
+        positions
+            .iter()
+            .filter_map(|position| self.tile.project(position, self.extent))
+            .collect()
+    }
+
+    fn point(&self, position: &Position, clip: &Clip) -> Option<[i32; 2]> {
+        let point = self.tile.project(position, self.extent)?;
+        clip.contains(point).then(|| round(point))
+    }
+
+    fn line(&self, line: &[Position], clip: &Clip) -> Vec<Vec<[i32; 2]>> {
+        clip.line(&self.project(line))
+            .into_iter()
+            .map(|line| dedup(line.into_iter().map(round)))
+            .filter(|line| line.len() >= 2)
+            .collect()
+    }
+
+    /// The rings of a polygon, clipped and wound as MVT requires, or `None` if its exterior
+    /// ring doesn't survive.
+    fn polygon(&self, rings: &[Vec<Position>], clip: &Clip) -> Option<Vec<Vec<[i32; 2]>>> {
+        let mut polygon = vec![];
+        for (index, ring) in rings.iter().enumerate() {
+            let mut ring = self.project(ring);
+            // MVT rings are closed implicitly
+            if ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            let mut ring = dedup(clip.ring(&ring).into_iter().map(round));
+            while ring.len() > 1 && ring.first() == ring.last() {
+                ring.pop();
+            }
+            let area = signed_area(&ring);
+            if ring.len() < 3 || area == 0 {
+                if index == 0 {
+                    return None;
+                }
+                continue;
+            }
+            // Exterior rings must have a positive area in tile coordinates, and interior rings
+            // a negative one
+            if (index == 0) != (area > 0) {
+                ring[1..].reverse();
+            }
+            polygon.push(ring);
+        }
+        Some(polygon)
+    }
+}
+
+fn round([x, y]: [f64; 2]) -> [i32; 2] {
+    [x.round() as i32, y.round() as i32]
+}
+
+/// Remove consecutive duplicate points, which rounding can produce.
+fn dedup(points: impl Iterator<Item = [i32; 2]>) -> Vec<[i32; 2]> {
+    let mut deduped: Vec<[i32; 2]> = vec![];
+    for point in points {
+        if deduped.last() != Some(&point) {
+            deduped.push(point);
+        }
+    }
+    deduped
+}
+
+/// Twice the area of an implicitly closed ring, positive if it's clockwise in tile coordinates,
+/// where `y` points down.
+fn signed_area(ring: &[[i32; 2]]) -> i64 {
+    (0..ring.len())
+        .map(|index| {
+            let [x0, y0] = ring[index];
+            let [x1, y1] = ring[(index + 1) % ring.len()];
+            i64::from(x0) * i64::from(y1) - i64::from(x1) * i64::from(y0)
+        })
+        .sum()
+}
+
+/// The square that geometries are clipped to, in tile coordinates.
+struct Clip {
+    min: f64,
+    max: f64,
+}
+
+impl Clip {
+    fn contains(&self, [x, y]: [f64; 2]) -> bool {
+        (self.min..=self.max).contains(&x) && (self.min..=self.max).contains(&y)
+    }
+
+    /// Clip a line, which may split it into several lines.
+    fn line(&self, line: &[[f64; 2]]) -> Vec<Vec<[f64; 2]>> {
+        let mut lines = vec![];
+        let mut current: Vec<[f64; 2]> = vec![];
+        for segment in line.windows(2) {
+            let Some((start, end)) = self.segment(segment[0], segment[1]) else {
+                continue;
+            };
+            if current.last() != Some(&start) {
+                if current.len() > 1 {
+                    lines.push(std::mem::take(&mut current));
+                }
+                current.clear();
+                current.push(start);
+            }
+            current.push(end);
+        }
+        if current.len() > 1 {
+            lines.push(current);
+        }
+        lines
+    }
+
+    /// Clip a segment with the Liang–Barsky algorithm.
+    fn segment(&self, start: [f64; 2], end: [f64; 2]) -> Option<([f64; 2], [f64; 2])> {
+        let delta = [end[0] - start[0], end[1] - start[1]];
+        let (mut t0, mut t1) = (0.0, 1.0);
+        for axis in 0..2 {
+            for (p, q) in [
+                (-delta[axis], start[axis] - self.min),
+                (delta[axis], self.max - start[axis]),
+            ] {
+                if p == 0.0 {
+                    if q < 0.0 {
+                        return None;
+                    }
+                    continue;
+                }
+                let t = q / p;
+                if p < 0.0 {
+                    if t > t1 {
+                        return None;
+                    }
+                    t0 = f64::max(t0, t);
+                } else {
+                    if t < t0 {
+                        return None;
+                    }
+                    t1 = f64::min(t1, t);
+                }
+            }
+        }
+        let at = |t: f64| [start[0] + t * delta[0], start[1] + t * delta[1]];
+        Some((at(t0), at(t1)))
+    }
+
+    /// Clip an implicitly closed ring with the Sutherland–Hodgman algorithm.
+    fn ring(&self, ring: &[[f64; 2]]) -> Vec<[f64; 2]> {
+        let mut ring = ring.to_vec();
+        for axis in 0..2 {
+            for (bound, inside) in [
+                (
+                    self.min,
+                    (|value, bound| value >= bound) as fn(f64, f64) -> bool,
+                ),
+                (self.max, |value, bound| value <= bound),
+            ] {
+                let input = std::mem::take(&mut ring);
+                for (index, &current) in input.iter().enumerate() {
+                    let previous = input[(index + input.len() - 1) % input.len()];
+                    let crossing = || {
+                        let t = (bound - previous[axis]) / (current[axis] - previous[axis]);
+                        let mut point = [
+                            previous[0] + t * (current[0] - previous[0]),
+                            previous[1] + t * (current[1] - previous[1]),
+                        ];
+                        point[axis] = bound;
+                        point
+                    };
+                    match (inside(previous[axis], bound), inside(current[axis], bound)) {
+                        (true, true) => ring.push(current),
+                        (true, false) => ring.push(crossing()),
+                        (false, true) => {
+                            ring.push(crossing());
+                            ring.push(current);
+                        }
+                        (false, false) => {}
+                    }
+                }
+            }
+        }
+        ring
+    }
+}
+
+/// A feature's geometry in tile coordinates.
+enum TileGeometry {
+    Points(Vec<[i32; 2]>),
+    Lines(Vec<Vec<[i32; 2]>>),
+    Polygons(Vec<Vec<Vec<[i32; 2]>>>),
+}
+
+impl TileGeometry {
+    fn is_empty(&self) -> bool {
+        match self {
+            TileGeometry::Points(points) => points.is_empty(),
+            TileGeometry::Lines(lines) => lines.is_empty(),
+            TileGeometry::Polygons(polygons) => polygons.is_empty(),
+        }
+    }
+
+    /// The MVT `GeomType`.
+    fn geometry_type(&self) -> u64 {
+        match self {
+            TileGeometry::Points(_) => 1,
+            TileGeometry::Lines(_) => 2,
+            TileGeometry::Polygons(_) => 3,
+        }
+    }
+
+    /// Encode as MVT geometry commands.
+    fn commands(&self) -> Vec<u32> {
+        let mut commands = Commands::default();
+        match self {
+            TileGeometry::Points(points) => commands.move_to(points),
+            TileGeometry::Lines(lines) => {
+                for line in lines {
+                    commands.move_to(&line[..1]);
+                    commands.line_to(&line[1..]);
+                }
+            }
+            TileGeometry::Polygons(polygons) => {
+                for ring in polygons.iter().flatten() {
+                    commands.move_to(&ring[..1]);
+                    commands.line_to(&ring[1..]);
+                    commands.close_path();
+                }
+            }
+        }
+        commands.commands
+    }
+}
+
+const MOVE_TO: u32 = 1;
+const LINE_TO: u32 = 2;
+const CLOSE_PATH: u32 = 7;
+
+#[derive(Default)]
+struct Commands {
+    commands: Vec<u32>,
+    cursor: [i32; 2],
+}
+
+impl Commands {
+    fn move_to(&mut self, points: &[[i32; 2]]) {
+        self.points(MOVE_TO, points);
+    }
+
+    fn line_to(&mut self, points: &[[i32; 2]]) {
+        self.points(LINE_TO, points);
+    }
+
+    fn close_path(&mut self) {
+        self.commands.push(CLOSE_PATH | (1 << 3));
+    }
+
+    fn points(&mut self, command: u32, points: &[[i32; 2]]) {
+        self.commands.push(command | ((points.len() as u32) << 3));
+        for &point in points {
+            for (value, cursor) in point.into_iter().zip(self.cursor) {
+                let delta = value.wrapping_sub(cursor);
+                self.commands.push(((delta << 1) ^ (delta >> 31)) as u32);
+            }
+            self.cursor = point;
+        }
+    }
+}
+
+/// A layer of the tile, with its features encoded as they're added.
+#[derive(Clone, Debug)]
+struct LayerEncoder {
+    name: String,
+    /// The encoded `features` fields of the layer
+    features: Vec<u8>,
+    keys: Vec<String>,
+    key_indexes: HashMap<String, u32>,
+    values: Vec<Value>,
+    value_indexes: HashMap<Value, u32>,
+}
+
+impl LayerEncoder {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            features: vec![],
+            keys: vec![],
+            key_indexes: HashMap::new(),
+            values: vec![],
+            value_indexes: HashMap::new(),
+        }
+    }
+
+    /// The indexes of each key and value of `properties`, adding any new ones.
+    fn tags<'a>(
+        &mut self,
+        properties: impl Iterator<Item = (&'a String, &'a JsonValue)>,
+    ) -> Vec<u32> {
+        let mut tags = vec![];
+        for (key, value) in properties {
+            let Some(value) = Value::from_json(value) else {
+                continue;
+            };
+            let key_index = *self.key_indexes.entry(key.clone()).or_insert_with(|| {
+                self.keys.push(key.clone());
+                self.keys.len() as u32 - 1
+            });
+            let value_index = match self.value_indexes.get(&value) {
+                Some(&index) => index,
+                None => {
+                    let index = self.values.len() as u32;
+                    self.values.push(value.clone());
+                    self.value_indexes.insert(value, index);
+                    index
+                }
+            };
+            tags.extend([key_index, value_index]);
+        }
+        tags
+    }
+
+    fn add_feature(&mut self, id: Option<u64>, tags: &[u32], geometry: &TileGeometry) {
+        if geometry.is_empty() {
+            return;
+        }
+        let mut feature = vec![];
+        if let Some(id) = id {
+            write_varint_field(&mut feature, 1, id);
+        }
+        if !tags.is_empty() {
+            write_packed(&mut feature, 2, tags);
+        }
+        write_varint_field(&mut feature, 3, geometry.geometry_type());
+        write_packed(&mut feature, 4, &geometry.commands());
+        write_bytes(&mut self.features, 2, &feature);
+    }
+}
+
+/// A value of a layer's values table.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Value {
+    String(String),
+    /// The bits of an `f64`, so that values can be hashed
+    Double(u64),
+    Uint(u64),
+    Sint(i64),
+    Bool(bool),
+}
+
+impl Value {
+    fn from_json(value: &JsonValue) -> Option<Self> {
+        Some(match value {
+            JsonValue::Null => return None,
+            JsonValue::Bool(value) => Value::Bool(*value),
+            JsonValue::Number(number) => {
+                if let Some(number) = number.as_u64() {
+                    Value::Uint(number)
+                } else if let Some(number) = number.as_i64() {
+                    Value::Sint(number)
+                } else {
+                    Value::Double(number.as_f64()?.to_bits())
+                }
+            }
+            JsonValue::String(value) => Value::String(value.clone()),
+            JsonValue::Array(_) | JsonValue::Object(_) => Value::String(value.to_string()),
+        })
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Value::String(value) => write_bytes(out, 1, value.as_bytes()),
+            Value::Double(bits) => {
+                write_key(out, 3, WIRE_FIXED64);
+                out.extend_from_slice(&bits.to_le_bytes());
+            }
+            Value::Uint(value) => write_varint_field(out, 5, *value),
+            Value::Sint(value) => write_varint_field(out, 6, ((value << 1) ^ (value >> 63)) as u64),
+            Value::Bool(value) => write_varint_field(out, 7, u64::from(*value)),
+        }
+    }
+}
+
+/// Decode a Mapbox Vector Tile into a [`FeatureCollection`] for each of its layers, by name.
+///
+/// The tile's coordinates are unprojected into WGS84 using its address, `tile`. Each feature's
+/// tags become its `properties`, and its `id`, if it has one, its `id`. Features whose geometry
+/// type is unknown have no geometry.
+///
+/// Polygons are wound as [RFC 7946](https://tools.ietf.org/html/rfc7946#section-3.1.6)
+/// recommends: exterior rings counter-clockwise, and holes clockwise.
+pub fn decode(bytes: &[u8], tile: TileId) -> Result<BTreeMap<String, FeatureCollection>> {
+    let mut layers: BTreeMap<String, FeatureCollection> = BTreeMap::new();
+    let mut reader = Reader::new(bytes, 0);
+    while let Some((field, wire)) = reader.field()? {
+        if field == 3 {
+            let (name, features) = decode_layer(wire.bytes(reader.offset)?, tile)?;
+            layers.entry(name).or_default().features.extend(features);
+        }
+    }
+    Ok(layers)
+}
+
+fn decode_layer(mut reader: Reader, tile: TileId) -> Result<(String, Vec<Feature>)> {
+    let mut name = String::new();
+    let mut features = vec![];
+    let mut keys = vec![];
+    let mut values = vec![];
+    let mut extent = 4096;
+    // Keys and values may come after the features that refer to them
+    while let Some((field, wire)) = reader.field()? {
+        match field {
+            1 => name = wire.bytes(reader.offset)?.string()?,
+            2 => features.push(wire.bytes(reader.offset)?),
+            3 => keys.push(wire.bytes(reader.offset)?.string()?),
+            4 => values.push(decode_value(wire.bytes(reader.offset)?)?),
+            5 => extent = u32::try_from(wire.varint(reader.offset)?).unwrap_or(0),
+            _ => {}
+        }
+    }
+    if extent == 0 {
+        return Err(reader.error("invalid layer extent"));
+    }
+    let features = features
+        .into_iter()
+        .map(|feature| decode_feature(feature, &keys, &values, tile, extent))
+        .collect::<Result<_>>()?;
+    Ok((name, features))
+}
+
+fn decode_feature(
+    mut reader: Reader,
+    keys: &[String],
+    values: &[JsonValue],
+    tile: TileId,
+    extent: u32,
+) -> Result<Feature> {
+    let mut id = None;
+    let mut tags = vec![];
+    let mut geometry_type = 0;
+    let mut commands = vec![];
+    let mut commands_offset = reader.offset;
+    loop {
+        let offset = reader.offset;
+        let Some((field, wire)) = reader.field()? else {
+            break;
+        };
+        match field {
+            1 => id = Some(wire.varint(reader.offset)?),
+            2 => wire.packed(reader.offset, &mut tags)?,
+            3 => geometry_type = wire.varint(reader.offset)?,
+            4 => {
+                commands_offset = offset;
+                wire.packed(reader.offset, &mut commands)?
+            }
+            _ => {}
+        }
+    }
+    let mut properties = JsonObject::new();
+    for tag in tags.chunks(2) {
+        let &[key, value] = tag else {
+            return Err(reader.error("odd number of tags"));
+        };
+        let (Some(key), Some(value)) = (keys.get(key as usize), values.get(value as usize)) else {
+            return Err(reader.error("tag refers to a missing key or value"));
+        };
+        properties.insert(key.clone(), value.clone());
+    }
+    let lines = decode_commands(&commands).map_err(|message| Error::InvalidMvt {
+        message: message.to_string(),
+        offset: commands_offset,
+    })?;
+    let unproject = |line: Vec<[i32; 2]>| -> Vec<Position> {
+        line.into_iter()
+            .map(|point| tile.unproject(point, extent))
+            .collect()
+    };
+    let value = match geometry_type {
+        1 => {
+            let mut points: Vec<Position> = lines.into_iter().flat_map(unproject).collect();
+            match points.len() {
+                0 => None,
+                1 => points.pop().map(GeometryValue::new_point),
+                _ => Some(GeometryValue::new_multi_point(points)),
+            }
+        }
+        2 => {
+            let mut lines: Vec<Vec<Position>> = lines.into_iter().map(unproject).collect();
+            match lines.len() {
+                0 => None,
+                1 => lines.pop().map(GeometryValue::new_line_string),
+                _ => Some(GeometryValue::new_multi_line_string(lines)),
+            }
+        }
+        3 => {
+            let mut polygons: Vec<Vec<Vec<Position>>> = decode_polygons(lines)
+                .into_iter()
+                .map(|polygon| polygon.into_iter().map(unproject).collect())
+                .collect();
+            match polygons.len() {
+                0 => None,
+                1 => polygons.pop().map(GeometryValue::new_polygon),
+                _ => Some(GeometryValue::new_multi_polygon(polygons)),
+            }
+        }
+        _ => None,
+    };
+    Ok(Feature {
+        bbox: None,
+        geometry: value.map(Geometry::new),
+        id: id.map(|id| feature::Id::Number(id.into())),
+        properties: Some(properties),
+        foreign_members: None,
+    })
+}
+
+/// Decode geometry commands into lists of tile coordinates, starting a new list for each
+/// `MoveTo`, and repeating the first point of a list on each `ClosePath`.
+fn decode_commands(commands: &[u32]) -> std::result::Result<Vec<Vec<[i32; 2]>>, &'static str> {
+    let mut lines: Vec<Vec<[i32; 2]>> = vec![];
+    let mut cursor = [0i32, 0];
+    let mut commands = commands.iter();
+    while let Some(&command) = commands.next() {
+        let count = command >> 3;
+        match command & 0x7 {
+            MOVE_TO | LINE_TO => {
+                for _ in 0..count {
+                    for axis in &mut cursor {
+                        let &value = commands.next().ok_or("missing command parameters")?;
+                        *axis = axis.wrapping_add(((value >> 1) as i32) ^ -((value & 1) as i32));
+                    }
+                    if command & 0x7 == MOVE_TO {
+                        lines.push(vec![]);
+                    }
+                    lines.last_mut().ok_or("LineTo before MoveTo")?.push(cursor);
+                }
+            }
+            CLOSE_PATH => {
+                let line = lines.last_mut().ok_or("ClosePath before MoveTo")?;
+                line.push(line[0]);
+            }
+            _ => return Err("unknown command"),
+        }
+    }
+    Ok(lines)
+}
+
+/// Group rings into polygons, each starting with an exterior ring, by their winding, and wind
+/// them as RFC 7946 recommends once unprojected.
+fn decode_polygons(rings: Vec<Vec<[i32; 2]>>) -> Vec<Vec<Vec<[i32; 2]>>> {
+    let mut polygons: Vec<Vec<Vec<[i32; 2]>>> = vec![];
+    // Version 1 tiles may wind their exterior rings either way, so follow the first ring
+    let mut exterior_sign = None;
+    for mut ring in rings {
+        let sign = signed_area(&ring).signum();
+        if sign == 0 {
+            continue;
+        }
+        let exterior_sign = *exterior_sign.get_or_insert(sign);
+        // Exterior rings are counter-clockwise in WGS84, so clockwise, with a negative area, in
+        // tile coordinates
+        if (sign == exterior_sign) == (sign > 0) {
+            ring.reverse();
+        }
+        match polygons.last_mut() {
+            Some(polygon) if sign != exterior_sign => polygon.push(ring),
+            _ => polygons.push(vec![ring]),
+        }
+    }
+    polygons
+}
+
+fn decode_value(mut reader: Reader) -> Result<JsonValue> {
+    let mut value = JsonValue::Null;
+    while let Some((field, wire)) = reader.field()? {
+        let offset = reader.offset;
+        value = match (field, wire) {
+            (1, wire) => JsonValue::String(wire.bytes(offset)?.string()?),
+            (2, Wire::Fixed32(bits)) => JsonValue::from(f32::from_bits(bits)),
+            (3, Wire::Fixed64(bits)) => JsonValue::from(f64::from_bits(bits)),
+            (4, wire) => JsonValue::from(wire.varint(offset)? as i64),
+            (5, wire) => JsonValue::from(wire.varint(offset)?),
+            (6, wire) => {
+                let value = wire.varint(offset)?;
+                JsonValue::from((value >> 1) as i64 ^ -((value & 1) as i64))
+            }
+            (7, wire) => JsonValue::Bool(wire.varint(offset)? != 0),
+            _ => continue,
+        };
+    }
+    Ok(value)
+}
+
+const WIRE_VARINT: u32 = 0;
+const WIRE_FIXED64: u32 = 1;
+const WIRE_BYTES: u32 = 2;
+const WIRE_FIXED32: u32 = 5;
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_key(out: &mut Vec<u8>, field: u32, wire_type: u32) {
+    write_varint(out, u64::from((field << 3) | wire_type));
+}
+
+fn write_varint_field(out: &mut Vec<u8>, field: u32, value: u64) {
+    write_key(out, field, WIRE_VARINT);
+    write_varint(out, value);
+}
+
+fn write_bytes(out: &mut Vec<u8>, field: u32, bytes: &[u8]) {
+    write_key(out, field, WIRE_BYTES);
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn write_packed(out: &mut Vec<u8>, field: u32, values: &[u32]) {
+    let mut bytes = vec![];
+    for &value in values {
+        write_varint(&mut bytes, u64::from(value));
+    }
+    write_bytes(out, field, &bytes);
+}
+
+/// A field's value, by its wire type.
+enum Wire<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    Bytes(&'a [u8]),
+    Fixed32(u32),
+}
+
+impl<'a> Wire<'a> {
+    /// The value of a length-delimited field, read from the field ending at `end`.
+    fn bytes(self, end: usize) -> Result<Reader<'a>> {
+        match self {
+            Wire::Bytes(bytes) => Ok(Reader::new(bytes, end - bytes.len())),
+            _ => Err(Error::InvalidMvt {
+                message: "expected a length-delimited field".to_string(),
+                offset: end,
+            }),
+        }
+    }
+
+    fn varint(self, end: usize) -> Result<u64> {
+        match self {
+            Wire::Varint(value) => Ok(value),
+            _ => Err(Error::InvalidMvt {
+                message: "expected a varint field".to_string(),
+                offset: end,
+            }),
+        }
+    }
+
+    /// Append the values of a repeated `uint32` field, whether or not it's packed.
+    fn packed(self, end: usize, values: &mut Vec<u32>) -> Result<()> {
+        match self {
+            Wire::Varint(value) => values.push(value as u32),
+            Wire::Bytes(_) => {
+                let mut reader = self.bytes(end)?;
+                while reader.offset < reader.end() {
+                    values.push(reader.varint()? as u32);
+                }
+            }
+            _ => {
+                return Err(Error::InvalidMvt {
+                    message: "expected a packed field".to_string(),
+                    offset: end,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the fields of a protobuf message.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    /// The offset of `bytes` within the whole tile, for errors
+    start: usize,
+    /// The offset of the next byte to read, within the whole tile
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], start: usize) -> Self {
+        Self {
+            bytes,
+            start,
+            offset: start,
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.start + self.bytes.len()
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::InvalidMvt {
+            message: message.to_string(),
+            offset: self.offset,
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let index = self.offset - self.start;
+        if self.bytes.len() - index < len {
+            return Err(self.error("unexpected end of the message"));
+        }
+        self.offset += len;
+        Ok(&self.bytes[index..index + len])
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte < 0x80 {
+                return Ok(value);
+            }
+        }
+        Err(self.error("varint is too long"))
+    }
+
+    /// The next field number and value, or `None` at the end of the message.
+    fn field(&mut self) -> Result<Option<(u32, Wire<'a>)>> {
+        if self.offset == self.end() {
+            return Ok(None);
+        }
+        let key = self.varint()?;
+        let field = u32::try_from(key >> 3).map_err(|_| self.error("invalid field number"))?;
+        let wire = match (key & 0x7) as u32 {
+            WIRE_VARINT => Wire::Varint(self.varint()?),
+            WIRE_FIXED64 => Wire::Fixed64(u64::from_le_bytes(self.take(8)?.try_into().unwrap())),
+            WIRE_BYTES => {
+                let len = usize::try_from(self.varint()?).unwrap_or(usize::MAX);
+                Wire::Bytes(self.take(len)?)
+            }
+            WIRE_FIXED32 => Wire::Fixed32(u32::from_le_bytes(self.take(4)?.try_into().unwrap())),
+            _ => return Err(self.error("unsupported wire type")),
+        };
+        Ok(Some((field, wire)))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let bytes = self.take(self.end() - self.offset)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| self.error("invalid UTF-8 string"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Geometry;
+    use serde_json::json;
+
+    fn feature(geometry: Geometry, properties: JsonValue) -> Feature {
+        Feature {
+            properties: Some(properties.as_object().unwrap().clone()),
+            ..Feature::from(geometry)
+        }
+    }
+
+    #[test]
+    fn encode_point() {
+        let mut encoder = TileEncoder::new(TileId::new(0, 0, 0));
+        encoder.add_feature(
+            "a",
+            &Feature {
+                id: Some(feature::Id::Number(1.into())),
+                ..feature(
+                    Geometry::new_point([0.0, 0.0]),
+                    json!({ "k": "v", "nothing": null }),
+                )
+            },
+        );
+        #[rustfmt::skip]
+        let expected = [
+            0x1a, 0x21, // Layer
+            0x0a, 0x01, b'a', // Name
+            0x12, 0x0f, // Feature
+            0x08, 0x01, // Id
+            0x12, 0x02, 0x00, 0x00, // Tags
+            0x18, 0x01, // Point
+            0x22, 0x05, 0x09, 0x80, 0x20, 0x80, 0x20, // MoveTo(2048, 2048)
+            0x1a, 0x01, b'k', // Key
+            0x22, 0x03, 0x0a, 0x01, b'v', // Value
+            0x28, 0x80, 0x20, // Extent
+            0x78, 0x02, // Version
+        ];
+        assert_eq!(encoder.encode(), expected);
+    }
+
+    #[test]
+    fn project() {
+        let tile = TileId::new(1, 1, 0);
+        assert_eq!(tile.project(&[0.0, 0.0].into(), 4096), Some([0.0, 4096.0]));
+        // Clamped to the edge of the map
+        let corner = tile.project(&[180.0, 90.0].into(), 4096).unwrap();
+        assert_eq!(round(corner), [4096, 0]);
+        let [x, y] = TileId::new(10, 511, 340)
+            .project(&[-0.1276, 51.5072].into(), 4096)
+            .unwrap();
+        assert!((0.0..4096.0).contains(&x) && (0.0..4096.0).contains(&y));
+    }
+
+    #[test]
+    fn clip_line() {
+        let clip = Clip {
+            min: 0.0,
+            max: 10.0,
+        };
+        assert_eq!(
+            clip.line(&[
+                [-5.0, 5.0],
+                [5.0, 5.0],
+                [5.0, 15.0],
+                [15.0, 15.0],
+                [15.0, 5.0],
+                [5.0, 5.0]
+            ]),
+            [
+                vec![[0.0, 5.0], [5.0, 5.0], [5.0, 10.0]],
+                vec![[10.0, 5.0], [5.0, 5.0]]
+            ]
+        );
+        assert!(clip.line(&[[-5.0, -5.0], [-5.0, 15.0]]).is_empty());
+    }
+
+    #[test]
+    fn clip_polygon() {
+        let encoder = TileEncoder::new(TileId::new(0, 0, 0))
+            .with_extent(4096)
+            .with_buffer(0);
+        let clip = Clip {
+            min: 0.0,
+            max: 4096.0,
+        };
+        // Counter-clockwise in WGS84, covering the east of the world and beyond
+        let polygon = encoder
+            .polygon(
+                &[vec![
+                    [0.0, -10.0].into(),
+                    [200.0, -10.0].into(),
+                    [200.0, 10.0].into(),
+                    [0.0, 10.0].into(),
+                    [0.0, -10.0].into(),
+                ]],
+                &clip,
+            )
+            .unwrap();
+        assert_eq!(polygon.len(), 1);
+        assert!(signed_area(&polygon[0]) > 0);
+        assert!(polygon[0].iter().all(|&[x, _]| (2048..=4096).contains(&x)));
+
+        // A hole is wound the other way
+        let polygon = encoder
+            .polygon(
+                &[
+                    vec![
+                        [-10.0, -10.0].into(),
+                        [-10.0, 10.0].into(),
+                        [10.0, 10.0].into(),
+                        [10.0, -10.0].into(),
+                        [-10.0, -10.0].into(),
+                    ],
+                    vec![
+                        [-1.0, -1.0].into(),
+                        [-1.0, 1.0].into(),
+                        [1.0, 1.0].into(),
+                        [1.0, -1.0].into(),
+                        [-1.0, -1.0].into(),
+                    ],
+                ],
+                &clip,
+            )
+            .unwrap();
+        assert!(signed_area(&polygon[0]) > 0);
+        assert!(signed_area(&polygon[1]) < 0);
+
+        // Outside the tile
+        assert!(
+            encoder
+                .polygon(
+                    &[vec![
+                        [0.0, 89.9].into(),
+                        [1.0, 89.9].into(),
+                        [1.0, 89.99].into(),
+                        [0.0, 89.9].into(),
+                    ]],
+                    &clip,
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn commands() {
+        let lines =
+            TileGeometry::Lines(vec![vec![[2, 2], [2, 10], [10, 10]], vec![[1, 1], [3, 5]]]);
+        // From the example in the specification
+        assert_eq!(
+            lines.commands(),
+            [9, 4, 4, 18, 0, 16, 16, 0, 9, 17, 17, 10, 4, 8]
+        );
+        let polygons = TileGeometry::Polygons(vec![vec![vec![[3, 6], [8, 12], [20, 34]]]]);
+        assert_eq!(polygons.commands(), [9, 6, 12, 18, 10, 12, 24, 44, 15]);
+    }
+
+    #[test]
+    fn layers_and_tags() {
+        let mut encoder = TileEncoder::new(TileId::new(0, 0, 0));
+        let properties = json!({ "int": -1, "uint": 1, "float": 1.5, "bool": true, "list": [1] });
+        encoder.add_feature(
+            "b",
+            &feature(Geometry::new_point([0.0, 0.0]), properties.clone()),
+        );
+        encoder.add_feature("b", &feature(Geometry::new_point([1.0, 1.0]), properties));
+        encoder.add_feature(
+            "b",
+            &feature(
+                Geometry::new_geometry_collection([
+                    Geometry::new_point([0.0, 0.0]),
+                    Geometry::new_line_string([[0.0, 0.0], [1.0, 1.0]]),
+                ]),
+                json!({ "uint": 1 }),
+            ),
+        );
+        // Without geometry
+        encoder.add_feature("c", &Feature::default());
+        let layer = &encoder.layers[0];
+        assert_eq!(encoder.layers.len(), 1);
+        assert_eq!(layer.keys, ["bool", "float", "int", "list", "uint"]);
+        assert_eq!(
+            layer.values,
+            [
+                Value::Bool(true),
+                Value::Double(1.5f64.to_bits()),
+                Value::Sint(-1),
+                Value::String("[1]".to_string()),
+                Value::Uint(1),
+            ]
+        );
+    }
+
+    /// Compare positions to within a unit of a zoom 4 tile.
+    fn assert_positions_eq(actual: &GeometryValue, expected: &GeometryValue) {
+        let actual_positions: Vec<&Position> = actual.coords().collect();
+        let expected_positions: Vec<&Position> = expected.coords().collect();
+        assert_eq!(actual.type_name(), expected.type_name());
+        assert_eq!(actual_positions.len(), expected_positions.len());
+        for (actual, expected) in actual_positions.iter().zip(&expected_positions) {
+            assert!(
+                (actual[0] - expected[0]).abs() < 1e-2 && (actual[1] - expected[1]).abs() < 1e-2,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn decode_point() {
+        let mut encoder = TileEncoder::new(TileId::new(0, 0, 0));
+        encoder.add_feature(
+            "a",
+            &Feature {
+                id: Some(feature::Id::Number(1.into())),
+                ..feature(Geometry::new_point([0.0, 0.0]), json!({ "k": "v" }))
+            },
+        );
+        let layers = decode(&encoder.encode(), TileId::new(0, 0, 0)).unwrap();
+        assert_eq!(
+            layers["a"],
+            FeatureCollection::from_iter([Feature {
+                id: Some(feature::Id::Number(1.into())),
+                ..feature(Geometry::new_point([0.0, 0.0]), json!({ "k": "v" }))
+            }])
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let tile = TileId::new(4, 8, 5);
+        let geometries = [
+            GeometryValue::new_multi_point([[1.0, 45.0], [2.0, 46.0]]),
+            GeometryValue::new_multi_line_string([
+                vec![[1.0, 45.0], [2.0, 46.0], [3.0, 45.0]],
+                vec![[4.0, 45.0], [5.0, 46.0]],
+            ]),
+            GeometryValue::new_multi_polygon([
+                vec![
+                    vec![
+                        [1.0, 45.0],
+                        [10.0, 45.0],
+                        [10.0, 50.0],
+                        [1.0, 50.0],
+                        [1.0, 45.0],
+                    ],
+                    vec![
+                        [2.0, 46.0],
+                        [2.0, 47.0],
+                        [3.0, 47.0],
+                        [3.0, 46.0],
+                        [2.0, 46.0],
+                    ],
+                ],
+                vec![vec![[11.0, 45.0], [12.0, 45.0], [12.0, 46.0], [11.0, 45.0]]],
+            ]),
+        ];
+        let properties = json!({ "int": -1, "uint": 1, "float": 1.5, "bool": true, "string": "a" });
+        let mut encoder = TileEncoder::new(tile);
+        for geometry in &geometries {
+            encoder.add_feature(
+                "b",
+                &feature(Geometry::new(geometry.clone()), properties.clone()),
+            );
+        }
+        let layers = decode(&encoder.encode(), tile).unwrap();
+        for (feature, expected) in layers["b"].features.iter().zip(&geometries) {
+            assert_positions_eq(&feature.geometry.as_ref().unwrap().value, expected);
+            assert_eq!(feature.properties, properties.as_object().cloned());
+            assert_eq!(feature.id, None);
+        }
+        assert_eq!(layers["b"].features.len(), 3);
+    }
+
+    #[test]
+    fn zero_extent() {
+        let tile = TileId::new(0, 0, 0);
+        let mut encoder = TileEncoder::new(tile).with_extent(0).with_buffer(0);
+        encoder.add_feature("a", &feature(Geometry::new_point([10.0, 10.0]), json!({})));
+        // The tile can be read back, though with a single cell, it can only hold the corners
+        let layers = decode(&encoder.encode(), tile).unwrap();
+        assert_eq!(layers["a"].features.len(), 1);
+    }
+
+    #[test]
+    fn decode_values() {
+        #[rustfmt::skip]
+        let layer = [
+            0x0a, 0x01, b'c', // Name
+            0x12, 0x0a, // Feature
+            0x12, 0x04, 0x00, 0x00, 0x00, 0x01, // Tags
+            0x18, 0x00, // Unknown geometry
+            0x08, 0x07, // Id, after the geometry
+            0x1a, 0x01, b'k', // Key
+            0x22, 0x05, 0x15, 0x00, 0x00, 0xc0, 0x3f, // Float 1.5
+            0x22, 0x0b, 0x20, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, // Int -1
+        ];
+        let mut tile = vec![];
+        write_bytes(&mut tile, 3, &layer);
+        let layers = decode(&tile, TileId::new(0, 0, 0)).unwrap();
+        assert_eq!(
+            layers["c"].features,
+            [Feature {
+                id: Some(feature::Id::Number(7.into())),
+                properties: json!({ "k": -1 }).as_object().cloned(),
+                ..Feature::default()
+            }]
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let mut encoder = TileEncoder::new(TileId::new(0, 0, 0));
+        encoder.add_feature(
+            "a",
+            &feature(Geometry::new_point([0.0, 0.0]), json!({ "k": "v" })),
+        );
+        let tile = encoder.encode();
+        for len in 1..tile.len() {
+            assert!(matches!(
+                decode(&tile[..len], TileId::new(0, 0, 0)),
+                Err(Error::InvalidMvt { .. })
+            ));
+        }
+        // A LineTo without a MoveTo
+        let mut feature = vec![];
+        write_varint_field(&mut feature, 3, 2);
+        write_packed(&mut feature, 4, &[10, 2, 2]);
+        let mut layer = vec![];
+        write_bytes(&mut layer, 2, &feature);
+        let mut tile = vec![];
+        write_bytes(&mut tile, 3, &layer);
+        assert!(matches!(
+            decode(&tile, TileId::new(0, 0, 0)),
+            Err(Error::InvalidMvt { offset: 6, .. })
+        ));
+    }
+}
@@ -0,0 +1,644 @@
+use crate::crs::{self, Reproject};
+use crate::{Crs, Error, GeoJson, Position, Result};
+
+use serde::Deserialize;
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::cell::Cell;
+use std::fmt;
+use std::io::Read;
+use std::sync::Arc;
+
+/// Options controlling how GeoJSON input is parsed.
+///
+/// By default this crate is permissive about what it accepts as input. Enabling
+/// [strict](ParseOptions::strict) mode rejects input which doesn't conform to
+/// [RFC 7946](https://tools.ietf.org/html/rfc7946), reporting the offending [`StrictViolation`]
+/// along with the line and column where it was found.
+///
+/// Options are passed to [`GeoJson::from_str_with_options`] or
+/// [`GeoJson::from_reader_with_options`]. `FromStr` has no way to take them, and
+/// [`GeoJson::from_reader`] returns a `serde_json` error which can't carry a [`StrictViolation`],
+/// so both always parse with the default options.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Error, GeoJson, ParseOptions, StrictViolation};
+///
+/// let geojson_str = r#"{
+///   "type": "Feature",
+///   "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+/// }"#;
+///
+/// // Accepted by default...
+/// assert!(GeoJson::from_str_with_options(geojson_str, &ParseOptions::default()).is_ok());
+///
+/// // ...but the spec requires a Feature to have a `properties` member.
+/// let err = GeoJson::from_str_with_options(geojson_str, &ParseOptions::strict()).unwrap_err();
+/// let Error::StrictModeViolation { violation, line, column } = err else {
+///     panic!("expected a strict mode violation");
+/// };
+/// assert_eq!(violation, StrictViolation::MissingProperties);
+/// assert_eq!((line, column), (4, 1));
+/// ```
+#[derive(Clone, Default)]
+pub struct ParseOptions {
+    strict: bool,
+    reproject: Option<Arc<Reproject>>,
+}
+
+impl fmt::Debug for ParseOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParseOptions")
+            .field("strict", &self.strict)
+            .field("reproject", &self.reproject.is_some())
+            .finish()
+    }
+}
+
+impl ParseOptions {
+    /// Options which reject any input that doesn't conform to RFC 7946.
+    ///
+    /// See [`StrictViolation`] for everything that's checked.
+    pub fn strict() -> Self {
+        Self::default().with_strict(true)
+    }
+
+    /// Enable or disable strict mode.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether strict mode is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Normalize data with a legacy [`Crs`] to WGS 84 longitude and latitude, as RFC 7946
+    /// requires, by calling `reproject` with the CRS of every position.
+    ///
+    /// Every object whose `crs` member isn't [CRS84](Crs::is_crs84) is reprojected, and the `crs`
+    /// members are removed. Objects without a `crs` member, or with a malformed one, inherit
+    /// their parent's, or are left as they are at the top level. A malformed `crs` member isn't
+    /// removed. Any `bbox` members of reprojected objects, and of the
+    /// objects containing them, are recomputed.
+    ///
+    /// `reproject` returns `None` for a CRS it doesn't support, failing the parse with
+    /// [`Error::UnsupportedCrs`]. Strict mode rejects any `crs` member before this happens.
+    ///
+    /// Only [`GeoJson::from_str_with_options`] and [`GeoJson::from_reader_with_options`]
+    /// reproject. Parsing any other way, e.g. with `FromStr` or a
+    /// [`FeatureReader`](crate::FeatureReader), leaves `crs` members as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{GeoJson, GeometryValue, ParseOptions, projection};
+    ///
+    /// let geojson_str = r#"{
+    ///   "type": "Feature",
+    ///   "crs": { "type": "name", "properties": { "name": "urn:ogc:def:crs:EPSG::3857" } },
+    ///   "properties": null,
+    ///   "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+    /// }"#;
+    ///
+    /// let options = ParseOptions::default().with_reprojection(|crs, position| {
+    ///     (crs.epsg_code() == Some(3857)).then(|| projection::from_web_mercator(position))
+    /// });
+    /// let GeoJson::Feature(feature) = GeoJson::from_str_with_options(geojson_str, &options).unwrap() else {
+    ///     panic!("expected a Feature");
+    /// };
+    /// assert_eq!(feature.geometry.unwrap().value, GeometryValue::new_point([0.0, 0.0]));
+    /// assert_eq!(feature.foreign_members, None);
+    /// ```
+    pub fn with_reprojection(
+        mut self,
+        reproject: impl Fn(&Crs, &Position) -> Option<Position> + Send + Sync + 'static,
+    ) -> Self {
+        self.reproject = Some(Arc::new(reproject));
+        self
+    }
+
+    fn parse_slice(&self, input: &[u8]) -> Result<GeoJson> {
+        if self.strict {
+            check_strict(input)?;
+        }
+        self.finish(serde_json::from_slice(input)?)
+    }
+
+    fn finish(&self, mut geojson: GeoJson) -> Result<GeoJson> {
+        if let Some(reproject) = &self.reproject {
+            crs::normalize(&mut geojson, reproject.as_ref())?;
+        }
+        Ok(geojson)
+    }
+}
+
+/// Input accepted by default, but rejected when parsing with [`ParseOptions::strict`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StrictViolation {
+    /// A Feature has no `properties` member. It must have one, even if it's `null`.
+    ///
+    /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
+    MissingProperties,
+    /// A Feature's `id` is `null`. It must be either a string or a number.
+    ///
+    /// [GeoJSON Format Specification § 3.2](https://tools.ietf.org/html/rfc7946#section-3.2)
+    NullId,
+    /// A position has more than three elements.
+    ///
+    /// [GeoJSON Format Specification § 3.1.1](https://tools.ietf.org/html/rfc7946#section-3.1.1)
+    PositionTooLong(usize),
+    /// A `bbox` member which isn't an array, e.g. `"bbox": null`.
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    BboxNotArray,
+    /// A member reserved for one type of GeoJSON object was used on another, e.g. a Feature
+    /// with a `coordinates` member. These would otherwise be silently dropped, rather than
+    /// appearing in `foreign_members`.
+    ///
+    /// [GeoJSON Format Specification § 7.1](https://tools.ietf.org/html/rfc7946#section-7.1)
+    ReservedMember {
+        member: &'static str,
+        object_type: &'static str,
+    },
+    /// The `crs` member from the 2008 GeoJSON specification. RFC 7946 coordinates are always
+    /// WGS 84 longitude and latitude.
+    ///
+    /// [GeoJSON Format Specification Appendix B.1](https://tools.ietf.org/html/rfc7946#appendix-B.1)
+    CrsMember,
+}
+
+impl fmt::Display for StrictViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrictViolation::MissingProperties => {
+                f.write_str("Feature is missing a `properties` member")
+            }
+            StrictViolation::NullId => f.write_str("Feature `id` must not be null"),
+            StrictViolation::PositionTooLong(len) => write!(
+                f,
+                "A position must not contain more than three elements, but got `{len}`"
+            ),
+            StrictViolation::BboxNotArray => f.write_str("`bbox` must be an array"),
+            StrictViolation::ReservedMember {
+                member,
+                object_type,
+            } => write!(f, "`{member}` is not allowed on a {object_type} object"),
+            StrictViolation::CrsMember => {
+                f.write_str("the `crs` member is not supported by RFC 7946")
+            }
+        }
+    }
+}
+
+impl GeoJson {
+    /// Deserialize a GeoJson object from an IO stream of JSON, using the given [`ParseOptions`].
+    ///
+    /// In strict mode, the entire input is buffered before parsing.
+    pub fn from_reader_with_options<R>(mut rdr: R, options: &ParseOptions) -> Result<Self>
+    where
+        R: Read,
+    {
+        if !options.is_strict() {
+            return options.finish(serde_json::from_reader(rdr)?);
+        }
+        let mut input = vec![];
+        rdr.read_to_end(&mut input)?;
+        options.parse_slice(&input)
+    }
+
+    /// Deserialize a GeoJson object from a string, using the given [`ParseOptions`].
+    pub fn from_str_with_options(s: &str, options: &ParseOptions) -> Result<Self> {
+        options.parse_slice(s.as_bytes())
+    }
+}
+
+/// Walk the JSON input, checking for anything not allowed in strict mode.
+///
+/// serde_json attaches a line and column to any error raised while walking, so a violation is
+/// reported as a custom error, while the violation itself is passed back out via `found`.
+fn check_strict(input: &[u8]) -> Result<()> {
+    let found = Cell::new(None);
+    let mut deserializer = serde_json::Deserializer::from_slice(input);
+    let seed = ObjectSeed {
+        found: &found,
+        expected: None,
+    };
+    let result = seed
+        .deserialize(&mut deserializer)
+        .and_then(|()| deserializer.end());
+    match (result, found.take()) {
+        (Ok(()), _) => Ok(()),
+        (Err(err), Some(violation)) => Err(Error::StrictModeViolation {
+            violation,
+            line: err.line(),
+            column: err.column(),
+        }),
+        (Err(err), None) => Err(err.into()),
+    }
+}
+
+type Found<'a> = &'a Cell<Option<StrictViolation>>;
+
+fn violation<E: de::Error>(found: Found, violation: StrictViolation) -> E {
+    let err = E::custom(&violation);
+    found.set(Some(violation));
+    err
+}
+
+/// A GeoJSON object. `expected` is the type of object implied by its position in the document,
+/// or `None` at the top level.
+#[derive(Clone, Copy)]
+struct ObjectSeed<'a> {
+    found: Found<'a>,
+    expected: Option<&'static str>,
+}
+
+impl<'de> DeserializeSeed<'de> for ObjectSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ObjectSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("GeoJson object")
+    }
+
+    // `"geometry": null` is allowed, anything else which isn't an object will be rejected when
+    // actually parsing.
+    fn visit_unit<E: de::Error>(self) -> std::result::Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+        let found = self.found;
+        let mut object_type: Option<String> = None;
+        let mut has_properties = false;
+        let mut null_id = false;
+        let mut reserved_members: Vec<&'static str> = vec![];
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "type" => object_type = Some(map.next_value()?),
+                "bbox" => map.next_value_seed(BboxSeed { found })?,
+                "crs" => return Err(violation(found, StrictViolation::CrsMember)),
+                "id" => null_id = map.next_value::<Option<IgnoredAny>>()?.is_none(),
+                "properties" => {
+                    has_properties = true;
+                    reserved_members.push("properties");
+                    map.next_value::<IgnoredAny>()?;
+                }
+                "coordinates" => {
+                    reserved_members.push("coordinates");
+                    map.next_value_seed(CoordinatesSeed { found })?;
+                }
+                "geometry" => {
+                    reserved_members.push("geometry");
+                    map.next_value_seed(ObjectSeed {
+                        found,
+                        expected: Some("Geometry"),
+                    })?;
+                }
+                "geometries" => {
+                    reserved_members.push("geometries");
+                    map.next_value_seed(ObjectsSeed(ObjectSeed {
+                        found,
+                        expected: Some("Geometry"),
+                    }))?;
+                }
+                "features" => {
+                    map.next_value_seed(ObjectsSeed(ObjectSeed {
+                        found,
+                        expected: Some("Feature"),
+                    }))?;
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        let object_type = match (object_type.as_deref(), self.expected) {
+            (Some("Feature"), _) => "Feature",
+            (Some("FeatureCollection"), _) => "FeatureCollection",
+            (Some(_), _) => "Geometry",
+            // A missing `type` is rejected when actually parsing
+            (None, Some(expected)) => expected,
+            (None, None) => return Ok(()),
+        };
+
+        // https://tools.ietf.org/html/rfc7946#section-7.1
+        let disallowed: &[&'static str] = match object_type {
+            "Feature" => &["coordinates", "geometries"],
+            "FeatureCollection" => &["coordinates", "geometries", "geometry", "properties"],
+            _ => &["geometry", "properties"],
+        };
+        if let Some(member) = reserved_members
+            .into_iter()
+            .find(|member| disallowed.contains(member))
+        {
+            return Err(violation(
+                found,
+                StrictViolation::ReservedMember {
+                    member,
+                    object_type,
+                },
+            ));
+        }
+
+        if object_type == "Feature" {
+            if !has_properties {
+                return Err(violation(found, StrictViolation::MissingProperties));
+            }
+            if null_id {
+                return Err(violation(found, StrictViolation::NullId));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An array of GeoJSON objects, i.e. `features` or `geometries`.
+struct ObjectsSeed<'a>(ObjectSeed<'a>);
+
+impl<'de> DeserializeSeed<'de> for ObjectsSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ObjectsSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of GeoJson objects")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        while seq.next_element_seed(self.0)?.is_some() {}
+        Ok(())
+    }
+}
+
+struct BboxSeed<'a> {
+    found: Found<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for BboxSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<(), D::Error> {
+        match Option::<IgnoredAny>::deserialize(deserializer)? {
+            Some(_) => Ok(()),
+            None => Err(violation(self.found, StrictViolation::BboxNotArray)),
+        }
+    }
+}
+
+/// The (potentially nested) `coordinates` array of a Geometry.
+#[derive(Clone, Copy)]
+struct CoordinatesSeed<'a> {
+    found: Found<'a>,
+}
+
+impl<'de> DeserializeSeed<'de> for CoordinatesSeed<'_> {
+    type Value = bool;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<bool, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for CoordinatesSeed<'_> {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a coordinate element (number or array)")
+    }
+
+    fn visit_i64<E>(self, _value: i64) -> std::result::Result<bool, E> {
+        Ok(true)
+    }
+
+    fn visit_u64<E>(self, _value: u64) -> std::result::Result<bool, E> {
+        Ok(true)
+    }
+
+    fn visit_f64<E>(self, _value: f64) -> std::result::Result<bool, E> {
+        Ok(true)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<bool, A::Error> {
+        // Only a position directly contains numbers, anything else is a list of positions (or a
+        // list of lists...)
+        let mut numbers = 0;
+        while let Some(is_number) = seq.next_element_seed(self)? {
+            if is_number {
+                numbers += 1;
+            }
+        }
+        if numbers > 3 {
+            return Err(violation(
+                self.found,
+                StrictViolation::PositionTooLong(numbers),
+            ));
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn strict_violation(geojson_str: &str) -> (StrictViolation, usize, usize) {
+        match GeoJson::from_str_with_options(geojson_str, &ParseOptions::strict()) {
+            Err(Error::StrictModeViolation {
+                violation,
+                line,
+                column,
+            }) => (violation, line, column),
+            other => panic!("expected strict mode violation, got {other:?}"),
+        }
+    }
+
+    fn assert_permissive_only(geojson_str: &str) {
+        GeoJson::from_str_with_options(geojson_str, &ParseOptions::default()).unwrap();
+        GeoJson::from_str_with_options(geojson_str, &ParseOptions::strict()).unwrap_err();
+    }
+
+    #[test]
+    fn accepts_valid_input() {
+        let geojson_str = json!({
+            "type": "FeatureCollection",
+            "bbox": [100.0, 0.0, 105.0, 1.0],
+            "features": [
+                {
+                    "type": "Feature",
+                    "id": 1,
+                    "geometry": { "type": "Point", "coordinates": [102.0, 0.5, 10.0] },
+                    "properties": null
+                },
+                {
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "GeometryCollection",
+                        "geometries": [
+                            { "type": "Polygon", "coordinates": [[[100.0, 0.0], [101.0, 0.0], [101.0, 1.0], [100.0, 0.0]]] }
+                        ]
+                    },
+                    "properties": { "coordinates": "foreign members may be nested in properties" },
+                    "title": "foreign members are fine"
+                }
+            ]
+        })
+        .to_string();
+        let strict = GeoJson::from_str_with_options(&geojson_str, &ParseOptions::strict()).unwrap();
+        let permissive: GeoJson = geojson_str.parse().unwrap();
+        assert_eq!(strict, permissive);
+    }
+
+    #[test]
+    fn missing_properties() {
+        let geojson_str = json!({
+            "type": "FeatureCollection",
+            "features": [{ "type": "Feature", "geometry": null }]
+        })
+        .to_string();
+        assert_permissive_only(&geojson_str);
+        assert_eq!(
+            strict_violation(&geojson_str).0,
+            StrictViolation::MissingProperties
+        );
+    }
+
+    #[test]
+    fn null_id() {
+        let geojson_str = r#"{
+            "type": "Feature",
+            "id": null,
+            "geometry": null,
+            "properties": {}
+        }"#;
+        assert_permissive_only(geojson_str);
+        assert_eq!(
+            strict_violation(geojson_str),
+            (StrictViolation::NullId, 6, 9)
+        );
+    }
+
+    #[test]
+    fn position_too_long() {
+        let geojson_str = r#"{
+            "type": "LineString",
+            "coordinates": [[1, 2], [1, 2, 3, 4]]
+        }"#;
+        assert_permissive_only(geojson_str);
+        assert_eq!(
+            strict_violation(geojson_str),
+            (StrictViolation::PositionTooLong(4), 3, 48)
+        );
+    }
+
+    #[test]
+    fn null_bbox() {
+        let geojson_str = r#"{"type": "Point", "coordinates": [1, 2], "bbox": null}"#;
+        assert_permissive_only(geojson_str);
+        assert_eq!(
+            strict_violation(geojson_str),
+            (StrictViolation::BboxNotArray, 1, 54)
+        );
+    }
+
+    #[test]
+    fn reserved_members() {
+        let geojson_str = json!({
+            "type": "Feature",
+            "geometry": null,
+            "properties": null,
+            "coordinates": [1, 2]
+        })
+        .to_string();
+        assert_permissive_only(&geojson_str);
+        assert_eq!(
+            strict_violation(&geojson_str).0,
+            StrictViolation::ReservedMember {
+                member: "coordinates",
+                object_type: "Feature"
+            }
+        );
+
+        let geojson_str = json!({
+            "type": "Point",
+            "coordinates": [1, 2],
+            "properties": {}
+        })
+        .to_string();
+        assert_permissive_only(&geojson_str);
+        assert_eq!(
+            strict_violation(&geojson_str).0,
+            StrictViolation::ReservedMember {
+                member: "properties",
+                object_type: "Geometry"
+            }
+        );
+    }
+
+    #[test]
+    fn crs_member() {
+        let geojson_str = json!({
+            "type": "FeatureCollection",
+            "crs": { "type": "name", "properties": { "name": "urn:ogc:def:crs:EPSG::3857" } },
+            "features": []
+        })
+        .to_string();
+        assert_permissive_only(&geojson_str);
+        assert_eq!(strict_violation(&geojson_str).0, StrictViolation::CrsMember);
+    }
+
+    #[test]
+    fn malformed_input() {
+        let err = GeoJson::from_str_with_options("{", &ParseOptions::strict()).unwrap_err();
+        assert!(matches!(err, Error::MalformedGeoJson(_)));
+    }
+
+    #[test]
+    fn from_reader() {
+        let geojson_str = r#"{"type": "Feature", "geometry": null}"#;
+        GeoJson::from_reader_with_options(geojson_str.as_bytes(), &ParseOptions::default())
+            .unwrap();
+        let err =
+            GeoJson::from_reader_with_options(geojson_str.as_bytes(), &ParseOptions::strict())
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::StrictModeViolation {
+                violation: StrictViolation::MissingProperties,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,349 @@
+//! The [encoded polyline](https://developers.google.com/maps/documentation/utilities/polylinealgorithm)
+//! algorithm.
+use crate::{Error, GeometryValue, Position, Result};
+
+impl GeometryValue {
+    /// Decode a `LineString` from an [encoded polyline](https://developers.google.com/maps/documentation/utilities/polylinealgorithm).
+    ///
+    /// `precision` is the number of decimal places the polyline was encoded with: 5 for Google
+    /// Maps, or 6 for OSRM and Valhalla. Polylines are encoded as latitude then longitude, and
+    /// are swapped into GeoJSON's longitude then latitude.
+    ///
+    /// A precision above 15, beyond the digits of an `f64`, is an
+    /// [`Error::InvalidPolylinePrecision`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let line_string = GeometryValue::from_polyline("_p~iF~ps|U_ulLnnqC_mqNvxq`@", 5).unwrap();
+    /// assert_eq!(
+    ///     line_string,
+    ///     GeometryValue::new_line_string([[-120.2, 38.5], [-120.95, 40.7], [-126.453, 43.252]])
+    /// );
+    /// assert_eq!(line_string.to_polyline(5).unwrap(), "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+    /// ```
+    pub fn from_polyline(polyline: &str, precision: u32) -> Result<Self> {
+        Ok(GeometryValue::LineString {
+            coordinates: decode(polyline, precision)?,
+        })
+    }
+
+    /// Decode a `MultiLineString` from a list of [encoded polylines](https://developers.google.com/maps/documentation/utilities/polylinealgorithm),
+    /// one for each line.
+    ///
+    /// See [`GeometryValue::from_polyline`].
+    pub fn from_polylines(
+        polylines: impl IntoIterator<Item = impl AsRef<str>>,
+        precision: u32,
+    ) -> Result<Self> {
+        Ok(GeometryValue::MultiLineString {
+            coordinates: polylines
+                .into_iter()
+                .map(|polyline| decode(polyline.as_ref(), precision))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Encode a `LineString` as an [encoded polyline](https://developers.google.com/maps/documentation/utilities/polylinealgorithm),
+    /// rounded to `precision` decimal places.
+    ///
+    /// Each position is written as latitude then longitude, and any altitude is dropped.
+    /// Coordinates which aren't finite, or which are too large to be encoded exactly at this
+    /// precision, are an [`Error::UnencodablePolyline`]. As when decoding, a precision above 15
+    /// is an [`Error::InvalidPolylinePrecision`].
+    ///
+    /// Returns [`Error::InvalidGeometryConversion`] for any other type of geometry. To encode a
+    /// `MultiLineString`, see [`GeometryValue::to_polylines`].
+    pub fn to_polyline(&self, precision: u32) -> Result<String> {
+        match self {
+            GeometryValue::LineString { coordinates } => encode(coordinates, precision),
+            _ => Err(Error::InvalidGeometryConversion {
+                expected_type: "LineString",
+                found_type: self.type_name(),
+            }),
+        }
+    }
+
+    /// Encode each line of a `MultiLineString`, or the line of a `LineString`, as an
+    /// [encoded polyline](https://developers.google.com/maps/documentation/utilities/polylinealgorithm).
+    ///
+    /// See [`GeometryValue::to_polyline`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let lines = GeometryValue::new_multi_line_string([
+    ///     [[13.388798, 52.517033], [13.397631, 52.529432]],
+    ///     [[13.428554, 52.523239], [13.418946, 52.502031]],
+    /// ]);
+    /// let polylines = lines.to_polylines(6).unwrap();
+    /// assert_eq!(GeometryValue::from_polylines(&polylines, 6).unwrap(), lines);
+    /// ```
+    pub fn to_polylines(&self, precision: u32) -> Result<Vec<String>> {
+        match self {
+            GeometryValue::LineString { coordinates } => Ok(vec![encode(coordinates, precision)?]),
+            GeometryValue::MultiLineString { coordinates } => coordinates
+                .iter()
+                .map(|line| encode(line, precision))
+                .collect(),
+            _ => Err(Error::InvalidGeometryConversion {
+                expected_type: "LineString or MultiLineString",
+                found_type: self.type_name(),
+            }),
+        }
+    }
+}
+
+/// The largest magnitude of a scaled coordinate, below which every integer is exactly
+/// representable as an `f64`.
+const MAX_SCALED: f64 = (1u64 << 53) as f64;
+
+/// The most decimal places an `f64` can hold.
+const MAX_PRECISION: u32 = 15;
+
+/// The factor coordinates are scaled by to encode them at `precision`.
+fn factor(precision: u32) -> Result<f64> {
+    if precision > MAX_PRECISION {
+        return Err(Error::InvalidPolylinePrecision(precision));
+    }
+    Ok(10f64.powi(precision as i32))
+}
+
+fn encode(line: &[Position], precision: u32) -> Result<String> {
+    let factor = factor(precision)?;
+    let mut polyline = String::new();
+    let mut previous = [0i64, 0];
+    for (position_index, position) in line.iter().enumerate() {
+        let &[longitude, latitude, ..] = position.as_slice() else {
+            return Err(Error::PositionTooShort(position.len()));
+        };
+        for (index, coordinate) in [latitude, longitude].into_iter().enumerate() {
+            let scaled = (coordinate * factor).round();
+            if !scaled.is_finite() || scaled.abs() > MAX_SCALED {
+                return Err(Error::UnencodablePolyline {
+                    coordinate,
+                    index: position_index,
+                });
+            }
+            let scaled = scaled as i64;
+            // Both values are within 2^53, so this can't overflow
+            encode_value(&mut polyline, scaled - previous[index]);
+            previous[index] = scaled;
+        }
+    }
+    Ok(polyline)
+}
+
+/// Write a signed value as chunks of five bits, least significant first, each but the last
+/// flagged with `0x20`.
+fn encode_value(polyline: &mut String, value: i64) {
+    let mut value = if value < 0 { !(value << 1) } else { value << 1 } as u64;
+    while value >= 0x20 {
+        polyline.push(char::from((0x20 | (value & 0x1f)) as u8 + 63));
+        value >>= 5;
+    }
+    polyline.push(char::from(value as u8 + 63));
+}
+
+fn decode(polyline: &str, precision: u32) -> Result<Vec<Position>> {
+    let factor = factor(precision)?;
+    let bytes = polyline.as_bytes();
+    let mut line = vec![];
+    let mut offset = 0;
+    let mut previous = [0i64, 0];
+    let next = |previous: i64, offset: &mut usize| {
+        let start = *offset;
+        previous
+            .checked_add(decode_value(bytes, offset)?)
+            .ok_or_else(|| Error::InvalidPolyline {
+                message: "coordinate overflows".to_string(),
+                offset: start,
+            })
+    };
+    while offset < bytes.len() {
+        let latitude = next(previous[0], &mut offset)?;
+        if offset == bytes.len() {
+            return Err(Error::InvalidPolyline {
+                message: "expected a longitude after the latitude".to_string(),
+                offset,
+            });
+        }
+        let longitude = next(previous[1], &mut offset)?;
+        previous = [latitude, longitude];
+        line.push(Position::from([
+            longitude as f64 / factor,
+            latitude as f64 / factor,
+        ]));
+    }
+    Ok(line)
+}
+
+fn decode_value(bytes: &[u8], offset: &mut usize) -> Result<i64> {
+    let mut value: u64 = 0;
+    let mut shift = 0;
+    loop {
+        let error = |message: &str| Error::InvalidPolyline {
+            message: message.to_string(),
+            offset: *offset,
+        };
+        let byte = match bytes.get(*offset) {
+            None => return Err(error("unexpected end of the polyline")),
+            Some(byte @ 63..=126) => byte - 63,
+            Some(_) => return Err(error("unexpected character")),
+        };
+        let chunk = u64::from(byte & 0x1f);
+        // Any bits shifted past the 64th would be lost
+        if shift >= 64 || (chunk << shift) >> shift != chunk {
+            return Err(error("value is too large"));
+        }
+        value |= chunk << shift;
+        shift += 5;
+        *offset += 1;
+        if byte < 0x20 {
+            break;
+        }
+    }
+    let magnitude = (value >> 1) as i64;
+    Ok(if value & 1 == 1 {
+        !magnitude
+    } else {
+        magnitude
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_polylines() {
+        // From https://developers.google.com/maps/documentation/utilities/polylinealgorithm
+        let line_string =
+            GeometryValue::new_line_string([[-120.2, 38.5], [-120.95, 40.7], [-126.453, 43.252]]);
+        assert_eq!(
+            GeometryValue::from_polyline("_p~iF~ps|U_ulLnnqC_mqNvxq`@", 5).unwrap(),
+            line_string
+        );
+        assert_eq!(
+            line_string.to_polyline(5).unwrap(),
+            "_p~iF~ps|U_ulLnnqC_mqNvxq`@"
+        );
+        assert_eq!(
+            line_string.to_polyline(6).unwrap(),
+            "_izlhA~rlgdF_{geC~ywl@_kwzCn`{nI"
+        );
+        assert_eq!(
+            GeometryValue::from_polyline("_izlhA~rlgdF_{geC~ywl@_kwzCn`{nI", 6).unwrap(),
+            line_string
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let lines = GeometryValue::new_multi_line_string([
+            vec![
+                vec![-0.000001, 0.000001, 100.0],
+                vec![179.999999, -89.999999, 200.0],
+            ],
+            vec![],
+            vec![vec![13.388798, 52.517033]],
+        ]);
+        let polylines = lines.to_polylines(6).unwrap();
+        assert_eq!(polylines[1], "");
+        assert_eq!(
+            GeometryValue::from_polylines(&polylines, 6).unwrap(),
+            GeometryValue::new_multi_line_string([
+                vec![[-0.000001, 0.000001], [179.999999, -89.999999]],
+                vec![],
+                vec![[13.388798, 52.517033]],
+            ])
+        );
+        // Rounded to the precision
+        assert_eq!(
+            GeometryValue::from_polyline(&lines.to_polylines(5).unwrap()[2], 5).unwrap(),
+            GeometryValue::new_line_string([[13.3888, 52.51703]])
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for polyline in ["_p~iF", "_p~iF~ps|", "_p~iF ~ps|U", "ü"] {
+            assert!(
+                matches!(
+                    GeometryValue::from_polyline(polyline, 5),
+                    Err(Error::InvalidPolyline { .. })
+                ),
+                "{polyline}"
+            );
+        }
+        assert!(matches!(
+            GeometryValue::new_point([1.0, 2.0]).to_polyline(5),
+            Err(Error::InvalidGeometryConversion { .. })
+        ));
+        assert!(matches!(
+            GeometryValue::new_point([1.0, 2.0]).to_polylines(5),
+            Err(Error::InvalidGeometryConversion {
+                expected_type: "LineString or MultiLineString",
+                ..
+            })
+        ));
+        assert!(matches!(
+            GeometryValue::new_line_string([vec![1.0]]).to_polyline(5),
+            Err(Error::PositionTooShort(1))
+        ));
+    }
+
+    #[test]
+    fn overflow() {
+        // Each value is the most negative i64, so the second latitude overflows
+        assert!(matches!(
+            GeometryValue::from_polyline(&"~~~~~~~~~~~~N".repeat(4), 5),
+            Err(Error::InvalidPolyline { offset: 26, .. })
+        ));
+        // A final chunk with bits beyond the 64th
+        assert!(matches!(
+            GeometryValue::from_polyline("~~~~~~~~~~~~~?", 5),
+            Err(Error::InvalidPolyline { .. })
+        ));
+
+        for coordinate in [1e300, -1e300, f64::NAN, f64::INFINITY] {
+            let line_string = GeometryValue::new_line_string([[0.0, 0.0], [coordinate, 0.0]]);
+            assert!(
+                matches!(
+                    line_string.to_polyline(5),
+                    Err(Error::UnencodablePolyline { index: 1, .. })
+                ),
+                "{coordinate}"
+            );
+        }
+    }
+
+    #[test]
+    fn precision() {
+        let line_string = GeometryValue::new_line_string([[0.0, 0.0], [1.0, 2.0]]);
+        let polyline = line_string.to_polyline(15).unwrap();
+        assert_eq!(
+            GeometryValue::from_polyline(&polyline, 15).unwrap(),
+            line_string
+        );
+        for precision in [16, 40, 400, u32::MAX] {
+            assert!(matches!(
+                line_string.to_polyline(precision),
+                Err(Error::InvalidPolylinePrecision(p)) if p == precision
+            ));
+            assert!(matches!(
+                GeometryValue::from_polyline("??", precision),
+                Err(Error::InvalidPolylinePrecision(_))
+            ));
+        }
+        // At the most precision, only small coordinates can be encoded
+        assert!(matches!(
+            GeometryValue::new_line_string([[0.0, 10.0]]).to_polyline(15),
+            Err(Error::UnencodablePolyline { index: 0, .. })
+        ));
+    }
+}
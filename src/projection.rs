@@ -0,0 +1,184 @@
+//! Converting positions between WGS84 longitude and latitude
+//! ([EPSG:4326](https://epsg.io/4326)), [Web Mercator](https://epsg.io/3857) metres, and the
+//! pixels of a web map at a zoom level.
+//!
+//! Each function takes and returns a single [`Position`], keeping any altitude or other elements
+//! beyond the first two, and returning positions with fewer than two elements as they are. To
+//! project a whole geometry or collection, pass one to `map_coords`:
+//!
+//! ```
+//! use geojson::{BboxUpdate, Feature, FeatureCollection, Geometry, GeometryValue};
+//! use geojson::projection;
+//!
+//! let mut collection = FeatureCollection::from_iter([Feature::from(Geometry::new_line_string([
+//!     [-180.0, 0.0],
+//!     [180.0, 90.0],
+//! ]))]);
+//! collection.map_coords(BboxUpdate::Remove, projection::to_web_mercator);
+//! let metres: Vec<[f64; 2]> = collection
+//!     .coords()
+//!     .map(|position| [position[0].round(), position[1].round()])
+//!     .collect();
+//! assert_eq!(metres, [[-20_037_508.0, 0.0], [20_037_508.0, 20_037_508.0]]);
+//!
+//! // The pixels of 256px tiles at zoom 2
+//! let mut point = GeometryValue::new_point([0.0, 0.0]);
+//! point.map_coords(BboxUpdate::Remove, |position| projection::to_pixels(position, 2.0, 256.0));
+//! assert_eq!(point, GeometryValue::new_point([512.0, 512.0]));
+//! ```
+use crate::Position;
+
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+/// The latitude beyond which Web Mercator is clamped, so that the map is square.
+pub const MAX_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// The largest x or y of Web Mercator, in metres: half the circumference of the earth at the
+/// equator.
+pub const MAX_MERCATOR: f64 = 20_037_508.342_789_244;
+
+/// The radius of the WGS84 ellipsoid at the equator, in metres
+const EARTH_RADIUS: f64 = 6_378_137.0;
+
+/// `position` with its first two elements replaced by `x` and `y`.
+fn with_xy(position: &Position, x: f64, y: f64) -> Position {
+    let mut position = position.clone();
+    position[0] = x;
+    position[1] = y;
+    position
+}
+
+/// Project a WGS84 longitude and latitude to Web Mercator metres.
+///
+/// Latitudes are clamped to ±[`MAX_LATITUDE`].
+pub fn to_web_mercator(position: &Position) -> Position {
+    let &[longitude, latitude, ..] = position.as_slice() else {
+        return position.clone();
+    };
+    let latitude = latitude.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    with_xy(
+        position,
+        longitude.to_radians() * EARTH_RADIUS,
+        (FRAC_PI_4 + latitude / 2.0).tan().ln() * EARTH_RADIUS,
+    )
+}
+
+/// Unproject Web Mercator metres to a WGS84 longitude and latitude.
+///
+/// The inverse of [`to_web_mercator`].
+pub fn from_web_mercator(position: &Position) -> Position {
+    let &[x, y, ..] = position.as_slice() else {
+        return position.clone();
+    };
+    with_xy(
+        position,
+        (x / EARTH_RADIUS).to_degrees(),
+        (2.0 * (y / EARTH_RADIUS).exp().atan() - FRAC_PI_2).to_degrees(),
+    )
+}
+
+/// Project a WGS84 longitude and latitude to the pixels of a web map at `zoom`, made of square
+/// tiles `tile_size` pixels wide: usually 256 or 512.
+///
+/// The map is `tile_size * 2^zoom` pixels wide, with its origin at the top left, i.e. at 180°W
+/// and [`MAX_LATITUDE`]°N, and y increasing southwards. The pixel `[x, y]` is in the tile
+/// `[x / tile_size, y / tile_size]`, rounded down. Latitudes are clamped to ±[`MAX_LATITUDE`].
+pub fn to_pixels(position: &Position, zoom: f64, tile_size: f64) -> Position {
+    let &[x, y, ..] = to_web_mercator(position).as_slice() else {
+        return position.clone();
+    };
+    let size = tile_size * zoom.exp2();
+    with_xy(
+        position,
+        (1.0 + x / MAX_MERCATOR) / 2.0 * size,
+        (1.0 - y / MAX_MERCATOR) / 2.0 * size,
+    )
+}
+
+/// Unproject the pixels of a web map at `zoom` to a WGS84 longitude and latitude.
+///
+/// The inverse of [`to_pixels`].
+pub fn from_pixels(position: &Position, zoom: f64, tile_size: f64) -> Position {
+    let &[x, y, ..] = position.as_slice() else {
+        return position.clone();
+    };
+    let size = tile_size * zoom.exp2();
+    from_web_mercator(&with_xy(
+        position,
+        (2.0 * x / size - 1.0) * MAX_MERCATOR,
+        (1.0 - 2.0 * y / size) * MAX_MERCATOR,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Position, expected: impl Into<Position>, tolerance: f64) {
+        let expected = expected.into();
+        assert_eq!(actual.len(), expected.len(), "{actual:?} != {expected:?}");
+        for (a, b) in actual.as_slice().iter().zip(expected.as_slice()) {
+            assert!((a - b).abs() <= tolerance, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn web_mercator() {
+        assert_close(to_web_mercator(&[0.0, 0.0].into()), [0.0, 0.0], 1e-9);
+        assert_close(
+            to_web_mercator(&[180.0, MAX_LATITUDE].into()),
+            [MAX_MERCATOR, MAX_MERCATOR],
+            1e-6,
+        );
+        // Clamped
+        assert_eq!(
+            to_web_mercator(&[-180.0, -90.0].into()),
+            to_web_mercator(&[-180.0, -MAX_LATITUDE].into())
+        );
+        // A degree of longitude is a 360th of the equator, and y at 45°N is R * ln(tan(67.5°))
+        assert_close(
+            to_web_mercator(&[1.0, 45.0, 11.0].into()),
+            [111_319.490_793, 5_621_521.486_192, 11.0],
+            1e-6,
+        );
+        assert_close(
+            from_web_mercator(&[111_319.490_793, 5_621_521.486_192, 11.0].into()),
+            [1.0, 45.0, 11.0],
+            1e-9,
+        );
+        assert_eq!(
+            to_web_mercator(&vec![1.0].into()),
+            Position::from(vec![1.0])
+        );
+    }
+
+    #[test]
+    fn pixels() {
+        assert_close(
+            to_pixels(&[0.0, 0.0].into(), 0.0, 256.0),
+            [128.0, 128.0],
+            1e-9,
+        );
+        assert_close(
+            to_pixels(&[-180.0, 90.0].into(), 1.0, 256.0),
+            [0.0, 0.0],
+            1e-9,
+        );
+        assert_close(
+            to_pixels(&[180.0, -90.0].into(), 1.0, 512.0),
+            [1024.0, 1024.0],
+            1e-9,
+        );
+        // Fractional zooms
+        assert_close(
+            to_pixels(&[90.0, 0.0].into(), 1.5, 256.0),
+            [543.06, 362.04],
+            1e-2,
+        );
+
+        let position = Position::from([13.405, 52.52, 34.0]);
+        let pixels = to_pixels(&position, 12.0, 512.0);
+        assert_eq!(pixels[2], 34.0);
+        assert_close(from_pixels(&pixels, 12.0, 512.0), position, 1e-9);
+    }
+}
@@ -0,0 +1,1131 @@
+//! Inferring the types of feature properties.
+//!
+//! A [`Schema`] summarises the properties of a set of features: for each key, which
+//! [`JsonType`]s its values have, how often it's present or `null`, and a few example values.
+//!
+//! ```
+//! use geojson::FeatureCollection;
+//! use geojson::schema::JsonType;
+//!
+//! let collection: FeatureCollection = r#"{
+//!   "type": "FeatureCollection",
+//!   "features": [
+//!     { "type": "Feature", "geometry": null, "properties": { "name": "a", "rank": 1 } },
+//!     { "type": "Feature", "geometry": null, "properties": { "name": "b", "rank": "2" } },
+//!     { "type": "Feature", "geometry": null, "properties": { "name": null } }
+//!   ]
+//! }"#.parse().unwrap();
+//!
+//! let schema = collection.infer_schema();
+//! let name = schema.property("name").unwrap();
+//! assert_eq!(name.types().collect::<Vec<_>>(), [JsonType::String, JsonType::Null]);
+//! assert!(name.is_nullable());
+//! assert_eq!(schema.presence_ratio("rank"), 2.0 / 3.0);
+//! assert_eq!(schema.heterogeneous_keys().collect::<Vec<_>>(), ["rank"]);
+//! ```
+//!
+//! A [`PropertySchema`] says which types each property's values should have, and which properties
+//! are required. It can be built by hand, from a [`Schema`], from a
+//! [JSON Schema](https://json-schema.org), or from a Rust type. Check features against it with
+//! [`FeatureCollection::validate_properties`] or [`FeatureReader::validate_properties`].
+//!
+//! ```
+//! use geojson::FeatureCollection;
+//! use geojson::schema::{JsonType, PropertySchema};
+//!
+//! let collection: FeatureCollection = r#"{
+//!   "type": "FeatureCollection",
+//!   "features": [
+//!     { "type": "Feature", "id": "a", "geometry": null, "properties": { "name": 1 } }
+//!   ]
+//! }"#.parse().unwrap();
+//!
+//! let schema = PropertySchema::new()
+//!     .with_required("name", [JsonType::String])
+//!     .with_optional("rank", [JsonType::Integer, JsonType::Null]);
+//! let violations = collection.validate_properties(&schema);
+//! assert_eq!(
+//!     violations[0].to_string(),
+//!     r#"feature 0 (id "a"): property `name` should be string, but is integer"#
+//! );
+//! ```
+use crate::{Error, Feature, FeatureCollection, FeatureReader, JsonValue, Result, feature};
+
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::Read;
+
+mod trace;
+
+/// The type of a JSON value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum JsonType {
+    /// A number without a fractional part or exponent, which fits in an `i64` or `u64`
+    Integer,
+    /// Any other number
+    Float,
+    String,
+    Boolean,
+    Object,
+    Array,
+    Null,
+}
+
+impl JsonType {
+    /// The type of `value`.
+    pub fn of(value: &JsonValue) -> Self {
+        match value {
+            JsonValue::Null => JsonType::Null,
+            JsonValue::Bool(_) => JsonType::Boolean,
+            JsonValue::Number(number) if number.is_f64() => JsonType::Float,
+            JsonValue::Number(_) => JsonType::Integer,
+            JsonValue::String(_) => JsonType::String,
+            JsonValue::Array(_) => JsonType::Array,
+            JsonValue::Object(_) => JsonType::Object,
+        }
+    }
+
+    fn is_number(&self) -> bool {
+        matches!(self, JsonType::Integer | JsonType::Float)
+    }
+}
+
+impl fmt::Display for JsonType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            JsonType::Integer => "integer",
+            JsonType::Float => "float",
+            JsonType::String => "string",
+            JsonType::Boolean => "boolean",
+            JsonType::Object => "object",
+            JsonType::Array => "array",
+            JsonType::Null => "null",
+        })
+    }
+}
+
+/// What's been observed of the properties of a set of features.
+///
+/// Build one with [`FeatureCollection::infer_schema`] or [`FeatureReader::infer_schema`], or
+/// add features one at a time with [`Schema::add_feature`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schema {
+    feature_count: usize,
+    properties: BTreeMap<String, PropertyProfile>,
+    max_examples: usize,
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Self {
+            feature_count: 0,
+            properties: BTreeMap::new(),
+            max_examples: 3,
+        }
+    }
+}
+
+impl Schema {
+    /// A schema without any features.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The most distinct example values to keep for each key. Defaults to `3`.
+    pub fn with_max_examples(mut self, max_examples: usize) -> Self {
+        self.max_examples = max_examples;
+        self
+    }
+
+    /// Add the properties of `feature`.
+    pub fn add_feature(&mut self, feature: &Feature) {
+        self.feature_count += 1;
+        for (key, value) in feature.properties.iter().flatten() {
+            let property = self.properties.entry(key.clone()).or_default();
+            property.present_count += 1;
+            *property.type_counts.entry(JsonType::of(value)).or_default() += 1;
+            if !value.is_null()
+                && property.examples.len() < self.max_examples
+                && !property.examples.contains(value)
+            {
+                property.examples.push(value.clone());
+            }
+        }
+    }
+
+    /// The number of features added.
+    pub fn feature_count(&self) -> usize {
+        self.feature_count
+    }
+
+    /// The profile of the property `key`, if any feature had it.
+    pub fn property(&self, key: &str) -> Option<&PropertyProfile> {
+        self.properties.get(key)
+    }
+
+    /// Every key seen, in order.
+    pub fn properties(&self) -> impl Iterator<Item = (&str, &PropertyProfile)> {
+        self.properties
+            .iter()
+            .map(|(key, property)| (key.as_str(), property))
+    }
+
+    /// The fraction of features which have `key` among their properties, even if its value is
+    /// `null`.
+    pub fn presence_ratio(&self, key: &str) -> f64 {
+        match self.properties.get(key) {
+            Some(property) => property.present_count as f64 / self.feature_count as f64,
+            None => 0.0,
+        }
+    }
+
+    /// Whether `key` is missing from, or `null` in, any feature.
+    pub fn is_optional(&self, key: &str) -> bool {
+        self.properties.get(key).is_none_or(|property| {
+            property.present_count < self.feature_count || property.is_nullable()
+        })
+    }
+
+    /// The keys whose values are of more than one type.
+    ///
+    /// See [`PropertyProfile::is_heterogeneous`].
+    pub fn heterogeneous_keys(&self) -> impl Iterator<Item = &str> {
+        self.properties()
+            .filter(|(_, property)| property.is_heterogeneous())
+            .map(|(key, _)| key)
+    }
+}
+
+/// What's been observed of the values of one key of the properties of a set of features.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertyProfile {
+    present_count: usize,
+    type_counts: BTreeMap<JsonType, usize>,
+    examples: Vec<JsonValue>,
+}
+
+impl PropertyProfile {
+    /// The number of features which have this key, even if its value is `null`.
+    pub fn present_count(&self) -> usize {
+        self.present_count
+    }
+
+    /// The types of the values seen, in the order of [`JsonType`].
+    pub fn types(&self) -> impl Iterator<Item = JsonType> + '_ {
+        self.type_counts.keys().copied()
+    }
+
+    /// The number of values seen of type `json_type`.
+    pub fn count(&self, json_type: JsonType) -> usize {
+        self.type_counts.get(&json_type).copied().unwrap_or(0)
+    }
+
+    /// Whether any value was `null`.
+    pub fn is_nullable(&self) -> bool {
+        self.count(JsonType::Null) > 0
+    }
+
+    /// Whether values were of more than one type, besides `null`.
+    ///
+    /// Integers and floats are both numbers, so a mix of them isn't heterogeneous.
+    pub fn is_heterogeneous(&self) -> bool {
+        let mut types = self
+            .types()
+            .filter(|json_type| *json_type != JsonType::Null)
+            .map(|json_type| {
+                if json_type.is_number() {
+                    JsonType::Float
+                } else {
+                    json_type
+                }
+            });
+        types
+            .next()
+            .is_some_and(|first| types.any(|json_type| json_type != first))
+    }
+
+    /// Up to [`Schema::with_max_examples`] distinct non-`null` values, in the order they were
+    /// seen.
+    pub fn examples(&self) -> &[JsonValue] {
+        &self.examples
+    }
+}
+
+/// The types a property's values may have, and whether it's required.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyRule {
+    /// The types the values may have, or any type if empty. An integer is also a float.
+    pub types: Vec<JsonType>,
+    /// Whether every feature must have the property, even if its value is `null`
+    pub required: bool,
+}
+
+impl PropertyRule {
+    fn allows(&self, json_type: JsonType) -> bool {
+        self.types.is_empty()
+            || self.types.contains(&json_type)
+            || (json_type == JsonType::Integer && self.types.contains(&JsonType::Float))
+    }
+}
+
+/// The properties that features should have.
+///
+/// See the [`schema`](crate::schema) module for details.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropertySchema {
+    properties: BTreeMap<String, PropertyRule>,
+    additional_properties: bool,
+}
+
+impl PropertySchema {
+    /// A schema which allows any properties.
+    pub fn new() -> Self {
+        Self {
+            properties: BTreeMap::new(),
+            additional_properties: true,
+        }
+    }
+
+    /// Require every feature to have `key`, with a value of one of `types`, or of any type if
+    /// `types` is empty.
+    pub fn with_required(
+        mut self,
+        key: impl Into<String>,
+        types: impl IntoIterator<Item = JsonType>,
+    ) -> Self {
+        self.properties.insert(
+            key.into(),
+            PropertyRule {
+                types: types.into_iter().collect(),
+                required: true,
+            },
+        );
+        self
+    }
+
+    /// Allow features to have `key`, with a value of one of `types`, or of any type if `types`
+    /// is empty.
+    pub fn with_optional(
+        mut self,
+        key: impl Into<String>,
+        types: impl IntoIterator<Item = JsonType>,
+    ) -> Self {
+        self.properties.insert(
+            key.into(),
+            PropertyRule {
+                types: types.into_iter().collect(),
+                required: false,
+            },
+        );
+        self
+    }
+
+    /// Whether features may have properties besides those in the schema. Defaults to `true`.
+    pub fn with_additional_properties(mut self, additional_properties: bool) -> Self {
+        self.additional_properties = additional_properties;
+        self
+    }
+
+    /// The rule for the property `key`, if the schema has one.
+    pub fn property(&self, key: &str) -> Option<&PropertyRule> {
+        self.properties.get(key)
+    }
+
+    /// Describe the properties written by [`ser::to_feature_collection_string`](crate::ser::to_feature_collection_string)
+    /// for values of type `T`, i.e. all its fields besides `geometry`.
+    ///
+    /// The fields are discovered from `T`'s `Deserialize` implementation, so `T` must be a struct
+    /// with named fields. `Option` fields are optional and nullable; all other fields are
+    /// required. A field whose type can't be discovered, e.g. because its `Deserialize`
+    /// implementation rejects the placeholder values it's given, accepts any value.
+    ///
+    /// ```
+    /// use geojson::schema::{JsonType, PropertySchema};
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Airport {
+    ///     #[serde(deserialize_with = "geojson::de::deserialize_geometry")]
+    ///     geometry: geojson::Geometry,
+    ///     name: String,
+    ///     elevation: Option<f64>,
+    /// }
+    ///
+    /// let schema = PropertySchema::for_type::<Airport>().unwrap();
+    /// assert_eq!(
+    ///     schema,
+    ///     PropertySchema::new()
+    ///         .with_required("name", [JsonType::String])
+    ///         .with_optional("elevation", [JsonType::Null, JsonType::Float])
+    ///         .with_additional_properties(false)
+    /// );
+    /// ```
+    pub fn for_type<T: DeserializeOwned>() -> Result<Self> {
+        Ok(TypeDescription::of::<T>()?.properties)
+    }
+
+    /// Read a schema from a [JSON Schema](https://json-schema.org) for the `properties` of
+    /// features, such as one written by [`PropertySchema::to_json_schema`].
+    ///
+    /// Only the `type`, `properties`, `required` and `additionalProperties` keywords are
+    /// understood; any others are ignored.
+    pub fn from_json_schema(json_schema: &JsonValue) -> Result<Self> {
+        let invalid = |message: &str| Error::InvalidPropertySchema(message.to_string());
+        let Some(json_schema) = json_schema.as_object() else {
+            return Err(invalid("a JSON Schema must be an object"));
+        };
+        let mut schema = PropertySchema::new();
+        if let Some(additional_properties) = json_schema.get("additionalProperties") {
+            // A schema for the additional properties is as good as allowing them
+            schema.additional_properties = additional_properties != &JsonValue::Bool(false);
+        }
+        let required: Vec<&str> = match json_schema.get("required") {
+            None => vec![],
+            Some(required) => required
+                .as_array()
+                .and_then(|required| required.iter().map(JsonValue::as_str).collect())
+                .ok_or_else(|| invalid("`required` must be an array of strings"))?,
+        };
+        let properties = match json_schema.get("properties") {
+            None => &serde_json::Map::new(),
+            Some(properties) => properties
+                .as_object()
+                .ok_or_else(|| invalid("`properties` must be an object"))?,
+        };
+        for (key, property) in properties {
+            let types = match property.get("type") {
+                None => vec![],
+                Some(JsonValue::String(name)) => json_schema_types(name)
+                    .ok_or_else(|| invalid(&format!("unknown type `{name}`")))?,
+                Some(JsonValue::Array(names)) => {
+                    let mut types = vec![];
+                    for name in names {
+                        let name = name.as_str().unwrap_or_default();
+                        types.extend(
+                            json_schema_types(name)
+                                .ok_or_else(|| invalid(&format!("unknown type `{name}`")))?,
+                        );
+                    }
+                    types
+                }
+                Some(_) => return Err(invalid("`type` must be a string or an array of strings")),
+            };
+            schema.properties.insert(
+                key.clone(),
+                PropertyRule {
+                    types,
+                    required: required.contains(&key.as_str()),
+                },
+            );
+        }
+        for key in required {
+            schema
+                .properties
+                .entry(key.to_string())
+                .or_insert(PropertyRule {
+                    types: vec![],
+                    required: true,
+                });
+        }
+        Ok(schema)
+    }
+
+    /// Write the schema as a [JSON Schema](https://json-schema.org) for the `properties` of
+    /// features.
+    pub fn to_json_schema(&self) -> JsonValue {
+        let properties: serde_json::Map<String, JsonValue> = self
+            .properties
+            .iter()
+            .map(|(key, rule)| {
+                let mut names: Vec<&str> = vec![];
+                for json_type in &rule.types {
+                    let name = match json_type {
+                        JsonType::Integer if rule.types.contains(&JsonType::Float) => continue,
+                        JsonType::Integer => "integer",
+                        JsonType::Float => "number",
+                        JsonType::String => "string",
+                        JsonType::Boolean => "boolean",
+                        JsonType::Object => "object",
+                        JsonType::Array => "array",
+                        JsonType::Null => "null",
+                    };
+                    names.push(name);
+                }
+                let property = match names.as_slice() {
+                    [] => json!({}),
+                    [name] => json!({ "type": name }),
+                    names => json!({ "type": names }),
+                };
+                (key.clone(), property)
+            })
+            .collect();
+        let required: Vec<&String> = self
+            .properties
+            .iter()
+            .filter(|(_, rule)| rule.required)
+            .map(|(key, _)| key)
+            .collect();
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": self.additional_properties,
+        })
+    }
+
+    /// Check the properties of `feature`, the `index`th of its collection or stream.
+    pub fn check(&self, index: usize, feature: &Feature) -> Vec<PropertyViolation> {
+        let violation = |key: &str, kind| PropertyViolation {
+            index,
+            id: feature.id.clone(),
+            key: key.to_string(),
+            kind,
+        };
+        let mut violations = vec![];
+        for (key, rule) in &self.properties {
+            match feature.property(key) {
+                None if rule.required => {
+                    violations.push(violation(key, PropertyViolationKind::Missing))
+                }
+                None => {}
+                Some(value) => {
+                    let found = JsonType::of(value);
+                    if !rule.allows(found) {
+                        violations.push(violation(
+                            key,
+                            PropertyViolationKind::UnexpectedType {
+                                expected: rule.types.clone(),
+                                found,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+        if !self.additional_properties {
+            for (key, _) in feature.properties.iter().flatten() {
+                if !self.properties.contains_key(key) {
+                    violations.push(violation(key, PropertyViolationKind::Unexpected));
+                }
+            }
+        }
+        violations
+    }
+}
+
+impl From<&Schema> for PropertySchema {
+    /// Allow the types seen for each property, requiring those seen in every feature.
+    fn from(schema: &Schema) -> Self {
+        let mut property_schema = PropertySchema::new();
+        for (key, property) in schema.properties() {
+            property_schema.properties.insert(
+                key.to_string(),
+                PropertyRule {
+                    types: property.types().collect(),
+                    required: property.present_count() == schema.feature_count(),
+                },
+            );
+        }
+        property_schema
+    }
+}
+
+/// The JSON types of a JSON Schema type name.
+fn json_schema_types(name: &str) -> Option<Vec<JsonType>> {
+    Some(match name {
+        "integer" => vec![JsonType::Integer],
+        "number" => vec![JsonType::Integer, JsonType::Float],
+        "string" => vec![JsonType::String],
+        "boolean" => vec![JsonType::Boolean],
+        "object" => vec![JsonType::Object],
+        "array" => vec![JsonType::Array],
+        "null" => vec![JsonType::Null],
+        _ => return None,
+    })
+}
+
+/// How a feature's properties don't match a [`PropertySchema`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PropertyViolationKind {
+    /// A required property is missing.
+    Missing,
+    /// A property's value is of a type the schema doesn't allow.
+    UnexpectedType {
+        expected: Vec<JsonType>,
+        found: JsonType,
+    },
+    /// A property isn't in a schema which doesn't allow additional properties.
+    Unexpected,
+}
+
+/// A single property of a feature which doesn't match a [`PropertySchema`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyViolation {
+    /// The index of the feature in its collection or stream
+    pub index: usize,
+    /// The feature's `id`, if it has one
+    pub id: Option<feature::Id>,
+    /// The key of the property
+    pub key: String,
+    /// How the property doesn't match the schema
+    pub kind: PropertyViolationKind,
+}
+
+impl fmt::Display for PropertyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "feature {}", self.index)?;
+        match &self.id {
+            Some(feature::Id::String(id)) => write!(f, " (id {id:?})")?,
+            Some(feature::Id::Number(id)) => write!(f, " (id {id})")?,
+            None => {}
+        }
+        write!(f, ": property `{}` ", self.key)?;
+        match &self.kind {
+            PropertyViolationKind::Missing => f.write_str("is missing"),
+            PropertyViolationKind::UnexpectedType { expected, found } => {
+                let expected: Vec<String> = expected.iter().map(ToString::to_string).collect();
+                write!(f, "should be {}, but is {found}", expected.join(" or "))
+            }
+            PropertyViolationKind::Unexpected => f.write_str("isn't in the schema"),
+        }
+    }
+}
+
+/// Write a [JSON Schema](https://json-schema.org) describing the GeoJSON written by
+/// [`ser::to_feature_collection_string`](crate::ser::to_feature_collection_string) for values of
+/// type `T`.
+///
+/// The properties are described as by [`PropertySchema::for_type`], and the geometry by trying
+/// each type of geometry, and `null`, against `T`'s `geometry` field.
+///
+#[cfg_attr(feature = "geo-types", doc = "```")]
+#[cfg_attr(not(feature = "geo-types"), doc = "```ignore")]
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Airport {
+///     #[serde(
+///         serialize_with = "geojson::ser::serialize_geometry",
+///         deserialize_with = "geojson::de::deserialize_geometry"
+///     )]
+///     geometry: geo_types::Point,
+///     name: String,
+/// }
+///
+/// let json_schema = geojson::schema::feature_collection_json_schema::<Airport>().unwrap();
+/// let feature = &json_schema["properties"]["features"]["items"];
+/// assert_eq!(feature["properties"]["geometry"]["properties"]["type"]["const"], "Point");
+/// assert_eq!(feature["properties"]["properties"]["required"][0], "name");
+/// ```
+pub fn feature_collection_json_schema<T: DeserializeOwned>() -> Result<JsonValue> {
+    let description = TypeDescription::of::<T>()?;
+    Ok(json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "required": ["type", "features"],
+        "properties": {
+            "type": { "const": "FeatureCollection" },
+            "features": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["type", "geometry", "properties"],
+                    "properties": {
+                        "type": { "const": "Feature" },
+                        "id": { "type": ["string", "number"] },
+                        "geometry": description.geometry_json_schema(),
+                        "properties": description.properties.to_json_schema(),
+                    },
+                },
+            },
+        },
+    }))
+}
+
+/// What [`ser::to_feature_collection_string`](crate::ser::to_feature_collection_string) writes
+/// for a type.
+struct TypeDescription {
+    properties: PropertySchema,
+    /// The types of geometry its `geometry` field accepts
+    geometry_types: Vec<&'static str>,
+    nullable_geometry: bool,
+}
+
+/// A geometry of each type, to find which its `geometry` field accepts.
+const GEOMETRY_SAMPLES: [(&str, &str); 7] = [
+    ("Point", "[0, 0]"),
+    ("MultiPoint", "[[0, 0]]"),
+    ("LineString", "[[0, 0], [1, 1]]"),
+    ("MultiLineString", "[[[0, 0], [1, 1]]]"),
+    ("Polygon", "[[[0, 0], [1, 0], [1, 1], [0, 0]]]"),
+    ("MultiPolygon", "[[[[0, 0], [1, 0], [1, 1], [0, 0]]]]"),
+    ("GeometryCollection", ""),
+];
+
+impl TypeDescription {
+    fn of<T: DeserializeOwned>() -> Result<Self> {
+        let mut geometry_types = vec![];
+        let mut traced = None;
+        let mut last_error = None;
+        for (geometry_type, coordinates) in GEOMETRY_SAMPLES {
+            let sample = if geometry_type == "GeometryCollection" {
+                json!({ "type": geometry_type, "geometries": [] })
+            } else {
+                json!({
+                    "type": geometry_type,
+                    "coordinates": serde_json::from_str::<JsonValue>(coordinates)?,
+                })
+            };
+            match trace::trace::<T>(&sample) {
+                Ok(trace) => {
+                    geometry_types.push(geometry_type);
+                    traced = Some(trace);
+                }
+                Err(error) => last_error = Some(error),
+            }
+        }
+        let nullable_geometry = trace::trace::<T>(&JsonValue::Null).is_ok();
+        let Some(traced) = traced else {
+            let error = last_error
+                .map(|error| error.to_string())
+                .unwrap_or_default();
+            return Err(Error::InvalidPropertySchema(format!(
+                "can't describe the fields of `{}`: {error}",
+                std::any::type_name::<T>()
+            )));
+        };
+        if !traced.has_geometry {
+            geometry_types.clear();
+        }
+
+        let mut properties = PropertySchema::new().with_additional_properties(false);
+        for (key, field) in traced.fields {
+            let nullable = field.types.contains(&JsonType::Null);
+            properties.properties.insert(
+                key,
+                PropertyRule {
+                    types: if field.any { vec![] } else { field.types },
+                    required: !nullable,
+                },
+            );
+        }
+        Ok(Self {
+            properties,
+            geometry_types,
+            nullable_geometry: nullable_geometry || !traced.has_geometry,
+        })
+    }
+
+    fn geometry_json_schema(&self) -> JsonValue {
+        let position = json!({ "type": "array", "items": { "type": "number" }, "minItems": 2 });
+        let array = |items: JsonValue| json!({ "type": "array", "items": items });
+        let mut schemas: Vec<JsonValue> = self
+            .geometry_types
+            .iter()
+            .map(|geometry_type| {
+                let (key, value) = match *geometry_type {
+                    "Point" => ("coordinates", position.clone()),
+                    "MultiPoint" | "LineString" => ("coordinates", array(position.clone())),
+                    "MultiLineString" | "Polygon" => {
+                        ("coordinates", array(array(position.clone())))
+                    }
+                    "MultiPolygon" => ("coordinates", array(array(array(position.clone())))),
+                    _ => ("geometries", array(json!({ "type": "object" }))),
+                };
+                json!({
+                    "type": "object",
+                    "required": ["type", key],
+                    "properties": {
+                        "type": { "const": geometry_type },
+                        key: value,
+                    },
+                })
+            })
+            .collect();
+        if self.nullable_geometry {
+            schemas.push(json!({ "type": "null" }));
+        }
+        match schemas.len() {
+            1 => schemas.pop().unwrap(),
+            _ => json!({ "oneOf": schemas }),
+        }
+    }
+}
+
+impl FeatureCollection {
+    /// Infer the [`Schema`] of the features' properties.
+    pub fn infer_schema(&self) -> Schema {
+        let mut schema = Schema::new();
+        self.features
+            .iter()
+            .for_each(|feature| schema.add_feature(feature));
+        schema
+    }
+
+    /// Check the properties of every feature against `schema`, returning every
+    /// [`PropertyViolation`] found.
+    pub fn validate_properties(&self, schema: &PropertySchema) -> Vec<PropertyViolation> {
+        self.features
+            .iter()
+            .enumerate()
+            .flat_map(|(index, feature)| schema.check(index, feature))
+            .collect()
+    }
+}
+
+impl<R: Read> FeatureReader<R> {
+    /// Infer the [`Schema`] of the properties of the remaining features, reading one at a time.
+    ///
+    /// ```
+    /// use geojson::{FeatureReader, JsonValue};
+    ///
+    /// let input = r#"{
+    ///   "type": "FeatureCollection",
+    ///   "features": [
+    ///     { "type": "Feature", "geometry": null, "properties": { "rank": 1 } },
+    ///     { "type": "Feature", "geometry": null, "properties": { "rank": 2.5 } }
+    ///   ]
+    /// }"#;
+    /// let schema = FeatureReader::from_reader(input.as_bytes()).infer_schema().unwrap();
+    /// assert_eq!(schema.feature_count(), 2);
+    /// let rank = schema.property("rank").unwrap();
+    /// assert_eq!(rank.examples(), [JsonValue::from(1), JsonValue::from(2.5)]);
+    /// ```
+    pub fn infer_schema(&mut self) -> Result<Schema> {
+        let mut schema = Schema::new();
+        for feature in self.features() {
+            schema.add_feature(&feature?);
+        }
+        Ok(schema)
+    }
+
+    /// Check the properties of the remaining features against `schema`, reading one at a time,
+    /// and returning every [`PropertyViolation`] found.
+    ///
+    /// To keep the features as well, call [`PropertySchema::check`] on each of
+    /// [`FeatureReader::features`].
+    pub fn validate_properties(
+        &mut self,
+        schema: &PropertySchema,
+    ) -> Result<Vec<PropertyViolation>> {
+        let mut violations = vec![];
+        for (index, feature) in self.features().enumerate() {
+            violations.extend(schema.check(index, &feature?));
+        }
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature(properties: Option<JsonValue>) -> Feature {
+        Feature {
+            properties: properties.map(|properties| properties.as_object().unwrap().clone()),
+            ..Feature::default()
+        }
+    }
+
+    #[test]
+    fn infer_schema() {
+        let collection = FeatureCollection::from_iter([
+            feature(Some(json!({
+                "int": 1, "float": 1.5, "number": 1, "bool": true, "list": [1], "object": {}
+            }))),
+            feature(Some(
+                json!({ "int": 1, "number": 2.5, "bool": "yes", "null": null }),
+            )),
+            feature(None),
+            feature(Some(json!({ "int": 3, "number": -3, "list": [] }))),
+            feature(Some(json!({ "int": 4, "number": 4 }))),
+        ]);
+        let schema = collection.infer_schema();
+        assert_eq!(schema.feature_count(), 5);
+        assert_eq!(
+            schema.properties().map(|(key, _)| key).collect::<Vec<_>>(),
+            ["bool", "float", "int", "list", "null", "number", "object"]
+        );
+
+        let int = schema.property("int").unwrap();
+        assert_eq!(int.types().collect::<Vec<_>>(), [JsonType::Integer]);
+        assert_eq!(int.present_count(), 4);
+        assert_eq!(int.count(JsonType::Integer), 4);
+        assert_eq!(int.examples(), [json!(1), json!(3), json!(4)]);
+        assert!(!int.is_nullable());
+        assert!(schema.is_optional("int"));
+        assert_eq!(schema.presence_ratio("int"), 0.8);
+
+        let number = schema.property("number").unwrap();
+        assert_eq!(
+            number.types().collect::<Vec<_>>(),
+            [JsonType::Integer, JsonType::Float]
+        );
+        assert!(!number.is_heterogeneous());
+
+        let null = schema.property("null").unwrap();
+        assert_eq!(null.types().collect::<Vec<_>>(), [JsonType::Null]);
+        assert!(null.is_nullable());
+        assert!(null.examples().is_empty());
+        assert!(!null.is_heterogeneous());
+
+        assert_eq!(schema.heterogeneous_keys().collect::<Vec<_>>(), ["bool"]);
+        assert_eq!(schema.presence_ratio("missing"), 0.0);
+        assert!(schema.is_optional("missing"));
+    }
+
+    #[test]
+    fn max_examples() {
+        let mut schema = Schema::new().with_max_examples(1);
+        schema.add_feature(&feature(Some(json!({ "a": 1 }))));
+        schema.add_feature(&feature(Some(json!({ "a": 2 }))));
+        assert_eq!(schema.property("a").unwrap().examples(), [json!(1)]);
+        assert!(!schema.is_optional("a"));
+    }
+
+    #[test]
+    fn validate_properties() {
+        let collection = FeatureCollection::from_iter([
+            feature(Some(json!({ "name": "a", "rank": 1 }))),
+            Feature {
+                id: Some(feature::Id::Number(7.into())),
+                ..feature(Some(json!({ "rank": 1.5, "extra": true })))
+            },
+            Feature {
+                id: Some(feature::Id::String("c".to_string())),
+                ..feature(Some(json!({ "name": null, "rank": "3" })))
+            },
+        ]);
+        let schema = PropertySchema::new()
+            .with_required("name", [JsonType::String, JsonType::Null])
+            .with_optional("rank", [JsonType::Float])
+            .with_additional_properties(false);
+        let violations = collection.validate_properties(&schema);
+        let messages: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "feature 1 (id 7): property `name` is missing",
+                "feature 1 (id 7): property `extra` isn't in the schema",
+                r#"feature 2 (id "c"): property `rank` should be float, but is string"#,
+            ]
+        );
+        assert_eq!(violations[0].index, 1);
+        assert_eq!(violations[0].kind, PropertyViolationKind::Missing);
+
+        // Inferred from the collection itself, so it must match
+        let inferred = PropertySchema::from(&collection.infer_schema());
+        assert!(!inferred.property("name").unwrap().required);
+        assert!(collection.validate_properties(&inferred).is_empty());
+
+        let json = serde_json::to_string(&collection).unwrap();
+        let mut reader = FeatureReader::from_reader(json.as_bytes());
+        assert_eq!(reader.validate_properties(&schema).unwrap(), violations);
+    }
+
+    #[test]
+    fn json_schema() {
+        let schema = PropertySchema::new()
+            .with_required("name", [JsonType::String])
+            .with_required("rank", [JsonType::Integer, JsonType::Float, JsonType::Null])
+            .with_optional("count", [JsonType::Integer])
+            .with_optional("anything", []);
+        let json_schema = schema.to_json_schema();
+        assert_eq!(
+            json_schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "anything": {},
+                    "count": { "type": "integer" },
+                    "name": { "type": "string" },
+                    "rank": { "type": ["number", "null"] },
+                },
+                "required": ["name", "rank"],
+                "additionalProperties": true,
+            })
+        );
+        let parsed = PropertySchema::from_json_schema(&json_schema).unwrap();
+        assert_eq!(
+            parsed.property("rank").unwrap().types,
+            [JsonType::Integer, JsonType::Float, JsonType::Null]
+        );
+        assert_eq!(parsed.to_json_schema(), json_schema);
+
+        for invalid in [
+            json!([]),
+            json!({ "required": "name" }),
+            json!({ "properties": { "a": { "type": "date" } } }),
+        ] {
+            assert!(matches!(
+                PropertySchema::from_json_schema(&invalid),
+                Err(Error::InvalidPropertySchema(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn for_type() {
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        enum Kind {
+            Small,
+            Large,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        enum Shape {
+            Unknown,
+            Circle(f64),
+        }
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Nested {
+            a: u8,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Place {
+            name: String,
+            #[serde(rename = "pop")]
+            population: Option<u32>,
+            area: f32,
+            open: bool,
+            tags: Vec<String>,
+            nested: Nested,
+            kind: Kind,
+            shape: Shape,
+            extra: JsonValue,
+        }
+
+        let schema = PropertySchema::for_type::<Place>().unwrap();
+        assert_eq!(
+            schema.to_json_schema(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "area": { "type": "number" },
+                    "extra": {},
+                    "kind": { "type": "string" },
+                    "name": { "type": "string" },
+                    "nested": { "type": "object" },
+                    "open": { "type": "boolean" },
+                    "pop": { "type": ["null", "integer"] },
+                    "shape": { "type": ["string", "object"] },
+                    "tags": { "type": "array" },
+                },
+                "required": ["area", "extra", "kind", "name", "nested", "open", "shape", "tags"],
+                "additionalProperties": false,
+            })
+        );
+        // Without a `geometry` field, features have null geometries
+        let json_schema = feature_collection_json_schema::<Place>().unwrap();
+        let feature = &json_schema["properties"]["features"]["items"];
+        assert_eq!(feature["properties"]["geometry"], json!({ "type": "null" }));
+        assert_eq!(feature["properties"]["properties"], schema.to_json_schema());
+
+        assert!(matches!(
+            PropertySchema::for_type::<Vec<u8>>(),
+            Err(Error::InvalidPropertySchema(_))
+        ));
+    }
+
+    #[test]
+    fn for_type_recursive() {
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Node {
+            name: String,
+            parent: Option<Box<Node>>,
+        }
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        enum Expression {
+            Not(Box<Expression>),
+            Literal(bool),
+        }
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Rule {
+            expression: Expression,
+            priority: u8,
+        }
+
+        assert_eq!(
+            PropertySchema::for_type::<Node>().unwrap(),
+            PropertySchema::new()
+                .with_required("name", [JsonType::String])
+                .with_optional("parent", [JsonType::Null, JsonType::Object])
+                .with_additional_properties(false)
+        );
+        // Without an `Option` to end the recursion, the field can't be described
+        assert_eq!(
+            PropertySchema::for_type::<Rule>().unwrap(),
+            PropertySchema::new()
+                .with_required("expression", [])
+                .with_required("priority", [JsonType::Integer])
+                .with_additional_properties(false)
+        );
+    }
+
+    #[test]
+    fn for_type_validating_fields() {
+        fn even<'de, D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> std::result::Result<u32, D::Error> {
+            let value = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+            if value % 2 != 0 {
+                return Err(serde::de::Error::custom("expected an even number"));
+            }
+            Ok(value)
+        }
+
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Team {
+            #[serde(deserialize_with = "even")]
+            players: u32,
+            name: String,
+            id: std::num::NonZeroU32,
+            #[serde(deserialize_with = "even")]
+            substitutes: u32,
+        }
+
+        assert_eq!(
+            PropertySchema::for_type::<Team>().unwrap(),
+            PropertySchema::new()
+                .with_required("id", [JsonType::Integer])
+                .with_required("name", [JsonType::String])
+                .with_required("players", [])
+                .with_required("substitutes", [])
+                .with_additional_properties(false)
+        );
+    }
+
+    #[cfg(feature = "geo-types")]
+    #[test]
+    fn feature_collection_json_schema_geometry() {
+        #[derive(serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Road {
+            #[serde(deserialize_with = "crate::de::deserialize_optional_geometry")]
+            geometry: Option<geo_types::LineString>,
+        }
+
+        let json_schema = feature_collection_json_schema::<Road>().unwrap();
+        let geometry = &json_schema["properties"]["features"]["items"]["properties"]["geometry"];
+        let types: Vec<&JsonValue> = geometry["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|schema| &schema["properties"]["type"]["const"])
+            .collect();
+        assert_eq!(types, [&json!("LineString"), &JsonValue::Null]);
+        assert_eq!(geometry["oneOf"][1], json!({ "type": "null" }));
+    }
+}
@@ -0,0 +1,479 @@
+//! Discovering the fields of a type from its `Deserialize` implementation, by deserializing it from
+//! a [`Deserializer`] which records what's asked of it and answers with placeholder values.
+//!
+//! A field whose `Deserialize` implementation rejects the placeholder values, or which nests too
+//! deeply, is moved to the end and the type is traced again. If it fails there too, the field is
+//! recorded as accepting any value.
+//!
+//! Only one variant of an enum can be deserialized at a time, so the type is traced once for each
+//! variant of its enum fields, and the types of each field are combined.
+use super::JsonType;
+use crate::JsonValue;
+
+use serde::de::value::StrDeserializer;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde_json::Error;
+
+use std::collections::BTreeMap;
+
+/// The types a field was deserialized as.
+#[derive(Debug, Default)]
+pub(super) struct FieldTypes {
+    pub types: Vec<JsonType>,
+    /// Whether the field accepts any JSON value, e.g. a `serde_json::Value`
+    pub any: bool,
+    /// The number of variants, if the field is an enum
+    variants: usize,
+}
+
+impl FieldTypes {
+    fn record(&mut self, json_type: JsonType) {
+        if !self.types.contains(&json_type) {
+            self.types.push(json_type);
+        }
+    }
+
+    fn merge(&mut self, other: FieldTypes) {
+        for json_type in other.types {
+            self.record(json_type);
+        }
+        self.any |= other.any;
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Trace {
+    /// Every field but `geometry`, by name
+    pub fields: BTreeMap<String, FieldTypes>,
+    pub has_geometry: bool,
+    /// The field being deserialized, which is the one at fault if deserializing fails
+    current: Option<&'static str>,
+}
+
+/// How deeply values may be nested within a field, e.g. a struct which contains itself through an
+/// `Option`. Any deeper `Option`s are `None`.
+const MAX_DEPTH: usize = 16;
+
+/// Trace the fields of `T`, deserializing its `geometry` field, if any, from `geometry`.
+pub(super) fn trace<T: DeserializeOwned>(geometry: &JsonValue) -> Result<Trace, Error> {
+    let mut trace = trace_variant::<T>(geometry, 0)?;
+    let variants = trace.fields.values().map(|types| types.variants).max();
+    for variant in 1..variants.unwrap_or(0) {
+        for (field, types) in trace_variant::<T>(geometry, variant)?.fields {
+            trace.fields.entry(field).or_default().merge(types);
+        }
+    }
+    Ok(trace)
+}
+
+/// Trace the fields of `T`, deserializing the `variant`th variant of each enum field, or its last
+/// if it has fewer.
+fn trace_variant<T: DeserializeOwned>(
+    geometry: &JsonValue,
+    variant: usize,
+) -> Result<Trace, Error> {
+    let mut deferred = vec![];
+    loop {
+        let mut trace = Trace::default();
+        let result = T::deserialize(TopLevel {
+            geometry,
+            deferred: &deferred,
+            variant,
+            trace: &mut trace,
+        });
+        let Err(error) = result else {
+            return Ok(trace);
+        };
+        match trace.current {
+            None | Some("geometry") => return Err(error),
+            // Every other field has been traced
+            Some(field) if deferred.contains(&field) => {
+                for field in deferred {
+                    let types = trace.fields.entry(field.to_string()).or_default();
+                    types.types.clear();
+                    types.any = true;
+                }
+                trace.current = None;
+                return Ok(trace);
+            }
+            Some(field) => deferred.push(field),
+        }
+    }
+}
+
+struct TopLevel<'a> {
+    geometry: &'a JsonValue,
+    /// Fields to deserialize after all the others
+    deferred: &'a [&'static str],
+    /// The variant to deserialize of enum fields
+    variant: usize,
+    trace: &'a mut Trace,
+}
+
+impl<'de> Deserializer<'de> for TopLevel<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Error> {
+        Err(de::Error::custom(
+            "only structs with named fields, and without flattened fields, can be described",
+        ))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let (deferred, fields): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .partition(|field| self.deferred.contains(field));
+        visitor.visit_map(Fields {
+            fields: fields.into_iter().chain(deferred).collect(),
+            index: 0,
+            depth: 0,
+            top_level: Some((self.geometry, self.variant, self.trace)),
+        })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier
+        ignored_any
+    }
+}
+
+/// The fields of a struct, keyed by name.
+struct Fields<'a> {
+    fields: Vec<&'static str>,
+    index: usize,
+    /// The depth of the struct
+    depth: usize,
+    /// For the type being traced, rather than a nested struct: its geometry, the variant of enum
+    /// fields to deserialize, and the trace
+    top_level: Option<(&'a JsonValue, usize, &'a mut Trace)>,
+}
+
+impl<'de> MapAccess<'de> for Fields<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.fields.get(self.index) {
+            Some(field) => seed.deserialize(StrDeserializer::new(field)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let field = self.fields[self.index];
+        self.index += 1;
+        match &mut self.top_level {
+            Some((geometry, variant, trace)) => {
+                trace.current = Some(field);
+                let value = if field == "geometry" {
+                    trace.has_geometry = true;
+                    seed.deserialize(JsonValue::clone(geometry))?
+                } else {
+                    let types = trace.fields.entry(field.to_string()).or_default();
+                    seed.deserialize(Values {
+                        types: Some(types),
+                        depth: 0,
+                        variant: *variant,
+                    })?
+                };
+                trace.current = None;
+                Ok(value)
+            }
+            None => seed.deserialize(Values::nested(self.depth)?),
+        }
+    }
+}
+
+/// Forward `deserialize_*` methods to those for a wider type, e.g. `i8` to `i64`.
+macro_rules! forward_values {
+    ($($($method:ident)+ => $target:ident,)+) => {
+        $($(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.$target(visitor)
+            }
+        )+)+
+    };
+}
+
+/// Answers with placeholder values, recording their types if `types` is `Some`.
+///
+/// Numbers are 1 rather than 0, for the sake of types like `NonZeroU32`.
+struct Values<'a> {
+    types: Option<&'a mut FieldTypes>,
+    /// How deeply nested within a field this is
+    depth: usize,
+    /// The variant to deserialize if this is an enum
+    variant: usize,
+}
+
+impl Values<'_> {
+    /// The values of something within a value at `depth`, whose types aren't recorded.
+    fn nested(depth: usize) -> Result<Values<'static>, Error> {
+        if depth >= MAX_DEPTH {
+            return Err(de::Error::custom("the type is nested too deeply"));
+        }
+        Ok(Values {
+            types: None,
+            depth: depth + 1,
+            variant: 0,
+        })
+    }
+
+    fn record(&mut self, json_type: JsonType) {
+        if let Some(types) = &mut self.types {
+            types.record(json_type);
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for Values<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        if let Some(types) = &mut self.types {
+            types.any = true;
+        }
+        visitor.visit_unit()
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Boolean);
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Integer);
+        visitor.visit_i64(1)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Integer);
+        visitor.visit_u64(1)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Float);
+        visitor.visit_f64(1.0)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::String);
+        visitor.visit_str("")
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::String);
+        visitor.visit_char(' ')
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        // serde_json writes bytes as an array of numbers
+        self.record(JsonType::Array);
+        visitor.visit_bytes(&[])
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Null);
+        if self.depth >= MAX_DEPTH {
+            return visitor.visit_none();
+        }
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Null);
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Array);
+        visitor.visit_seq(Elements {
+            remaining: 0,
+            depth: self.depth,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        mut self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.record(JsonType::Array);
+        visitor.visit_seq(Elements {
+            remaining: len,
+            depth: self.depth,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Object);
+        visitor.visit_map(Fields {
+            fields: vec![],
+            index: 0,
+            depth: self.depth,
+            top_level: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.record(JsonType::Object);
+        visitor.visit_map(Fields {
+            fields: fields.to_vec(),
+            index: 0,
+            depth: self.depth,
+            top_level: None,
+        })
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let Some(variant) = variants.get(self.variant).or(variants.last()) else {
+            return Err(de::Error::custom(
+                "an enum without variants can't be described",
+            ));
+        };
+        if let Some(types) = &mut self.types {
+            types.variants = variants.len();
+        }
+        visitor.visit_enum(Variant {
+            name: variant,
+            values: self,
+        })
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_values! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i128 => deserialize_i64,
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u128 => deserialize_u64,
+        deserialize_f32 => deserialize_f64,
+        deserialize_string deserialize_identifier => deserialize_str,
+        deserialize_byte_buf => deserialize_bytes,
+    }
+}
+
+/// The placeholder elements of a sequence or tuple, whose types aren't recorded.
+struct Elements {
+    remaining: usize,
+    /// The depth of the sequence
+    depth: usize,
+}
+
+impl<'de> SeqAccess<'de> for Elements {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        self.remaining -= 1;
+        seed.deserialize(Values::nested(self.depth)?).map(Some)
+    }
+}
+
+/// A variant of an enum, which serde_json writes as a string if it's a unit variant, or
+/// an object otherwise.
+struct Variant<'a> {
+    name: &'static str,
+    values: Values<'a>,
+}
+
+impl<'de, 'a> EnumAccess<'de> for Variant<'a> {
+    type Error = Error;
+    type Variant = Values<'a>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Values<'a>), Error> {
+        let variant = seed.deserialize(StrDeserializer::<Error>::new(self.name))?;
+        Ok((variant, self.values))
+    }
+}
+
+impl<'de> VariantAccess<'de> for Values<'_> {
+    type Error = Error;
+
+    fn unit_variant(mut self) -> Result<(), Error> {
+        self.record(JsonType::String);
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(mut self, seed: T) -> Result<T::Value, Error> {
+        self.record(JsonType::Object);
+        seed.deserialize(Values::nested(self.depth)?)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(mut self, len: usize, visitor: V) -> Result<V::Value, Error> {
+        self.record(JsonType::Object);
+        visitor.visit_seq(Elements {
+            remaining: len,
+            depth: self.depth,
+        })
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        mut self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.record(JsonType::Object);
+        visitor.visit_map(Fields {
+            fields: fields.to_vec(),
+            index: 0,
+            depth: self.depth,
+            top_level: None,
+        })
+    }
+}
@@ -96,7 +96,7 @@
 //!     ...
 //! }
 //! ```
-use crate::{Feature, JsonObject, JsonValue, Result};
+use crate::{Feature, FeatureWriter, JsonObject, JsonValue, Result, WriteOptions};
 
 use serde::{Serialize, Serializer, ser::Error};
 
@@ -270,6 +270,160 @@ where
     Ok(())
 }
 
+/// Serialize a single data structure to a GeoJSON Feature string, applying the given
+/// [`WriteOptions`], e.g. to limit the precision of coordinates.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Examples
+#[cfg_attr(feature = "geo-types", doc = "```")]
+#[cfg_attr(not(feature = "geo-types"), doc = "```ignore")]
+/// use serde::Serialize;
+/// use geojson::{Precision, WriteOptions};
+/// use geojson::ser::{serialize_geometry, to_feature_string_with_options};
+///
+/// #[derive(Serialize)]
+/// struct MyStruct {
+///     #[serde(serialize_with = "serialize_geometry")]
+///     geometry: geo_types::Point,
+/// }
+///
+/// let my_struct = MyStruct {
+///     geometry: geo_types::Point::new(1.23456789, 2.0),
+/// };
+///
+/// let options = WriteOptions::default().with_precision(Precision::DecimalPlaces(2));
+/// assert_eq!(
+///     to_feature_string_with_options(&my_struct, &options).unwrap(),
+///     r#"{"type":"Feature","geometry":{"type":"Point","coordinates":[1.23,2.0]},"properties":{}}"#
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_string_with_options<T>(value: &T, options: &WriteOptions) -> Result<String>
+where
+    T: Serialize,
+{
+    let vec = to_feature_byte_vec_with_options(value, options)?;
+    let string = unsafe {
+        // We do not emit invalid UTF-8.
+        String::from_utf8_unchecked(vec)
+    };
+    Ok(string)
+}
+
+/// Serialize elements to a GeoJSON FeatureCollection string, applying the given
+/// [`WriteOptions`], e.g. to limit the precision of coordinates.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_collection_string_with_options<T>(
+    values: &[T],
+    options: &WriteOptions,
+) -> Result<String>
+where
+    T: Serialize,
+{
+    let vec = to_feature_collection_byte_vec_with_options(values, options)?;
+    let string = unsafe {
+        // We do not emit invalid UTF-8.
+        String::from_utf8_unchecked(vec)
+    };
+    Ok(string)
+}
+
+/// Serialize a single data structure to a GeoJSON Feature byte vector, applying the given
+/// [`WriteOptions`], e.g. to limit the precision of coordinates.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_byte_vec_with_options<T>(value: &T, options: &WriteOptions) -> Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    let mut writer = Vec::with_capacity(128);
+    to_feature_writer_with_options(&mut writer, value, options)?;
+    Ok(writer)
+}
+
+/// Serialize elements to a GeoJSON FeatureCollection byte vector, applying the given
+/// [`WriteOptions`], e.g. to limit the precision of coordinates.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_collection_byte_vec_with_options<T>(
+    values: &[T],
+    options: &WriteOptions,
+) -> Result<Vec<u8>>
+where
+    T: Serialize,
+{
+    let mut writer = Vec::with_capacity(128);
+    to_feature_collection_writer_with_options(&mut writer, values, options)?;
+    Ok(writer)
+}
+
+/// Serialize a single data structure as a GeoJSON Feature into the IO stream, applying the given
+/// [`WriteOptions`], e.g. to limit the precision of coordinates.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_writer_with_options<W, T>(
+    writer: W,
+    value: &T,
+    options: &WriteOptions,
+) -> Result<()>
+where
+    W: io::Write,
+    T: Serialize,
+{
+    options.to_writer(writer, &to_feature(value)?)
+}
+
+/// Serialize elements as a GeoJSON FeatureCollection into the IO stream, applying the given
+/// [`WriteOptions`], e.g. to limit the precision of coordinates.
+///
+/// Note that `T` must have a column called `geometry`.
+///
+/// # Errors
+///
+/// Serialization can fail if `T`'s implementation of `Serialize` decides to
+/// fail, or if `T` contains a map with non-string keys.
+pub fn to_feature_collection_writer_with_options<W, T>(
+    writer: W,
+    features: &[T],
+    options: &WriteOptions,
+) -> Result<()>
+where
+    W: io::Write,
+    T: Serialize,
+{
+    let mut writer = FeatureWriter::from_writer(writer).with_options(options.clone());
+    for feature in features {
+        writer.serialize(feature)?;
+    }
+    writer.finish()
+}
+
 /// [`serde::serialize_with`](https://serde.rs/field-attrs.html#serialize_with) helper to serialize a type like a
 /// [`geo_types`], as a GeoJSON Geometry.
 ///
@@ -514,6 +668,60 @@ mod tests {
         assert_eq!(actual_output_json, expected_output_json);
     }
 
+    #[test]
+    fn with_options() {
+        #[derive(Serialize)]
+        struct MyStruct {
+            geometry: crate::Geometry,
+            name: String,
+        }
+
+        let my_structs = [
+            MyStruct {
+                geometry: crate::Geometry::new_point([0.123456, 1.987654]),
+                name: "burbs".to_string(),
+            },
+            MyStruct {
+                geometry: crate::Geometry::new_point([-5.55555, 5.0]),
+                name: "town".to_string(),
+            },
+        ];
+        let options = WriteOptions::default().with_precision(crate::Precision::DecimalPlaces(3));
+
+        let actual_output = to_feature_string_with_options(&my_structs[0], &options).unwrap();
+        let actual_output_json = JsonValue::from_str(&actual_output).unwrap();
+        assert_eq!(
+            actual_output_json,
+            json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [0.123, 1.988] },
+                "properties": { "name": "burbs" }
+            })
+        );
+
+        let actual_output =
+            to_feature_collection_string_with_options(&my_structs, &options).unwrap();
+        let actual_output_json = JsonValue::from_str(&actual_output).unwrap();
+        assert_eq!(
+            actual_output_json,
+            json!({
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "type": "Feature",
+                        "geometry": { "type": "Point", "coordinates": [0.123, 1.988] },
+                        "properties": { "name": "burbs" }
+                    },
+                    {
+                        "type": "Feature",
+                        "geometry": { "type": "Point", "coordinates": [-5.556, 5.0] },
+                        "properties": { "name": "town" }
+                    }
+                ]
+            })
+        );
+    }
+
     mod optional_geometry {
         use super::*;
         #[derive(Serialize)]
@@ -0,0 +1,1347 @@
+//! [TopoJSON](https://github.com/topojson/topojson-specification) topologies.
+//!
+//! TopoJSON stores the lines of every geometry once, as shared "arcs", which geometries refer to
+//! by index. This makes it much more compact than GeoJSON for datasets whose polygons share
+//! borders, such as [world-atlas](https://github.com/topojson/world-atlas).
+//!
+//! A [`Topology`] can be parsed with serde like the other GeoJSON types, and each of its named
+//! objects materialized as a [`FeatureCollection`] with [`Topology::to_feature_collection`].
+//! In the other direction, [`Topology::from_feature_collections`] builds a topology, which
+//! [`Topology::quantize`] can make smaller still.
+//!
+//! # Examples
+//!
+//! ```
+//! use geojson::topojson::Topology;
+//! use geojson::{Geometry, feature::Id};
+//!
+//! let topology: Topology = r#"{
+//!     "type": "Topology",
+//!     "objects": {
+//!         "regions": {
+//!             "type": "GeometryCollection",
+//!             "geometries": [
+//!                 { "type": "Polygon", "id": "west", "arcs": [[0, 1]] },
+//!                 { "type": "Polygon", "id": "east", "arcs": [[2, -1]] }
+//!             ]
+//!         }
+//!     },
+//!     "arcs": [
+//!         [[1, 0], [1, 1]],
+//!         [[1, 1], [0, 1], [0, 0], [1, 0]],
+//!         [[1, 0], [2, 0], [2, 1], [1, 1]]
+//!     ]
+//! }"#
+//! .parse()
+//! .unwrap();
+//!
+//! let regions = topology
+//!     .to_feature_collection(&topology.objects["regions"])
+//!     .unwrap();
+//! assert_eq!(regions.features.len(), 2);
+//! assert_eq!(regions.features[0].id, Some(Id::String("west".to_string())));
+//! // The arc between the two regions is shared, and reversed for the east region
+//! assert_eq!(
+//!     regions.features[1].geometry,
+//!     Some(Geometry::new_polygon([[
+//!         [1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0], [1.0, 0.0]
+//!     ]]))
+//! );
+//! ```
+use crate::errors::{Error, Result};
+use crate::{
+    Bbox, Feature, FeatureCollection, Geometry, GeometryValue, JsonObject, JsonValue, Position,
+    feature,
+};
+
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+use tinyvec::TinyVec;
+
+/// Topology Object
+///
+/// [TopoJSON Format Specification § 2.1](https://github.com/topojson/topojson-specification#21-topology-objects)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", from = "deserialize::DeserializeTopologyHelper")]
+pub struct Topology {
+    /// Bounding Box
+    ///
+    /// [TopoJSON Format Specification § 2.3](https://github.com/topojson/topojson-specification#23-bounding-boxes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<Bbox>,
+    /// The transform of a quantized topology, whose positions are integers.
+    ///
+    /// [TopoJSON Format Specification § 2.1.2](https://github.com/topojson/topojson-specification#212-transforms)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform: Option<Transform>,
+    /// The named geometries of the topology.
+    pub objects: BTreeMap<String, TopoGeometry>,
+    /// The lines shared by the topology's geometries. If the topology is quantized, every
+    /// position of an arc after its first is relative to the position before it.
+    ///
+    /// [TopoJSON Format Specification § 2.1.3](https://github.com/topojson/topojson-specification#213-arcs)
+    #[serde(serialize_with = "serialize_arcs")]
+    pub arcs: Vec<Vec<Position>>,
+    /// Foreign Members
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub foreign_members: Option<JsonObject>,
+}
+
+/// Converts the quantized positions of a topology back to their original coordinates.
+///
+/// [TopoJSON Format Specification § 2.1.2](https://github.com/topojson/topojson-specification#212-transforms)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Transform {
+    /// The size of one quantized unit, in each of the first two dimensions.
+    pub scale: [f64; 2],
+    /// The original coordinates of the quantized position `[0, 0]`.
+    pub translate: [f64; 2],
+}
+
+impl Transform {
+    /// Convert a quantized position to its original coordinates. Any elements beyond the second
+    /// are left as they are.
+    pub fn apply(&self, position: &Position) -> Position {
+        let mut position = position.clone();
+        for (index, element) in position.as_slice_mut().iter_mut().take(2).enumerate() {
+            *element = *element * self.scale[index] + self.translate[index];
+        }
+        position
+    }
+}
+
+/// Geometry Object of a [`Topology`]
+///
+/// Unlike a GeoJSON [`Geometry`], a TopoJSON geometry may have an `id` and `properties`, and
+/// lines refer to the topology's arcs rather than containing their positions.
+///
+/// [TopoJSON Format Specification § 2.2](https://github.com/topojson/topojson-specification#22-geometry-objects)
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(try_from = "deserialize::RawTopoGeometry")]
+pub struct TopoGeometry {
+    /// The geometry, or `None` for a geometry whose `type` is `null`.
+    pub value: Option<TopoGeometryValue>,
+    /// Identifier, which becomes the `id` of the GeoJSON [`Feature`].
+    pub id: Option<feature::Id>,
+    /// Properties, which become the `properties` of the GeoJSON [`Feature`].
+    pub properties: Option<JsonObject>,
+    /// Bounding Box
+    ///
+    /// [TopoJSON Format Specification § 2.3](https://github.com/topojson/topojson-specification#23-bounding-boxes)
+    pub bbox: Option<Bbox>,
+    /// Foreign Members
+    pub foreign_members: Option<JsonObject>,
+}
+
+/// The type and coordinates of a [`TopoGeometry`].
+///
+/// Each arc index refers to an arc of the [`Topology`]. A negative index `i` refers to the arc
+/// `!i` (i.e. `-i - 1`), reversed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TopoGeometryValue {
+    Point { coordinates: Position },
+    MultiPoint { coordinates: Vec<Position> },
+    LineString { arcs: Vec<i32> },
+    MultiLineString { arcs: Vec<Vec<i32>> },
+    Polygon { arcs: Vec<Vec<i32>> },
+    MultiPolygon { arcs: Vec<Vec<Vec<i32>>> },
+    GeometryCollection { geometries: Vec<TopoGeometry> },
+}
+
+impl TopoGeometryValue {
+    /// The TopoJSON `type` of this geometry, e.g. `"LineString"`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            TopoGeometryValue::Point { .. } => "Point",
+            TopoGeometryValue::MultiPoint { .. } => "MultiPoint",
+            TopoGeometryValue::LineString { .. } => "LineString",
+            TopoGeometryValue::MultiLineString { .. } => "MultiLineString",
+            TopoGeometryValue::Polygon { .. } => "Polygon",
+            TopoGeometryValue::MultiPolygon { .. } => "MultiPolygon",
+            TopoGeometryValue::GeometryCollection { .. } => "GeometryCollection",
+        }
+    }
+}
+
+impl TopoGeometry {
+    /// Returns a new `TopoGeometry` with the specified `value`, and no `id`, `properties`,
+    /// `bbox` or foreign members.
+    pub fn new(value: TopoGeometryValue) -> Self {
+        Self {
+            value: Some(value),
+            ..Default::default()
+        }
+    }
+}
+
+impl Serialize for TopoGeometry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match &self.value {
+            None => map.serialize_entry("type", &JsonValue::Null)?,
+            Some(value) => {
+                map.serialize_entry("type", value.type_name())?;
+                match value {
+                    TopoGeometryValue::Point { coordinates } => {
+                        map.serialize_entry("coordinates", &CompactPosition(coordinates))?
+                    }
+                    TopoGeometryValue::MultiPoint { coordinates } => {
+                        map.serialize_entry("coordinates", &CompactLine(coordinates))?
+                    }
+                    TopoGeometryValue::LineString { arcs } => map.serialize_entry("arcs", arcs)?,
+                    TopoGeometryValue::MultiLineString { arcs }
+                    | TopoGeometryValue::Polygon { arcs } => map.serialize_entry("arcs", arcs)?,
+                    TopoGeometryValue::MultiPolygon { arcs } => {
+                        map.serialize_entry("arcs", arcs)?
+                    }
+                    TopoGeometryValue::GeometryCollection { geometries } => {
+                        map.serialize_entry("geometries", geometries)?
+                    }
+                }
+            }
+        }
+        if let Some(id) = &self.id {
+            map.serialize_entry("id", id)?;
+        }
+        if let Some(properties) = &self.properties {
+            map.serialize_entry("properties", properties)?;
+        }
+        if let Some(bbox) = &self.bbox {
+            map.serialize_entry("bbox", bbox)?;
+        }
+        for (key, value) in self.foreign_members.iter().flatten() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// Writes integral elements without a fractional part, e.g. `[4000, 0]` rather than
+/// `[4000.0, 0.0]`, which matters for the size of a quantized topology.
+struct CompactPosition<'a>(&'a Position);
+
+impl Serialize for CompactPosition<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        // Larger integers can't be represented exactly
+        const MAX_EXACT: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for &element in self.0.as_slice() {
+            if element.fract() == 0.0 && element.abs() <= MAX_EXACT {
+                seq.serialize_element(&(element as i64))?;
+            } else {
+                seq.serialize_element(&element)?;
+            }
+        }
+        seq.end()
+    }
+}
+
+struct CompactLine<'a>(&'a [Position]);
+
+impl Serialize for CompactLine<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(CompactPosition))
+    }
+}
+
+fn serialize_arcs<S: Serializer>(
+    arcs: &[Vec<Position>],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(arcs.iter().map(|arc| CompactLine(arc)))
+}
+
+impl FromStr for Topology {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+}
+
+impl Topology {
+    /// Build a topology with a single `GeometryCollection` object named `name`, holding the
+    /// features of `collection`.
+    ///
+    /// See [`Topology::from_feature_collections`].
+    pub fn from_feature_collection(
+        name: impl Into<String>,
+        collection: &FeatureCollection,
+    ) -> Result<Self> {
+        Self::from_feature_collections([(name, collection)])
+    }
+
+    /// Build a topology with a `GeometryCollection` object for each named [`FeatureCollection`].
+    ///
+    /// Each [`Feature`] becomes a geometry with the feature's `id`, `properties`, `bbox` and
+    /// foreign members. The lines of every `LineString` and `Polygon` are cut wherever they meet
+    /// another line, and each resulting arc is stored only once, however many geometries share
+    /// it. For example, the border between two adjacent polygons becomes a single arc. Rings may
+    /// be rotated to start where they're cut, but are otherwise unchanged.
+    ///
+    /// The topology's `bbox` is set to the two-dimensional bounds of every position. Its arcs
+    /// aren't quantized; see [`Topology::quantize`].
+    ///
+    /// # Errors
+    ///
+    /// Geometries refer to arcs by `i32` indexes, so more than `i32::MAX` distinct arcs is an
+    /// [`Error::TopoJsonTooManyArcs`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::topojson::Topology;
+    /// use geojson::{Feature, FeatureCollection, Geometry};
+    ///
+    /// let collection = FeatureCollection::from_iter([
+    ///     Feature::from(Geometry::new_polygon([[
+    ///         [0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]
+    ///     ]])),
+    ///     Feature::from(Geometry::new_polygon([[
+    ///         [1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0], [1.0, 0.0]
+    ///     ]])),
+    /// ]);
+    /// let mut topology = Topology::from_feature_collections([("squares", &collection)]).unwrap();
+    ///
+    /// // The edge between the squares is shared
+    /// assert_eq!(topology.arcs.len(), 3);
+    ///
+    /// topology.quantize(1_000);
+    /// let json = serde_json::to_string(&topology).unwrap();
+    /// assert!(json.contains(r#""arcs":[[[500,0],[0,999]]"#));
+    /// ```
+    pub fn from_feature_collections<'a, N: Into<String>>(
+        collections: impl IntoIterator<Item = (N, &'a FeatureCollection)>,
+    ) -> Result<Self> {
+        let collections: Vec<_> = collections.into_iter().collect();
+        let mut encoder = Encoder::default();
+        for (_, collection) in &collections {
+            for geometry in collection
+                .features
+                .iter()
+                .flat_map(|feature| &feature.geometry)
+            {
+                encoder.find_junctions(&geometry.value);
+            }
+        }
+        let objects = collections
+            .into_iter()
+            .map(|(name, collection)| Ok((name.into(), encoder.object(collection)?)))
+            .collect::<Result<_>>()?;
+        let mut topology = Self {
+            bbox: None,
+            transform: None,
+            objects,
+            arcs: encoder.arcs,
+            foreign_members: None,
+        };
+        topology.bbox = topology.extent().map(Vec::from);
+        Ok(topology)
+    }
+
+    /// Quantize the topology's positions to integers between `0` and `quantization - 1` in each
+    /// dimension, and delta-encode its arcs, replacing any previous quantization.
+    ///
+    /// Quantization makes a topology much smaller, at the cost of precision: positions are
+    /// rounded to a grid of `quantization × quantization` cells over the topology's extent, and
+    /// consecutive positions of an arc which round to the same cell are merged. A quantization
+    /// of `1e4` or `1e5` is typical. Any elements of a position beyond the second are left as
+    /// they are.
+    ///
+    /// Also sets the topology's `bbox` to the extent of its positions. A `quantization` less than
+    /// 2 is treated as 2.
+    pub fn quantize(&mut self, quantization: u32) {
+        let quantization = quantization.max(2);
+        self.arcs = self.decode_arcs();
+        if let Some(transform) = self.transform.take() {
+            for object in self.objects.values_mut() {
+                for_each_point_mut(object, &mut |point| *point = transform.apply(point));
+            }
+        }
+        let Some([min_x, min_y, max_x, max_y]) = self.extent() else {
+            return;
+        };
+        let scale = |min: f64, max: f64| {
+            if max > min {
+                (max - min) / f64::from(quantization - 1)
+            } else {
+                1.0
+            }
+        };
+        let transform = Transform {
+            scale: [scale(min_x, max_x), scale(min_y, max_y)],
+            translate: [min_x, min_y],
+        };
+        let quantize = |position: &Position| {
+            let mut position = position.clone();
+            for (index, element) in position.as_slice_mut().iter_mut().take(2).enumerate() {
+                *element =
+                    ((*element - transform.translate[index]) / transform.scale[index]).round();
+            }
+            position
+        };
+
+        for object in self.objects.values_mut() {
+            for_each_point_mut(object, &mut |point| *point = quantize(point));
+        }
+        for arc in &mut self.arcs {
+            let mut previous: Option<Position> = None;
+            let mut deltas = Vec::with_capacity(arc.len());
+            for position in arc.iter().map(quantize) {
+                let mut delta = position.clone();
+                if let Some(previous) = &previous {
+                    if position.as_slice().get(..2) == previous.as_slice().get(..2) {
+                        continue;
+                    }
+                    for (index, element) in delta.as_slice_mut().iter_mut().take(2).enumerate() {
+                        *element -= previous[index];
+                    }
+                }
+                deltas.push(delta);
+                previous = Some(position);
+            }
+            // An arc needs at least two positions, even if they're the same
+            if deltas.len() == 1 && arc.len() > 1 {
+                let mut delta = arc[arc.len() - 1].clone();
+                for element in delta.as_slice_mut().iter_mut().take(2) {
+                    *element = 0.0;
+                }
+                deltas.push(delta);
+            }
+            *arc = deltas;
+        }
+        self.bbox = Some(vec![min_x, min_y, max_x, max_y]);
+        self.transform = Some(transform);
+    }
+
+    /// The two-dimensional extent of the topology's arcs and points, in their original
+    /// coordinates.
+    fn extent(&self) -> Option<[f64; 4]> {
+        let mut extent: Option<[f64; 4]> = None;
+        let mut extend = |position: &Position| {
+            let &[x, y, ..] = position.as_slice() else {
+                return;
+            };
+            let [min_x, min_y, max_x, max_y] = extent.get_or_insert([x, y, x, y]);
+            *min_x = min_x.min(x);
+            *min_y = min_y.min(y);
+            *max_x = max_x.max(x);
+            *max_y = max_y.max(y);
+        };
+        match &self.transform {
+            None => self.arcs.iter().flatten().for_each(&mut extend),
+            Some(_) => self.decode_arcs().iter().flatten().for_each(&mut extend),
+        }
+        for object in self.objects.values() {
+            for_each_point(object, &mut |point| match &self.transform {
+                Some(transform) => extend(&transform.apply(point)),
+                None => extend(point),
+            });
+        }
+        extent
+    }
+
+    /// Materialize one of the topology's [`objects`](Topology::objects) as a
+    /// [`FeatureCollection`].
+    ///
+    /// Each geometry of a `GeometryCollection` object becomes a [`Feature`] with the geometry's
+    /// `id`, `properties`, `bbox` and foreign members, while any other object becomes a single
+    /// feature. Nested `GeometryCollection`s become GeoJSON `GeometryCollection`s.
+    ///
+    /// Returns [`Error::TopoJsonArcOutOfBounds`] if a geometry refers to an arc which doesn't
+    /// exist.
+    pub fn to_feature_collection(&self, object: &TopoGeometry) -> Result<FeatureCollection> {
+        Decoder::new(self).feature_collection(object)
+    }
+
+    /// Materialize every one of the topology's [`objects`](Topology::objects) as a
+    /// [`FeatureCollection`], by name.
+    ///
+    /// See [`Topology::to_feature_collection`].
+    pub fn to_feature_collections(&self) -> Result<BTreeMap<String, FeatureCollection>> {
+        let decoder = Decoder::new(self);
+        self.objects
+            .iter()
+            .map(|(name, object)| Ok((name.clone(), decoder.feature_collection(object)?)))
+            .collect()
+    }
+
+    /// The arcs with their original coordinates, undoing any quantization.
+    fn decode_arcs(&self) -> Vec<Vec<Position>> {
+        let Some(transform) = &self.transform else {
+            return self.arcs.clone();
+        };
+        self.arcs
+            .iter()
+            .map(|arc| {
+                // Undo the delta encoding, then the quantization
+                let mut previous = [0.0, 0.0];
+                arc.iter()
+                    .map(|position| {
+                        let mut position = position.clone();
+                        for (index, element) in
+                            position.as_slice_mut().iter_mut().take(2).enumerate()
+                        {
+                            previous[index] += *element;
+                            *element = previous[index];
+                        }
+                        transform.apply(&position)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Converts TopoJSON geometries to GeoJSON, with the topology's arcs decoded up front.
+struct Decoder<'a> {
+    arcs: Vec<Vec<Position>>,
+    transform: Option<&'a Transform>,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(topology: &'a Topology) -> Self {
+        Self {
+            arcs: topology.decode_arcs(),
+            transform: topology.transform.as_ref(),
+        }
+    }
+
+    fn feature_collection(&self, object: &TopoGeometry) -> Result<FeatureCollection> {
+        Ok(match &object.value {
+            Some(TopoGeometryValue::GeometryCollection { geometries }) => FeatureCollection {
+                bbox: object.bbox.clone(),
+                features: geometries
+                    .iter()
+                    .map(|geometry| self.feature(geometry))
+                    .collect::<Result<_>>()?,
+                foreign_members: object.foreign_members.clone(),
+            },
+            _ => FeatureCollection::new([self.feature(object)?]),
+        })
+    }
+
+    fn feature(&self, geometry: &TopoGeometry) -> Result<Feature> {
+        Ok(Feature {
+            bbox: geometry.bbox.clone(),
+            geometry: geometry
+                .value
+                .as_ref()
+                .map(|value| Ok::<_, Error>(Geometry::new(self.geometry_value(value)?)))
+                .transpose()?,
+            id: geometry.id.clone(),
+            properties: geometry.properties.clone(),
+            foreign_members: geometry.foreign_members.clone(),
+        })
+    }
+
+    fn geometry_value(&self, value: &TopoGeometryValue) -> Result<GeometryValue> {
+        Ok(match value {
+            TopoGeometryValue::Point { coordinates } => GeometryValue::Point {
+                coordinates: self.point(coordinates),
+            },
+            TopoGeometryValue::MultiPoint { coordinates } => GeometryValue::MultiPoint {
+                coordinates: coordinates.iter().map(|point| self.point(point)).collect(),
+            },
+            TopoGeometryValue::LineString { arcs } => GeometryValue::LineString {
+                coordinates: self.line(arcs)?,
+            },
+            TopoGeometryValue::MultiLineString { arcs } => GeometryValue::MultiLineString {
+                coordinates: self.lines(arcs)?,
+            },
+            TopoGeometryValue::Polygon { arcs } => GeometryValue::Polygon {
+                coordinates: self.lines(arcs)?,
+            },
+            TopoGeometryValue::MultiPolygon { arcs } => GeometryValue::MultiPolygon {
+                coordinates: arcs
+                    .iter()
+                    .map(|polygon| self.lines(polygon))
+                    .collect::<Result<_>>()?,
+            },
+            TopoGeometryValue::GeometryCollection { geometries } => {
+                GeometryValue::GeometryCollection {
+                    geometries: geometries
+                        .iter()
+                        .filter_map(|geometry| {
+                            let value = self.geometry_value(geometry.value.as_ref()?);
+                            Some(value.map(|value| Geometry {
+                                bbox: geometry.bbox.clone(),
+                                value,
+                                foreign_members: geometry.foreign_members.clone(),
+                            }))
+                        })
+                        .collect::<Result<_>>()?,
+                }
+            }
+        })
+    }
+
+    fn point(&self, position: &Position) -> Position {
+        match self.transform {
+            Some(transform) => transform.apply(position),
+            None => position.clone(),
+        }
+    }
+
+    fn lines(&self, lines: &[Vec<i32>]) -> Result<Vec<Vec<Position>>> {
+        lines.iter().map(|arcs| self.line(arcs)).collect()
+    }
+
+    /// Join the arcs of a line, each of which starts where the previous one ended.
+    fn line(&self, arcs: &[i32]) -> Result<Vec<Position>> {
+        let mut line: Vec<Position> = vec![];
+        for &index in arcs {
+            let arc = usize::try_from(if index < 0 { !index } else { index })
+                .ok()
+                .and_then(|arc| self.arcs.get(arc))
+                .ok_or(Error::TopoJsonArcOutOfBounds(index))?;
+            // Don't repeat the shared position
+            line.pop();
+            if index < 0 {
+                line.extend(arc.iter().rev().cloned());
+            } else {
+                line.extend(arc.iter().cloned());
+            }
+        }
+        Ok(line)
+    }
+}
+
+/// Converts GeoJSON features to TopoJSON, cutting their lines into deduplicated arcs.
+#[derive(Default)]
+struct Encoder {
+    /// The neighbours of each position the first time it was seen within a line
+    neighbours: HashMap<PositionKey, [PositionKey; 2]>,
+    /// Positions where lines must be cut, because they meet another line
+    junctions: HashSet<PositionKey>,
+    arcs: Vec<Vec<Position>>,
+    /// The index of each arc, by the keys of its positions
+    arc_indexes: HashMap<Vec<u64>, i32>,
+}
+
+/// The exact bits of a position's elements.
+type PositionKey = TinyVec<[u64; 3]>;
+
+fn position_key(position: &Position) -> PositionKey {
+    // Adding zero turns -0.0 into 0.0
+    position
+        .as_slice()
+        .iter()
+        .map(|element| (element + 0.0).to_bits())
+        .collect()
+}
+
+/// A ring's positions without the closing position, if it has one.
+fn open_ring(ring: &[Position]) -> &[Position] {
+    match ring {
+        [first, .., last] if position_key(first) == position_key(last) => &ring[..ring.len() - 1],
+        _ => ring,
+    }
+}
+
+impl Encoder {
+    /// The first pass: find where the lines of `value` meet the lines seen so far.
+    fn find_junctions(&mut self, value: &GeometryValue) {
+        match value {
+            GeometryValue::Point { .. } | GeometryValue::MultiPoint { .. } => {}
+            GeometryValue::LineString { coordinates } => self.find_line_junctions(coordinates),
+            GeometryValue::MultiLineString { coordinates } => coordinates
+                .iter()
+                .for_each(|line| self.find_line_junctions(line)),
+            GeometryValue::Polygon { coordinates } => coordinates
+                .iter()
+                .for_each(|ring| self.find_ring_junctions(ring)),
+            GeometryValue::MultiPolygon { coordinates } => coordinates
+                .iter()
+                .flatten()
+                .for_each(|ring| self.find_ring_junctions(ring)),
+            GeometryValue::GeometryCollection { geometries } => geometries
+                .iter()
+                .for_each(|geometry| self.find_junctions(&geometry.value)),
+        }
+    }
+
+    fn find_line_junctions(&mut self, line: &[Position]) {
+        // Lines are always cut at their ends
+        if let (Some(first), Some(last)) = (line.first(), line.last()) {
+            self.junctions.insert(position_key(first));
+            self.junctions.insert(position_key(last));
+        }
+        for window in line.windows(3) {
+            self.visit(&window[1], &window[0], &window[2]);
+        }
+    }
+
+    fn find_ring_junctions(&mut self, ring: &[Position]) {
+        let ring = open_ring(ring);
+        for index in 0..ring.len() {
+            let previous = &ring[(index + ring.len() - 1) % ring.len()];
+            let next = &ring[(index + 1) % ring.len()];
+            self.visit(&ring[index], previous, next);
+        }
+    }
+
+    /// A position is a junction if it's seen with different neighbours, in either direction.
+    fn visit(&mut self, position: &Position, previous: &Position, next: &Position) {
+        let neighbours = [position_key(previous), position_key(next)];
+        match self.neighbours.entry(position_key(position)) {
+            Entry::Vacant(entry) => {
+                entry.insert(neighbours);
+            }
+            Entry::Occupied(entry) => {
+                let [previous, next] = neighbours;
+                let seen = entry.get();
+                if !(seen[0] == previous && seen[1] == next
+                    || seen[0] == next && seen[1] == previous)
+                {
+                    self.junctions.insert(entry.key().clone());
+                }
+            }
+        }
+    }
+
+    /// The second pass: convert each feature of `collection`, now every junction is known.
+    fn object(&mut self, collection: &FeatureCollection) -> Result<TopoGeometry> {
+        Ok(TopoGeometry {
+            value: Some(TopoGeometryValue::GeometryCollection {
+                geometries: collection
+                    .features
+                    .iter()
+                    .map(|feature| {
+                        Ok(TopoGeometry {
+                            value: feature
+                                .geometry
+                                .as_ref()
+                                .map(|geometry| self.geometry_value(&geometry.value))
+                                .transpose()?,
+                            id: feature.id.clone(),
+                            properties: feature.properties.clone(),
+                            bbox: feature.bbox.clone(),
+                            foreign_members: feature.foreign_members.clone(),
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+            }),
+            bbox: collection.bbox.clone(),
+            foreign_members: collection.foreign_members.clone(),
+            ..Default::default()
+        })
+    }
+
+    fn geometry_value(&mut self, value: &GeometryValue) -> Result<TopoGeometryValue> {
+        Ok(match value {
+            GeometryValue::Point { coordinates } => TopoGeometryValue::Point {
+                coordinates: coordinates.clone(),
+            },
+            GeometryValue::MultiPoint { coordinates } => TopoGeometryValue::MultiPoint {
+                coordinates: coordinates.clone(),
+            },
+            GeometryValue::LineString { coordinates } => TopoGeometryValue::LineString {
+                arcs: self.line(coordinates)?,
+            },
+            GeometryValue::MultiLineString { coordinates } => TopoGeometryValue::MultiLineString {
+                arcs: coordinates
+                    .iter()
+                    .map(|line| self.line(line))
+                    .collect::<Result<_>>()?,
+            },
+            GeometryValue::Polygon { coordinates } => TopoGeometryValue::Polygon {
+                arcs: self.polygon(coordinates)?,
+            },
+            GeometryValue::MultiPolygon { coordinates } => TopoGeometryValue::MultiPolygon {
+                arcs: coordinates
+                    .iter()
+                    .map(|polygon| self.polygon(polygon))
+                    .collect::<Result<_>>()?,
+            },
+            GeometryValue::GeometryCollection { geometries } => {
+                TopoGeometryValue::GeometryCollection {
+                    geometries: geometries
+                        .iter()
+                        .map(|geometry| {
+                            Ok(TopoGeometry {
+                                value: Some(self.geometry_value(&geometry.value)?),
+                                bbox: geometry.bbox.clone(),
+                                foreign_members: geometry.foreign_members.clone(),
+                                ..Default::default()
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                }
+            }
+        })
+    }
+
+    fn polygon(&mut self, rings: &[Vec<Position>]) -> Result<Vec<Vec<i32>>> {
+        rings.iter().map(|ring| self.ring(ring)).collect()
+    }
+
+    fn ring(&mut self, ring: &[Position]) -> Result<Vec<i32>> {
+        let ring = open_ring(ring);
+        if ring.is_empty() {
+            return Ok(vec![]);
+        }
+        // Start at a junction, so the ring is cut there. Without any, start at the least
+        // position, so that identical rings share an arc whichever position they start at.
+        let start = ring
+            .iter()
+            .position(|position| self.junctions.contains(&position_key(position)))
+            .unwrap_or_else(|| {
+                (0..ring.len())
+                    .min_by_key(|&index| position_key(&ring[index]))
+                    .unwrap_or_default()
+            });
+        let mut rotated = Vec::with_capacity(ring.len() + 1);
+        rotated.extend_from_slice(&ring[start..]);
+        rotated.extend_from_slice(&ring[..=start]);
+        self.line(&rotated)
+    }
+
+    /// Cut `line` at every junction, and find or add each resulting arc.
+    fn line(&mut self, line: &[Position]) -> Result<Vec<i32>> {
+        if line.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut arcs = vec![];
+        let mut start = 0;
+        for index in 1..line.len().saturating_sub(1) {
+            if self.junctions.contains(&position_key(&line[index])) {
+                arcs.push(self.arc(&line[start..=index])?);
+                start = index;
+            }
+        }
+        arcs.push(self.arc(&line[start..])?);
+        Ok(arcs)
+    }
+
+    fn arc(&mut self, positions: &[Position]) -> Result<i32> {
+        let key = |positions: &mut dyn Iterator<Item = &Position>| -> Vec<u64> {
+            positions
+                .flat_map(|position| {
+                    // Include the length, so positions of different lengths can't run together
+                    std::iter::once(position.len() as u64).chain(position_key(position))
+                })
+                .collect()
+        };
+        let forward = key(&mut positions.iter());
+        if let Some(&index) = self.arc_indexes.get(&forward) {
+            return Ok(index);
+        }
+        if let Some(&index) = self.arc_indexes.get(&key(&mut positions.iter().rev())) {
+            return Ok(!index);
+        }
+        let index = i32::try_from(self.arcs.len()).map_err(|_| Error::TopoJsonTooManyArcs)?;
+        self.arcs.push(positions.to_vec());
+        self.arc_indexes.insert(forward, index);
+        Ok(index)
+    }
+}
+
+/// Call `f` with the position of every `Point` and `MultiPoint` of `geometry`.
+fn for_each_point(geometry: &TopoGeometry, f: &mut impl FnMut(&Position)) {
+    match &geometry.value {
+        Some(TopoGeometryValue::Point { coordinates }) => f(coordinates),
+        Some(TopoGeometryValue::MultiPoint { coordinates }) => coordinates.iter().for_each(f),
+        Some(TopoGeometryValue::GeometryCollection { geometries }) => geometries
+            .iter()
+            .for_each(|geometry| for_each_point(geometry, f)),
+        _ => {}
+    }
+}
+
+/// Call `f` with the position of every `Point` and `MultiPoint` of `geometry`.
+fn for_each_point_mut(geometry: &mut TopoGeometry, f: &mut impl FnMut(&mut Position)) {
+    match &mut geometry.value {
+        Some(TopoGeometryValue::Point { coordinates }) => f(coordinates),
+        Some(TopoGeometryValue::MultiPoint { coordinates }) => coordinates.iter_mut().for_each(f),
+        Some(TopoGeometryValue::GeometryCollection { geometries }) => geometries
+            .iter_mut()
+            .for_each(|geometry| for_each_point_mut(geometry, f)),
+        _ => {}
+    }
+}
+
+pub(crate) mod deserialize {
+    use super::*;
+    use crate::util::normalize_foreign_members;
+    use serde::de::{DeserializeOwned, Error as _};
+
+    /// The purpose of this helper is to verify that `"type": "Topology"` during
+    /// deserialization, as with [`Feature`].
+    #[derive(Deserialize)]
+    pub(crate) struct DeserializeTopologyHelper {
+        #[allow(unused)]
+        r#type: TopologyType,
+        bbox: Option<Bbox>,
+        transform: Option<Transform>,
+        objects: BTreeMap<String, TopoGeometry>,
+        arcs: Vec<Vec<Position>>,
+        #[serde(flatten)]
+        foreign_members: Option<JsonObject>,
+    }
+
+    #[derive(Deserialize)]
+    enum TopologyType {
+        Topology,
+    }
+
+    impl From<DeserializeTopologyHelper> for Topology {
+        fn from(mut value: DeserializeTopologyHelper) -> Self {
+            normalize_foreign_members(&mut value.foreign_members);
+            Self {
+                bbox: value.bbox,
+                transform: value.transform,
+                objects: value.objects,
+                arcs: value.arcs,
+                foreign_members: value.foreign_members,
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, Deserialize)]
+    pub(crate) enum TopoGeometryType {
+        Point,
+        MultiPoint,
+        LineString,
+        MultiLineString,
+        Polygon,
+        MultiPolygon,
+        GeometryCollection,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(expecting = "TopoJSON geometry object")]
+    pub(crate) struct RawTopoGeometry {
+        r#type: Option<TopoGeometryType>,
+        #[serde(default)]
+        coordinates: Option<JsonValue>,
+        #[serde(default)]
+        arcs: Option<JsonValue>,
+        #[serde(default)]
+        geometries: Option<Vec<TopoGeometry>>,
+        id: Option<feature::Id>,
+        properties: Option<JsonObject>,
+        bbox: Option<Bbox>,
+        #[serde(flatten)]
+        foreign_members: Option<JsonObject>,
+    }
+
+    impl TryFrom<RawTopoGeometry> for TopoGeometry {
+        type Error = Error;
+
+        fn try_from(mut raw: RawTopoGeometry) -> Result<Self> {
+            fn member<T: DeserializeOwned>(
+                geometry_type: TopoGeometryType,
+                key: &str,
+                value: Option<JsonValue>,
+            ) -> Result<T> {
+                let value = value.ok_or_else(|| {
+                    serde_json::Error::custom(format!(
+                        "expected `{key}` for TopoJSON {geometry_type:?}"
+                    ))
+                })?;
+                Ok(serde_json::from_value(value)?)
+            }
+
+            let value = match raw.r#type {
+                None => None,
+                Some(t @ TopoGeometryType::Point) => Some(TopoGeometryValue::Point {
+                    coordinates: member(t, "coordinates", raw.coordinates)?,
+                }),
+                Some(t @ TopoGeometryType::MultiPoint) => Some(TopoGeometryValue::MultiPoint {
+                    coordinates: member(t, "coordinates", raw.coordinates)?,
+                }),
+                Some(t @ TopoGeometryType::LineString) => Some(TopoGeometryValue::LineString {
+                    arcs: member(t, "arcs", raw.arcs)?,
+                }),
+                Some(t @ TopoGeometryType::MultiLineString) => {
+                    Some(TopoGeometryValue::MultiLineString {
+                        arcs: member(t, "arcs", raw.arcs)?,
+                    })
+                }
+                Some(t @ TopoGeometryType::Polygon) => Some(TopoGeometryValue::Polygon {
+                    arcs: member(t, "arcs", raw.arcs)?,
+                }),
+                Some(t @ TopoGeometryType::MultiPolygon) => Some(TopoGeometryValue::MultiPolygon {
+                    arcs: member(t, "arcs", raw.arcs)?,
+                }),
+                Some(TopoGeometryType::GeometryCollection) => {
+                    Some(TopoGeometryValue::GeometryCollection {
+                        geometries: raw.geometries.ok_or_else(|| {
+                            serde_json::Error::custom(
+                                "expected `geometries` for TopoJSON GeometryCollection",
+                            )
+                        })?,
+                    })
+                }
+            };
+            normalize_foreign_members(&mut raw.foreign_members);
+            Ok(TopoGeometry {
+                value,
+                id: raw.id,
+                properties: raw.properties,
+                bbox: raw.bbox,
+                foreign_members: raw.foreign_members,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// The example from the TopoJSON specification, with a simpler transform.
+    fn quantized() -> Topology {
+        serde_json::from_value(json!({
+            "type": "Topology",
+            "transform": { "scale": [0.5, 0.25], "translate": [100, 0] },
+            "objects": {
+                "example": {
+                    "type": "GeometryCollection",
+                    "geometries": [
+                        {
+                            "type": "Point",
+                            "properties": { "prop0": "value0" },
+                            "coordinates": [4, 4]
+                        },
+                        {
+                            "type": "LineString",
+                            "properties": { "prop0": "value0", "prop1": 0 },
+                            "arcs": [0]
+                        },
+                        {
+                            "type": "Polygon",
+                            "id": 3,
+                            "properties": { "prop0": "value0", "prop1": { "this": "that" } },
+                            "arcs": [[-2]]
+                        },
+                        { "type": null, "id": "nothing" }
+                    ]
+                }
+            },
+            "arcs": [
+                [[4, 0], [2, 4], [2, -4], [2, 4]],
+                [[0, 0], [0, 4], [4, 0], [0, -4], [-4, 0]]
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn decode_quantized() {
+        let collection = quantized()
+            .to_feature_collection(&quantized().objects["example"])
+            .unwrap();
+        let geometries: Vec<_> = collection
+            .features
+            .iter()
+            .map(|feature| feature.geometry.clone())
+            .collect();
+        assert_eq!(
+            geometries,
+            [
+                Some(Geometry::new_point([102.0, 1.0])),
+                Some(Geometry::new_line_string([
+                    [102.0, 0.0],
+                    [103.0, 1.0],
+                    [104.0, 0.0],
+                    [105.0, 1.0]
+                ])),
+                // Reversed
+                Some(Geometry::new_polygon([[
+                    [100.0, 0.0],
+                    [102.0, 0.0],
+                    [102.0, 1.0],
+                    [100.0, 1.0],
+                    [100.0, 0.0]
+                ]])),
+                None,
+            ]
+        );
+        assert_eq!(collection.features[1].property("prop1"), Some(&json!(0)));
+        assert_eq!(
+            collection.features[2].id,
+            Some(feature::Id::Number(3.into()))
+        );
+        assert_eq!(
+            collection.features[3].id,
+            Some(feature::Id::String("nothing".to_string()))
+        );
+    }
+
+    #[test]
+    fn shared_arcs() {
+        let topology: Topology = serde_json::from_value(json!({
+            "type": "Topology",
+            "objects": {
+                "lines": {
+                    "type": "MultiLineString",
+                    "id": "lines",
+                    "arcs": [[0, 1], [-2, -1]]
+                },
+                "collection": {
+                    "type": "GeometryCollection",
+                    "bbox": [0, 0, 2, 2],
+                    "geometries": [{
+                        "type": "GeometryCollection",
+                        "geometries": [
+                            { "type": "MultiPoint", "coordinates": [[0, 0], [1, 1]] },
+                            { "type": "MultiPolygon", "arcs": [[[2]]] }
+                        ]
+                    }]
+                }
+            },
+            "arcs": [
+                [[0, 0], [1, 0]],
+                [[1, 0], [1, 1, 5]],
+                [[0, 0], [2, 0], [2, 2], [0, 0]]
+            ]
+        }))
+        .unwrap();
+        let collections = topology.to_feature_collections().unwrap();
+
+        let lines = &collections["lines"].features[0];
+        assert_eq!(lines.id, Some(feature::Id::String("lines".to_string())));
+        assert_eq!(
+            lines.geometry,
+            Some(Geometry::new_multi_line_string([
+                vec![vec![0.0, 0.0], vec![1.0, 0.0], vec![1.0, 1.0, 5.0]],
+                vec![vec![1.0, 1.0, 5.0], vec![1.0, 0.0], vec![0.0, 0.0]],
+            ]))
+        );
+
+        let collection = &collections["collection"];
+        assert_eq!(collection.bbox, Some(vec![0.0, 0.0, 2.0, 2.0]));
+        assert_eq!(
+            collection.features[0].geometry,
+            Some(Geometry::new_geometry_collection([
+                Geometry::new_multi_point([[0.0, 0.0], [1.0, 1.0]]),
+                Geometry::new_multi_polygon([[[[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 0.0]]]]),
+            ]))
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let topology = quantized();
+        let json = serde_json::to_value(&topology).unwrap();
+        assert_eq!(json["type"], "Topology");
+        assert_eq!(
+            json["objects"]["example"]["geometries"][2]["arcs"],
+            json!([[-2]])
+        );
+        assert_eq!(
+            json["objects"]["example"]["geometries"][3]["type"],
+            json!(null)
+        );
+        assert_eq!(serde_json::from_value::<Topology>(json).unwrap(), topology);
+    }
+
+    fn squares() -> FeatureCollection {
+        FeatureCollection::from_iter([
+            Feature {
+                id: Some(feature::Id::String("west".to_string())),
+                ..Feature::from(Geometry::new_polygon([[
+                    [0.0, 0.0],
+                    [1.0, 0.0],
+                    [1.0, 1.0],
+                    [0.0, 1.0],
+                    [0.0, 0.0],
+                ]]))
+            },
+            Feature {
+                properties: Some(json!({ "name": "east" }).as_object().unwrap().clone()),
+                ..Feature::from(Geometry::new_polygon([[
+                    [1.0, 1.0],
+                    [1.0, 0.0],
+                    [2.0, 0.0],
+                    [2.0, 1.0],
+                    [1.0, 1.0],
+                ]]))
+            },
+        ])
+    }
+
+    /// Whether two closed rings have the same positions, perhaps starting at a different one.
+    fn same_ring(a: &[Position], b: &[Position]) -> bool {
+        let (a, b) = (open_ring(a), open_ring(b));
+        a.len() == b.len()
+            && (0..a.len()).any(|start| a.iter().cycle().skip(start).take(a.len()).eq(b))
+    }
+
+    fn polygon(feature: &Feature) -> &Vec<Vec<Position>> {
+        let Some(Geometry {
+            value: GeometryValue::Polygon { coordinates },
+            ..
+        }) = &feature.geometry
+        else {
+            panic!("expected a polygon");
+        };
+        coordinates
+    }
+
+    #[test]
+    fn encode_shared_borders() {
+        let collection = squares();
+        let topology = Topology::from_feature_collection("squares", &collection).unwrap();
+        assert_eq!(topology.arcs.len(), 3);
+        assert_eq!(topology.bbox, Some(vec![0.0, 0.0, 2.0, 1.0]));
+
+        let decoded = topology
+            .to_feature_collection(&topology.objects["squares"])
+            .unwrap();
+        for (decoded, original) in decoded.features.iter().zip(&collection.features) {
+            assert_eq!(decoded.id, original.id);
+            assert_eq!(decoded.properties, original.properties);
+            assert!(same_ring(&polygon(decoded)[0], &polygon(original)[0]));
+        }
+    }
+
+    #[test]
+    fn encode_enclave() {
+        // A polygon with a hole, filled by another polygon starting at a different corner
+        let collection = FeatureCollection::from_iter([
+            Feature::from(Geometry::new_polygon([
+                [[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0], [0.0, 0.0]],
+                [[1.0, 1.0], [1.0, 2.0], [2.0, 2.0], [2.0, 1.0], [1.0, 1.0]],
+            ])),
+            Feature::from(Geometry::new_polygon([[
+                [1.0, 2.0],
+                [1.0, 1.0],
+                [2.0, 1.0],
+                [2.0, 2.0],
+                [1.0, 2.0],
+            ]])),
+        ]);
+        let topology = Topology::from_feature_collection("enclave", &collection).unwrap();
+        assert_eq!(topology.arcs.len(), 2);
+
+        let decoded = topology.to_feature_collections().unwrap();
+        let decoded = &decoded["enclave"].features;
+        assert!(same_ring(
+            &polygon(&decoded[0])[1],
+            &polygon(&collection.features[0])[1]
+        ));
+        assert!(same_ring(
+            &polygon(&decoded[1])[0],
+            &polygon(&collection.features[1])[0]
+        ));
+    }
+
+    #[test]
+    fn encode_lines_and_points() {
+        let collection = FeatureCollection::from_iter([
+            Feature::from(Geometry::new_line_string([
+                [0.0, 0.0],
+                [1.0, 1.0],
+                [2.0, 2.0],
+            ])),
+            Feature::from(Geometry::new_multi_line_string([
+                vec![[0.0, 2.0], [1.0, 1.0], [2.0, 0.0]],
+                vec![[2.0, 2.0], [1.0, 1.0], [0.0, 0.0]],
+            ])),
+            Feature::from(Geometry::new_geometry_collection([
+                Geometry::new_point([5.0, 5.0, 1.0]),
+                Geometry::new_multi_point([[6.0, 6.0], [7.0, 7.0]]),
+            ])),
+            Feature::default(),
+        ]);
+        let topology = Topology::from_feature_collections([("things", &collection)]).unwrap();
+        // Each line is cut where it crosses the other, and the reversed line is shared
+        assert_eq!(topology.arcs.len(), 4);
+        let Some(TopoGeometryValue::GeometryCollection { geometries }) =
+            &topology.objects["things"].value
+        else {
+            panic!("expected a GeometryCollection");
+        };
+        assert_eq!(
+            geometries[1].value,
+            Some(TopoGeometryValue::MultiLineString {
+                arcs: vec![vec![2, 3], vec![!1, !0]]
+            })
+        );
+        assert_eq!(geometries[3].value, None);
+
+        let decoded = topology.to_feature_collections().unwrap();
+        assert_eq!(decoded["things"], collection);
+    }
+
+    #[test]
+    fn quantize() {
+        let mut collection = squares();
+        collection
+            .features
+            .push(Feature::from(Geometry::new_point([0.5, 0.5])));
+        let mut topology = Topology::from_feature_collection("squares", &collection).unwrap();
+        topology.quantize(101);
+        assert_eq!(
+            topology.transform,
+            Some(Transform {
+                scale: [0.02, 0.01],
+                translate: [0.0, 0.0]
+            })
+        );
+
+        let json = serde_json::to_value(&topology).unwrap();
+        assert_eq!(json["arcs"][0], json!([[50, 0], [0, 100]]));
+        assert_eq!(
+            json["objects"]["squares"]["geometries"][2]["coordinates"],
+            json!([25, 50])
+        );
+
+        let decoded = serde_json::from_value::<Topology>(json)
+            .unwrap()
+            .to_feature_collections()
+            .unwrap();
+        let decoded = &decoded["squares"].features;
+        assert!(same_ring(
+            &polygon(&decoded[1])[0],
+            &polygon(&collection.features[1])[0]
+        ));
+        assert_eq!(decoded[2].geometry, Some(Geometry::new_point([0.5, 0.5])));
+
+        // Quantizing again replaces the previous quantization
+        let quantized = topology.clone();
+        topology.quantize(101);
+        assert_eq!(topology, quantized);
+        topology.quantize(2);
+        assert_eq!(
+            topology.arcs[0],
+            [Position::from([1.0, 0.0]), Position::from([0.0, 1.0])]
+        );
+        topology.quantize(0);
+        assert_eq!(
+            topology.arcs[0],
+            [Position::from([1.0, 0.0]), Position::from([0.0, 1.0])]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let topology: Topology = r#"{
+            "type": "Topology",
+            "objects": { "line": { "type": "LineString", "arcs": [0, -3] } },
+            "arcs": [[[0, 0], [1, 1]]]
+        }"#
+        .parse()
+        .unwrap();
+        assert!(matches!(
+            topology.to_feature_collections(),
+            Err(Error::TopoJsonArcOutOfBounds(-3))
+        ));
+
+        for invalid in [
+            r#"{ "type": "FeatureCollection", "objects": {}, "arcs": [] }"#,
+            r#"{ "type": "Topology", "objects": { "a": { "type": "Polygon" } }, "arcs": [] }"#,
+            r#"{ "type": "Topology", "objects": { "a": { "type": "Polygon", "arcs": [0] } }, "arcs": [] }"#,
+            r#"{ "type": "Topology", "objects": { "a": { "type": "Circle" } }, "arcs": [] }"#,
+        ] {
+            assert!(invalid.parse::<Topology>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::JsonObject;
+use crate::{GeometryValue, JsonObject};
+
+/// How deeply `GeometryCollection`s may be nested when parsing WKT or WKB. serde_json stops at a
+/// similar depth for GeoJSON.
+pub(crate) const MAX_COLLECTION_DEPTH: usize = 128;
 
 // Treat an empty foreign_members map as None
 pub(crate) fn normalize_foreign_members(fm: &mut Option<JsonObject>) {
@@ -22,3 +26,16 @@ pub(crate) fn normalize_foreign_members(fm: &mut Option<JsonObject>) {
         fm.take();
     }
 }
+
+/// The number of coordinates every position of `value` can be written with in WKT or WKB: the
+/// fewest elements of any position, between 2 and 4.
+pub(crate) fn coordinate_dimensions(value: &GeometryValue) -> usize {
+    value
+        .coords()
+        // An empty point has no dimensions to speak of
+        .filter(|position| !position.is_empty())
+        .map(|position| position.len())
+        .min()
+        .unwrap_or(2)
+        .clamp(2, 4)
+}
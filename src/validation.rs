@@ -0,0 +1,487 @@
+//! Checking GeoJSON objects against the rules of [RFC 7946](https://tools.ietf.org/html/rfc7946).
+//!
+//! Parsing is deliberately permissive, so a successfully parsed object may still contain
+//! geometry that the spec forbids, e.g. a `LineString` with a single position or a `Polygon`
+//! ring which isn't closed. Call `validate()` on a [`GeoJson`], [`FeatureCollection`],
+//! [`Feature`] or [`Geometry`] to get a list of every [`Violation`] found.
+//!
+//! ```
+//! use geojson::{GeoJson, validation::Severity};
+//!
+//! let geojson_str = r#"{
+//!   "type": "FeatureCollection",
+//!   "features": [{
+//!     "type": "Feature",
+//!     "properties": {},
+//!     "geometry": { "type": "LineString", "coordinates": [[1.0, 2.0]] }
+//!   }]
+//! }"#;
+//! let geojson: GeoJson = geojson_str.parse().unwrap();
+//!
+//! let violations = geojson.validate();
+//! assert_eq!(violations.len(), 1);
+//! assert_eq!(violations[0].path, "/features/0/geometry/coordinates");
+//! assert_eq!(violations[0].severity, Severity::Error);
+//! assert_eq!(
+//!     violations[0].to_string(),
+//!     "/features/0/geometry/coordinates: a LineString must have two or more positions, but has 1"
+//! );
+//! ```
+use crate::{Bbox, Feature, FeatureCollection, GeoJson, Geometry, GeometryValue, Position};
+use std::fmt;
+
+/// How serious a [`Violation`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The spec says an implementation SHOULD (NOT) do this. Most consumers will cope.
+    Warning,
+    /// The spec says an implementation MUST (NOT) do this. Consumers may reject the input or
+    /// fail in unexpected ways.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// The rule which was broken by a [`Violation`].
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ViolationKind {
+    /// A position must have at least two elements.
+    ///
+    /// [GeoJSON Format Specification § 3.1.1](https://tools.ietf.org/html/rfc7946#section-3.1.1)
+    PositionTooShort { len: usize },
+    /// A position should not have more than three elements.
+    ///
+    /// [GeoJSON Format Specification § 3.1.1](https://tools.ietf.org/html/rfc7946#section-3.1.1)
+    PositionTooLong { len: usize },
+    /// A `LineString` must have two or more positions.
+    ///
+    /// [GeoJSON Format Specification § 3.1.4](https://tools.ietf.org/html/rfc7946#section-3.1.4)
+    LineStringTooShort { len: usize },
+    /// A linear ring must have four or more positions.
+    ///
+    /// [GeoJSON Format Specification § 3.1.6](https://tools.ietf.org/html/rfc7946#section-3.1.6)
+    RingTooShort { len: usize },
+    /// The first and last positions of a linear ring must be identical.
+    ///
+    /// [GeoJSON Format Specification § 3.1.6](https://tools.ietf.org/html/rfc7946#section-3.1.6)
+    RingNotClosed,
+    /// A `GeometryCollection` should not contain another `GeometryCollection`.
+    ///
+    /// [GeoJSON Format Specification § 3.1.8](https://tools.ietf.org/html/rfc7946#section-3.1.8)
+    NestedGeometryCollection,
+    /// A bbox must have `2*n` elements, where `n >= 2` is the number of dimensions.
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    InvalidBboxLength { len: usize },
+    /// Apart from longitude, which may cross the antimeridian, the minimum value of each axis of
+    /// a bbox must not be greater than its maximum.
+    ///
+    /// [GeoJSON Format Specification § 5](https://tools.ietf.org/html/rfc7946#section-5)
+    InvalidBboxRange { axis: usize },
+}
+
+impl ViolationKind {
+    /// How serious a violation of this kind is.
+    pub fn severity(&self) -> Severity {
+        match self {
+            ViolationKind::PositionTooLong { .. } | ViolationKind::NestedGeometryCollection => {
+                Severity::Warning
+            }
+            ViolationKind::PositionTooShort { .. }
+            | ViolationKind::LineStringTooShort { .. }
+            | ViolationKind::RingTooShort { .. }
+            | ViolationKind::RingNotClosed
+            | ViolationKind::InvalidBboxLength { .. }
+            | ViolationKind::InvalidBboxRange { .. } => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for ViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ViolationKind::PositionTooShort { len } => write!(
+                f,
+                "a position must have two or more elements, but has {len}"
+            ),
+            ViolationKind::PositionTooLong { len } => write!(
+                f,
+                "a position should not have more than three elements, but has {len}"
+            ),
+            ViolationKind::LineStringTooShort { len } => write!(
+                f,
+                "a LineString must have two or more positions, but has {len}"
+            ),
+            ViolationKind::RingTooShort { len } => write!(
+                f,
+                "a linear ring must have four or more positions, but has {len}"
+            ),
+            ViolationKind::RingNotClosed => {
+                f.write_str("the first and last positions of a linear ring must be identical")
+            }
+            ViolationKind::NestedGeometryCollection => {
+                f.write_str("a GeometryCollection should not contain another GeometryCollection")
+            }
+            ViolationKind::InvalidBboxLength { len } => write!(
+                f,
+                "a bbox must have 2*n elements for n >= 2 dimensions, but has {len}"
+            ),
+            ViolationKind::InvalidBboxRange { axis } => write!(
+                f,
+                "the minimum of bbox axis {axis} is greater than its maximum"
+            ),
+        }
+    }
+}
+
+/// A single place where a GeoJSON object doesn't conform to RFC 7946.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    /// A [JSON Pointer](https://tools.ietf.org/html/rfc6901) to the offending member, relative
+    /// to the object `validate()` was called on, e.g. `/features/12/geometry/coordinates/0`.
+    pub path: String,
+    pub severity: Severity,
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.kind)
+    }
+}
+
+impl GeoJson {
+    /// Check this object against RFC 7946, returning every [`Violation`] found.
+    ///
+    /// See the [`validation`](crate::validation) module for details.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut validator = Validator::default();
+        match self {
+            GeoJson::Geometry(geometry) => validator.geometry(geometry, false),
+            GeoJson::Feature(feature) => validator.feature(feature),
+            GeoJson::FeatureCollection(feature_collection) => {
+                validator.feature_collection(feature_collection)
+            }
+        }
+        validator.violations
+    }
+}
+
+impl FeatureCollection {
+    /// Check this object against RFC 7946, returning every [`Violation`] found.
+    ///
+    /// See the [`validation`](crate::validation) module for details.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut validator = Validator::default();
+        validator.feature_collection(self);
+        validator.violations
+    }
+}
+
+impl Feature {
+    /// Check this object against RFC 7946, returning every [`Violation`] found.
+    ///
+    /// See the [`validation`](crate::validation) module for details.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut validator = Validator::default();
+        validator.feature(self);
+        validator.violations
+    }
+}
+
+impl Geometry {
+    /// Check this object against RFC 7946, returning every [`Violation`] found.
+    ///
+    /// See the [`validation`](crate::validation) module for details.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut validator = Validator::default();
+        validator.geometry(self, false);
+        validator.violations
+    }
+}
+
+/// Walks a GeoJSON object, keeping track of the JSON Pointer to the current member.
+#[derive(Default)]
+struct Validator {
+    path: String,
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn report(&mut self, kind: ViolationKind) {
+        self.violations.push(Violation {
+            path: self.path.clone(),
+            severity: kind.severity(),
+            kind,
+        });
+    }
+
+    /// Run `f` with `segment` appended to the current path.
+    fn at(&mut self, segment: impl fmt::Display, f: impl FnOnce(&mut Self)) {
+        use std::fmt::Write;
+        let len = self.path.len();
+        write!(self.path, "/{segment}").expect("writing to a String can't fail");
+        f(self);
+        self.path.truncate(len);
+    }
+
+    fn feature_collection(&mut self, feature_collection: &FeatureCollection) {
+        self.bbox(&feature_collection.bbox);
+        self.at("features", |v| {
+            for (idx, feature) in feature_collection.features.iter().enumerate() {
+                v.at(idx, |v| v.feature(feature));
+            }
+        });
+    }
+
+    fn feature(&mut self, feature: &Feature) {
+        self.bbox(&feature.bbox);
+        if let Some(geometry) = &feature.geometry {
+            self.at("geometry", |v| v.geometry(geometry, false));
+        }
+    }
+
+    fn geometry(&mut self, geometry: &Geometry, in_collection: bool) {
+        self.bbox(&geometry.bbox);
+        match &geometry.value {
+            GeometryValue::Point { coordinates } => {
+                self.at("coordinates", |v| v.position(coordinates));
+            }
+            GeometryValue::MultiPoint { coordinates } => {
+                self.at("coordinates", |v| v.positions(coordinates));
+            }
+            GeometryValue::LineString { coordinates } => {
+                self.at("coordinates", |v| v.line_string(coordinates));
+            }
+            GeometryValue::MultiLineString { coordinates } => self.at("coordinates", |v| {
+                for (idx, line_string) in coordinates.iter().enumerate() {
+                    v.at(idx, |v| v.line_string(line_string));
+                }
+            }),
+            GeometryValue::Polygon { coordinates } => {
+                self.at("coordinates", |v| v.polygon(coordinates));
+            }
+            GeometryValue::MultiPolygon { coordinates } => self.at("coordinates", |v| {
+                for (idx, polygon) in coordinates.iter().enumerate() {
+                    v.at(idx, |v| v.polygon(polygon));
+                }
+            }),
+            GeometryValue::GeometryCollection { geometries } => {
+                if in_collection {
+                    self.report(ViolationKind::NestedGeometryCollection);
+                }
+                self.at("geometries", |v| {
+                    for (idx, geometry) in geometries.iter().enumerate() {
+                        v.at(idx, |v| v.geometry(geometry, true));
+                    }
+                });
+            }
+        }
+    }
+
+    fn polygon(&mut self, rings: &[Vec<Position>]) {
+        for (idx, ring) in rings.iter().enumerate() {
+            self.at(idx, |v| v.ring(ring));
+        }
+    }
+
+    fn ring(&mut self, ring: &[Position]) {
+        if ring.len() < 4 {
+            self.report(ViolationKind::RingTooShort { len: ring.len() });
+        }
+        if ring.first() != ring.last() {
+            self.report(ViolationKind::RingNotClosed);
+        }
+        self.positions(ring);
+    }
+
+    fn line_string(&mut self, line_string: &[Position]) {
+        if line_string.len() < 2 {
+            self.report(ViolationKind::LineStringTooShort {
+                len: line_string.len(),
+            });
+        }
+        self.positions(line_string);
+    }
+
+    fn positions(&mut self, positions: &[Position]) {
+        for (idx, position) in positions.iter().enumerate() {
+            self.at(idx, |v| v.position(position));
+        }
+    }
+
+    fn position(&mut self, position: &Position) {
+        let len = position.len();
+        if len < 2 {
+            self.report(ViolationKind::PositionTooShort { len });
+        } else if len > 3 {
+            self.report(ViolationKind::PositionTooLong { len });
+        }
+    }
+
+    fn bbox(&mut self, bbox: &Option<Bbox>) {
+        let Some(bbox) = bbox else {
+            return;
+        };
+        self.at("bbox", |v| {
+            let len = bbox.len();
+            if len < 4 || len % 2 != 0 {
+                v.report(ViolationKind::InvalidBboxLength { len });
+                return;
+            }
+            let dimensions = len / 2;
+            // Axis 0 is longitude, where west > east is how a bbox spans the antimeridian.
+            for axis in 1..dimensions {
+                if bbox[axis] > bbox[axis + dimensions] {
+                    v.report(ViolationKind::InvalidBboxRange { axis });
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn paths(violations: &[Violation]) -> Vec<&str> {
+        violations.iter().map(|v| v.path.as_str()).collect()
+    }
+
+    #[test]
+    fn valid_geometries() {
+        let geometry = Geometry::new_geometry_collection([
+            Geometry::new_point([1.0, 2.0, 3.0]),
+            Geometry::new_line_string([[0.0, 0.0], [1.0, 1.0]]),
+            Geometry::new_polygon([[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]),
+            Geometry::new(GeometryValue::MultiPolygon {
+                coordinates: vec![],
+            }),
+        ]);
+        assert_eq!(geometry.validate(), vec![]);
+    }
+
+    #[test]
+    fn short_line_string() {
+        let geometry = Geometry::new_multi_line_string([vec![[0.0, 0.0], [1.0, 1.0]], vec![]]);
+        assert_eq!(
+            geometry.validate(),
+            vec![Violation {
+                path: "/coordinates/1".to_string(),
+                severity: Severity::Error,
+                kind: ViolationKind::LineStringTooShort { len: 0 },
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_rings() {
+        let geometry = Geometry::new_polygon([
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]],
+            vec![[0.1, 0.1], [0.2, 0.1], [0.2, 0.2]],
+        ]);
+        let violations = geometry.validate();
+        assert_eq!(paths(&violations), ["/coordinates/1", "/coordinates/1"]);
+        assert_eq!(violations[0].kind, ViolationKind::RingTooShort { len: 3 });
+        assert_eq!(violations[1].kind, ViolationKind::RingNotClosed);
+    }
+
+    #[test]
+    fn position_lengths() {
+        let geometry =
+            Geometry::new_multi_point([vec![1.0, 2.0], vec![1.0], vec![1.0, 2.0, 3.0, 4.0]]);
+        let violations = geometry.validate();
+        assert_eq!(paths(&violations), ["/coordinates/1", "/coordinates/2"]);
+        assert_eq!(violations[0].severity, Severity::Error);
+        assert_eq!(violations[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn nested_geometry_collection() {
+        let geometry = Geometry::new_geometry_collection([Geometry::new_geometry_collection([
+            Geometry::new_point([1.0, 2.0]),
+        ])]);
+        let violations = geometry.validate();
+        assert_eq!(paths(&violations), ["/geometries/0"]);
+        assert_eq!(violations[0].kind, ViolationKind::NestedGeometryCollection);
+        assert_eq!(violations[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn bbox() {
+        let mut geometry = Geometry::new_point([1.0, 2.0]);
+
+        // crossing the antimeridian is allowed
+        geometry.bbox = Some(vec![170.0, 0.0, -170.0, 10.0]);
+        assert_eq!(geometry.validate(), vec![]);
+
+        geometry.bbox = Some(vec![0.0, 10.0, 1.0, 0.0]);
+        assert_eq!(
+            geometry.validate()[0].kind,
+            ViolationKind::InvalidBboxRange { axis: 1 }
+        );
+
+        geometry.bbox = Some(vec![0.0, 0.0, 1.0]);
+        assert_eq!(
+            geometry.validate(),
+            vec![Violation {
+                path: "/bbox".to_string(),
+                severity: Severity::Error,
+                kind: ViolationKind::InvalidBboxLength { len: 3 },
+            }]
+        );
+    }
+
+    #[test]
+    fn feature_collection_paths() {
+        let geojson: GeoJson = json!({
+            "type": "FeatureCollection",
+            "bbox": [0.0, 0.0],
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": {},
+                    "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+                },
+                {
+                    "type": "Feature",
+                    "properties": {},
+                    "geometry": null
+                },
+                {
+                    "type": "Feature",
+                    "properties": {},
+                    "geometry": {
+                        "type": "GeometryCollection",
+                        "geometries": [{
+                            "type": "Polygon",
+                            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]]
+                        }]
+                    }
+                }
+            ]
+        })
+        .to_string()
+        .parse()
+        .unwrap();
+
+        let violations = geojson.validate();
+        assert_eq!(
+            paths(&violations),
+            ["/bbox", "/features/2/geometry/geometries/0/coordinates/0"]
+        );
+        assert_eq!(
+            violations[1].to_string(),
+            "/features/2/geometry/geometries/0/coordinates/0: the first and last positions of a linear ring must be identical"
+        );
+    }
+}
@@ -0,0 +1,640 @@
+//! Visiting every feature, geometry, ring and position of a GeoJSON object, without writing the
+//! recursive `match` over every type of geometry, and into nested `GeometryCollection`s, yourself.
+//!
+//! Implement [`GeoJsonVisitor`] (or [`GeoJsonVisitorMut`] to modify the object), overriding the
+//! hooks for whatever you're interested in, then pass it to one of the `walk_*` functions. Each
+//! hook's default implementation calls the matching `walk_*` function to visit what's inside, so
+//! an overriding hook should call it too to keep going deeper.
+//!
+//! ```
+//! use geojson::{GeoJson, Geometry, GeoJsonVisitor, Position};
+//! use geojson::visit::{walk_geojson, walk_geometry};
+//!
+//! #[derive(Default)]
+//! struct Counter {
+//!     geometries: usize,
+//!     rings: usize,
+//!     positions: usize,
+//! }
+//!
+//! impl GeoJsonVisitor for Counter {
+//!     fn visit_geometry(&mut self, geometry: &Geometry) {
+//!         self.geometries += 1;
+//!         walk_geometry(self, geometry);
+//!     }
+//!
+//!     fn visit_ring(&mut self, _ring: &[Position]) {
+//!         // Don't visit the positions of rings
+//!         self.rings += 1;
+//!     }
+//!
+//!     fn visit_position(&mut self, _position: &Position) {
+//!         self.positions += 1;
+//!     }
+//! }
+//!
+//! let geojson: GeoJson = r#"{
+//!   "type": "GeometryCollection",
+//!   "geometries": [
+//!     { "type": "LineString", "coordinates": [[0, 0], [1, 1]] },
+//!     { "type": "GeometryCollection", "geometries": [
+//!       { "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]] }
+//!     ]}
+//!   ]
+//! }"#.parse().unwrap();
+//!
+//! let mut counter = Counter::default();
+//! walk_geojson(&mut counter, &geojson);
+//! assert_eq!(counter.geometries, 4);
+//! assert_eq!(counter.rings, 1);
+//! assert_eq!(counter.positions, 2);
+//!
+//! // Or, to just visit every position
+//! assert_eq!(geojson.coords().count(), 6);
+//! ```
+use crate::{Feature, FeatureCollection, GeoJson, Geometry, GeometryValue, Position};
+
+use std::slice;
+
+/// Hooks called while walking a GeoJSON object.
+///
+/// See the [`visit`](crate::visit) module for details.
+pub trait GeoJsonVisitor {
+    fn visit_feature_collection(&mut self, collection: &FeatureCollection) {
+        walk_feature_collection(self, collection);
+    }
+
+    fn visit_feature(&mut self, feature: &Feature) {
+        walk_feature(self, feature);
+    }
+
+    /// Called for every geometry, including each in a `GeometryCollection`, before visiting
+    /// those inside it.
+    fn visit_geometry(&mut self, geometry: &Geometry) {
+        walk_geometry(self, geometry);
+    }
+
+    /// Called for every exterior and interior ring of a `Polygon` or `MultiPolygon`.
+    fn visit_ring(&mut self, ring: &[Position]) {
+        walk_ring(self, ring);
+    }
+
+    fn visit_position(&mut self, _position: &Position) {}
+}
+
+/// Visit the object inside `geojson`.
+pub fn walk_geojson<V: GeoJsonVisitor + ?Sized>(visitor: &mut V, geojson: &GeoJson) {
+    match geojson {
+        GeoJson::FeatureCollection(collection) => visitor.visit_feature_collection(collection),
+        GeoJson::Feature(feature) => visitor.visit_feature(feature),
+        GeoJson::Geometry(geometry) => visitor.visit_geometry(geometry),
+    }
+}
+
+/// Visit each feature of `collection`.
+pub fn walk_feature_collection<V: GeoJsonVisitor + ?Sized>(
+    visitor: &mut V,
+    collection: &FeatureCollection,
+) {
+    for feature in &collection.features {
+        visitor.visit_feature(feature);
+    }
+}
+
+/// Visit the geometry of `feature`, if it has one.
+pub fn walk_feature<V: GeoJsonVisitor + ?Sized>(visitor: &mut V, feature: &Feature) {
+    if let Some(geometry) = &feature.geometry {
+        visitor.visit_geometry(geometry);
+    }
+}
+
+/// Visit the rings or positions of `geometry`, or the geometries of a `GeometryCollection`.
+pub fn walk_geometry<V: GeoJsonVisitor + ?Sized>(visitor: &mut V, geometry: &Geometry) {
+    walk_geometry_value(visitor, &geometry.value);
+}
+
+/// Visit the rings or positions of `value`, or the geometries of a `GeometryCollection`.
+pub fn walk_geometry_value<V: GeoJsonVisitor + ?Sized>(visitor: &mut V, value: &GeometryValue) {
+    match value {
+        GeometryValue::Point { coordinates } => visitor.visit_position(coordinates),
+        GeometryValue::MultiPoint { coordinates } | GeometryValue::LineString { coordinates } => {
+            coordinates
+                .iter()
+                .for_each(|position| visitor.visit_position(position))
+        }
+        GeometryValue::MultiLineString { coordinates } => coordinates
+            .iter()
+            .flatten()
+            .for_each(|position| visitor.visit_position(position)),
+        GeometryValue::Polygon { coordinates } => {
+            coordinates.iter().for_each(|ring| visitor.visit_ring(ring))
+        }
+        GeometryValue::MultiPolygon { coordinates } => coordinates
+            .iter()
+            .flatten()
+            .for_each(|ring| visitor.visit_ring(ring)),
+        GeometryValue::GeometryCollection { geometries } => geometries
+            .iter()
+            .for_each(|geometry| visitor.visit_geometry(geometry)),
+    }
+}
+
+/// Visit each position of `ring`.
+pub fn walk_ring<V: GeoJsonVisitor + ?Sized>(visitor: &mut V, ring: &[Position]) {
+    for position in ring {
+        visitor.visit_position(position);
+    }
+}
+
+/// Hooks called while walking a GeoJSON object, which may modify it.
+///
+/// See the [`visit`](crate::visit) module for details.
+pub trait GeoJsonVisitorMut {
+    fn visit_feature_collection_mut(&mut self, collection: &mut FeatureCollection) {
+        walk_feature_collection_mut(self, collection);
+    }
+
+    fn visit_feature_mut(&mut self, feature: &mut Feature) {
+        walk_feature_mut(self, feature);
+    }
+
+    /// Called for every geometry, including each in a `GeometryCollection`, before visiting
+    /// those inside it.
+    fn visit_geometry_mut(&mut self, geometry: &mut Geometry) {
+        walk_geometry_mut(self, geometry);
+    }
+
+    /// Called for every exterior and interior ring of a `Polygon` or `MultiPolygon`.
+    fn visit_ring_mut(&mut self, ring: &mut Vec<Position>) {
+        walk_ring_mut(self, ring);
+    }
+
+    fn visit_position_mut(&mut self, _position: &mut Position) {}
+}
+
+/// Visit the object inside `geojson`.
+pub fn walk_geojson_mut<V: GeoJsonVisitorMut + ?Sized>(visitor: &mut V, geojson: &mut GeoJson) {
+    match geojson {
+        GeoJson::FeatureCollection(collection) => visitor.visit_feature_collection_mut(collection),
+        GeoJson::Feature(feature) => visitor.visit_feature_mut(feature),
+        GeoJson::Geometry(geometry) => visitor.visit_geometry_mut(geometry),
+    }
+}
+
+/// Visit each feature of `collection`.
+pub fn walk_feature_collection_mut<V: GeoJsonVisitorMut + ?Sized>(
+    visitor: &mut V,
+    collection: &mut FeatureCollection,
+) {
+    for feature in &mut collection.features {
+        visitor.visit_feature_mut(feature);
+    }
+}
+
+/// Visit the geometry of `feature`, if it has one.
+pub fn walk_feature_mut<V: GeoJsonVisitorMut + ?Sized>(visitor: &mut V, feature: &mut Feature) {
+    if let Some(geometry) = &mut feature.geometry {
+        visitor.visit_geometry_mut(geometry);
+    }
+}
+
+/// Visit the rings or positions of `geometry`, or the geometries of a `GeometryCollection`.
+pub fn walk_geometry_mut<V: GeoJsonVisitorMut + ?Sized>(visitor: &mut V, geometry: &mut Geometry) {
+    walk_geometry_value_mut(visitor, &mut geometry.value);
+}
+
+/// Visit the rings or positions of `value`, or the geometries of a `GeometryCollection`.
+pub fn walk_geometry_value_mut<V: GeoJsonVisitorMut + ?Sized>(
+    visitor: &mut V,
+    value: &mut GeometryValue,
+) {
+    match value {
+        GeometryValue::Point { coordinates } => visitor.visit_position_mut(coordinates),
+        GeometryValue::MultiPoint { coordinates } | GeometryValue::LineString { coordinates } => {
+            coordinates
+                .iter_mut()
+                .for_each(|position| visitor.visit_position_mut(position))
+        }
+        GeometryValue::MultiLineString { coordinates } => coordinates
+            .iter_mut()
+            .flatten()
+            .for_each(|position| visitor.visit_position_mut(position)),
+        GeometryValue::Polygon { coordinates } => coordinates
+            .iter_mut()
+            .for_each(|ring| visitor.visit_ring_mut(ring)),
+        GeometryValue::MultiPolygon { coordinates } => coordinates
+            .iter_mut()
+            .flatten()
+            .for_each(|ring| visitor.visit_ring_mut(ring)),
+        GeometryValue::GeometryCollection { geometries } => geometries
+            .iter_mut()
+            .for_each(|geometry| visitor.visit_geometry_mut(geometry)),
+    }
+}
+
+/// Visit each position of `ring`.
+pub fn walk_ring_mut<V: GeoJsonVisitorMut + ?Sized>(visitor: &mut V, ring: &mut [Position]) {
+    for position in ring {
+        visitor.visit_position_mut(position);
+    }
+}
+
+/// An iterator over every [`Position`] of a GeoJSON object, including those of any nested
+/// `GeometryCollection`s.
+///
+/// Returned by `coords()` on [`GeoJson`], [`FeatureCollection`], [`Feature`], [`Geometry`] and
+/// [`GeometryValue`].
+#[derive(Clone, Debug)]
+pub struct Coords<'a> {
+    stack: Vec<Frame<'a>>,
+}
+
+#[derive(Clone, Debug)]
+enum Frame<'a> {
+    Features(slice::Iter<'a, Feature>),
+    Geometries(slice::Iter<'a, Geometry>),
+    Polygons(slice::Iter<'a, Vec<Vec<Position>>>),
+    Lines(slice::Iter<'a, Vec<Position>>),
+    Positions(slice::Iter<'a, Position>),
+}
+
+impl<'a> Frame<'a> {
+    fn of(value: &'a GeometryValue) -> Self {
+        match value {
+            GeometryValue::Point { coordinates } => {
+                Frame::Positions(slice::from_ref(coordinates).iter())
+            }
+            GeometryValue::MultiPoint { coordinates }
+            | GeometryValue::LineString { coordinates } => Frame::Positions(coordinates.iter()),
+            GeometryValue::MultiLineString { coordinates }
+            | GeometryValue::Polygon { coordinates } => Frame::Lines(coordinates.iter()),
+            GeometryValue::MultiPolygon { coordinates } => Frame::Polygons(coordinates.iter()),
+            GeometryValue::GeometryCollection { geometries } => {
+                Frame::Geometries(geometries.iter())
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Coords<'a> {
+    type Item = &'a Position;
+
+    fn next(&mut self) -> Option<&'a Position> {
+        loop {
+            let inner = match self.stack.last_mut()? {
+                Frame::Positions(positions) => match positions.next() {
+                    Some(position) => return Some(position),
+                    None => None,
+                },
+                Frame::Lines(lines) => lines.next().map(|line| Frame::Positions(line.iter())),
+                Frame::Polygons(polygons) => {
+                    polygons.next().map(|polygon| Frame::Lines(polygon.iter()))
+                }
+                Frame::Geometries(geometries) => {
+                    geometries.next().map(|geometry| Frame::of(&geometry.value))
+                }
+                Frame::Features(features) => features
+                    .find_map(|feature| feature.geometry.as_ref())
+                    .map(|geometry| Frame::of(&geometry.value)),
+            };
+            match inner {
+                Some(inner) => self.stack.push(inner),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+/// A mutable iterator over every [`Position`] of a GeoJSON object, including those of any nested
+/// `GeometryCollection`s.
+///
+/// Returned by `coords_mut()` on [`GeoJson`], [`FeatureCollection`], [`Feature`], [`Geometry`]
+/// and [`GeometryValue`].
+#[derive(Debug)]
+pub struct CoordsMut<'a> {
+    stack: Vec<FrameMut<'a>>,
+}
+
+#[derive(Debug)]
+enum FrameMut<'a> {
+    Features(slice::IterMut<'a, Feature>),
+    Geometries(slice::IterMut<'a, Geometry>),
+    Polygons(slice::IterMut<'a, Vec<Vec<Position>>>),
+    Lines(slice::IterMut<'a, Vec<Position>>),
+    Positions(slice::IterMut<'a, Position>),
+}
+
+impl<'a> FrameMut<'a> {
+    fn of(value: &'a mut GeometryValue) -> Self {
+        match value {
+            GeometryValue::Point { coordinates } => {
+                FrameMut::Positions(slice::from_mut(coordinates).iter_mut())
+            }
+            GeometryValue::MultiPoint { coordinates }
+            | GeometryValue::LineString { coordinates } => {
+                FrameMut::Positions(coordinates.iter_mut())
+            }
+            GeometryValue::MultiLineString { coordinates }
+            | GeometryValue::Polygon { coordinates } => FrameMut::Lines(coordinates.iter_mut()),
+            GeometryValue::MultiPolygon { coordinates } => {
+                FrameMut::Polygons(coordinates.iter_mut())
+            }
+            GeometryValue::GeometryCollection { geometries } => {
+                FrameMut::Geometries(geometries.iter_mut())
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for CoordsMut<'a> {
+    type Item = &'a mut Position;
+
+    fn next(&mut self) -> Option<&'a mut Position> {
+        loop {
+            let inner = match self.stack.last_mut()? {
+                FrameMut::Positions(positions) => match positions.next() {
+                    Some(position) => return Some(position),
+                    None => None,
+                },
+                FrameMut::Lines(lines) => lines
+                    .next()
+                    .map(|line| FrameMut::Positions(line.iter_mut())),
+                FrameMut::Polygons(polygons) => polygons
+                    .next()
+                    .map(|polygon| FrameMut::Lines(polygon.iter_mut())),
+                FrameMut::Geometries(geometries) => geometries
+                    .next()
+                    .map(|geometry| FrameMut::of(&mut geometry.value)),
+                FrameMut::Features(features) => features
+                    .find_map(|feature| feature.geometry.as_mut())
+                    .map(|geometry| FrameMut::of(&mut geometry.value)),
+            };
+            match inner {
+                Some(inner) => self.stack.push(inner),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+impl GeoJson {
+    /// Iterate over every position of the object.
+    pub fn coords(&self) -> Coords<'_> {
+        match self {
+            GeoJson::FeatureCollection(collection) => collection.coords(),
+            GeoJson::Feature(feature) => feature.coords(),
+            GeoJson::Geometry(geometry) => geometry.coords(),
+        }
+    }
+
+    /// Iterate over every position of the object, mutably.
+    pub fn coords_mut(&mut self) -> CoordsMut<'_> {
+        match self {
+            GeoJson::FeatureCollection(collection) => collection.coords_mut(),
+            GeoJson::Feature(feature) => feature.coords_mut(),
+            GeoJson::Geometry(geometry) => geometry.coords_mut(),
+        }
+    }
+}
+
+impl FeatureCollection {
+    /// Iterate over every position of every feature.
+    pub fn coords(&self) -> Coords<'_> {
+        Coords {
+            stack: vec![Frame::Features(self.features.iter())],
+        }
+    }
+
+    /// Iterate over every position of every feature, mutably.
+    pub fn coords_mut(&mut self) -> CoordsMut<'_> {
+        CoordsMut {
+            stack: vec![FrameMut::Features(self.features.iter_mut())],
+        }
+    }
+}
+
+impl Feature {
+    /// Iterate over every position of the feature's geometry, if it has one.
+    pub fn coords(&self) -> Coords<'_> {
+        Coords {
+            stack: vec![Frame::Features(slice::from_ref(self).iter())],
+        }
+    }
+
+    /// Iterate over every position of the feature's geometry, if it has one, mutably.
+    pub fn coords_mut(&mut self) -> CoordsMut<'_> {
+        CoordsMut {
+            stack: vec![FrameMut::Features(slice::from_mut(self).iter_mut())],
+        }
+    }
+}
+
+impl Geometry {
+    /// Iterate over every position of the geometry.
+    pub fn coords(&self) -> Coords<'_> {
+        self.value.coords()
+    }
+
+    /// Iterate over every position of the geometry, mutably.
+    pub fn coords_mut(&mut self) -> CoordsMut<'_> {
+        self.value.coords_mut()
+    }
+}
+
+impl GeometryValue {
+    /// Iterate over every position of the geometry.
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let mut polygon = GeometryValue::new_polygon([[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]);
+    /// for position in polygon.coords_mut() {
+    ///     *position = [position[0] + 10.0, position[1]].into();
+    /// }
+    /// let xs: Vec<f64> = polygon.coords().map(|position| position[0]).collect();
+    /// assert_eq!(xs, [10.0, 11.0, 11.0, 10.0]);
+    /// ```
+    pub fn coords(&self) -> Coords<'_> {
+        Coords {
+            stack: vec![Frame::of(self)],
+        }
+    }
+
+    /// Iterate over every position of the geometry, mutably.
+    pub fn coords_mut(&mut self) -> CoordsMut<'_> {
+        CoordsMut {
+            stack: vec![FrameMut::of(self)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JsonObject;
+
+    fn collection() -> FeatureCollection {
+        let nested = GeometryValue::GeometryCollection {
+            geometries: vec![
+                Geometry::new(GeometryValue::new_point([0.0, 1.0])),
+                Geometry::new(GeometryValue::GeometryCollection {
+                    geometries: vec![
+                        Geometry::new(GeometryValue::new_multi_polygon([
+                            vec![
+                                vec![[2.0, 0.0], [3.0, 0.0], [3.0, 1.0], [2.0, 0.0]],
+                                vec![[2.1, 0.1], [2.9, 0.1], [2.9, 0.9], [2.1, 0.1]],
+                            ],
+                            vec![vec![[4.0, 0.0], [5.0, 0.0], [5.0, 1.0], [4.0, 0.0]]],
+                        ])),
+                        Geometry::new(GeometryValue::GeometryCollection { geometries: vec![] }),
+                    ],
+                }),
+                Geometry::new(GeometryValue::new_multi_line_string([
+                    vec![[6.0, 0.0], [7.0, 0.0]],
+                    vec![],
+                    vec![[8.0, 0.0]],
+                ])),
+            ],
+        };
+        FeatureCollection::from_iter([
+            Feature::default(),
+            Feature::from(Geometry::new(nested)),
+            Feature::default(),
+            Feature::from(Geometry::new(GeometryValue::new_line_string([[9.0, 0.0]]))),
+        ])
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl GeoJsonVisitor for Recorder {
+        fn visit_feature_collection(&mut self, collection: &FeatureCollection) {
+            self.events.push("collection".to_string());
+            walk_feature_collection(self, collection);
+        }
+
+        fn visit_feature(&mut self, feature: &Feature) {
+            self.events.push("feature".to_string());
+            walk_feature(self, feature);
+        }
+
+        fn visit_geometry(&mut self, geometry: &Geometry) {
+            self.events.push(geometry.value.type_name().to_string());
+            walk_geometry(self, geometry);
+        }
+
+        fn visit_ring(&mut self, ring: &[Position]) {
+            self.events.push(format!("ring of {}", ring.len()));
+        }
+
+        fn visit_position(&mut self, position: &Position) {
+            self.events.push(format!("{}", position[0]));
+        }
+    }
+
+    #[test]
+    fn visitor() {
+        let mut recorder = Recorder::default();
+        walk_geojson(&mut recorder, &GeoJson::from(collection()));
+        assert_eq!(
+            recorder.events,
+            [
+                "collection",
+                "feature",
+                "feature",
+                "GeometryCollection",
+                "Point",
+                "0",
+                "GeometryCollection",
+                "MultiPolygon",
+                "ring of 4",
+                "ring of 4",
+                "ring of 4",
+                "GeometryCollection",
+                "MultiLineString",
+                "6",
+                "7",
+                "8",
+                "feature",
+                "feature",
+                "LineString",
+                "9",
+            ]
+        );
+    }
+
+    #[test]
+    fn visitor_mut() {
+        struct Closer;
+
+        impl GeoJsonVisitorMut for Closer {
+            fn visit_feature_mut(&mut self, feature: &mut Feature) {
+                feature.properties = Some(JsonObject::new());
+                walk_feature_mut(self, feature);
+            }
+
+            fn visit_ring_mut(&mut self, ring: &mut Vec<Position>) {
+                ring.pop();
+                walk_ring_mut(self, ring);
+            }
+
+            fn visit_position_mut(&mut self, position: &mut Position) {
+                *position = [-position[0], position[1]].into();
+            }
+        }
+
+        let mut geojson = GeoJson::from(collection());
+        walk_geojson_mut(&mut Closer, &mut geojson);
+        let GeoJson::FeatureCollection(collection) = geojson else {
+            unreachable!()
+        };
+        assert!(collection.features[0].properties.is_some());
+        let xs: Vec<f64> = collection.coords().map(|position| position[0]).collect();
+        assert_eq!(
+            xs,
+            [
+                -0.0, -2.0, -3.0, -3.0, -2.1, -2.9, -2.9, -4.0, -5.0, -5.0, -6.0, -7.0, -8.0, -9.0
+            ]
+        );
+    }
+
+    #[test]
+    fn coords() {
+        let mut collection = collection();
+        let xs: Vec<f64> = collection.coords().map(|position| position[0]).collect();
+        assert_eq!(
+            xs,
+            [
+                0.0, 2.0, 3.0, 3.0, 2.0, 2.1, 2.9, 2.9, 2.1, 4.0, 5.0, 5.0, 4.0, 6.0, 7.0, 8.0, 9.0
+            ]
+        );
+        assert_eq!(collection.features[0].coords().count(), 0);
+        assert_eq!(collection.features[3].coords().count(), 1);
+
+        for position in collection.coords_mut() {
+            *position = [position[0] * 2.0, position[1]].into();
+        }
+        let mut expected = vec![];
+        for feature in &collection.features {
+            if let Some(geometry) = &feature.geometry {
+                expected.extend(geometry.value.coords().cloned());
+            }
+        }
+        assert_eq!(collection.coords().cloned().collect::<Vec<_>>(), expected);
+        assert_eq!(expected[16][0], 18.0);
+
+        let point = GeometryValue::new_point([1.0, 2.0]);
+        assert_eq!(
+            point.coords().collect::<Vec<_>>(),
+            [&Position::from([1.0, 2.0])]
+        );
+        let empty = GeometryValue::GeometryCollection { geometries: vec![] };
+        assert_eq!(empty.coords().count(), 0);
+    }
+}
@@ -0,0 +1,245 @@
+//! The [right-hand rule](https://tools.ietf.org/html/rfc7946#section-3.1.6) for polygon rings.
+use crate::{GeometryValue, Position};
+
+/// Twice the signed area of a ring, which is positive if the ring is counterclockwise.
+///
+/// Consecutive positions more than 180° of longitude apart are taken to cross the antimeridian.
+fn twice_signed_area(ring: &[Position]) -> f64 {
+    let mut offset = 0.0;
+    let mut area = 0.0;
+    for pair in ring.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        if a.len() < 2 || b.len() < 2 {
+            continue;
+        }
+        let a_x = a[0] + offset;
+        let delta = b[0] - a[0];
+        if delta > 180.0 {
+            offset -= 360.0;
+        } else if delta < -180.0 {
+            offset += 360.0;
+        }
+        let b_x = b[0] + offset;
+        area += a_x * b[1] - b_x * a[1];
+    }
+    area
+}
+
+/// Whether the ring at `index` of a polygon is wound the wrong way. Degenerate rings with no
+/// area have no winding, so are never wrong.
+pub(crate) fn is_ring_reversed(index: usize, ring: &[Position]) -> bool {
+    let area = twice_signed_area(ring);
+    if index == 0 { area < 0.0 } else { area > 0.0 }
+}
+
+fn is_polygon_winding_valid(rings: &[Vec<Position>]) -> bool {
+    rings
+        .iter()
+        .enumerate()
+        .all(|(index, ring)| !is_ring_reversed(index, ring))
+}
+
+fn enforce_polygon_winding(rings: &mut [Vec<Position>]) {
+    for (index, ring) in rings.iter_mut().enumerate() {
+        if is_ring_reversed(index, ring) {
+            // Reversing a closed ring leaves it closed
+            ring.reverse();
+        }
+    }
+}
+
+impl GeometryValue {
+    /// Whether every `Polygon` in this geometry follows the right-hand rule: exterior rings are
+    /// counterclockwise, and holes are clockwise. This includes the polygons of any
+    /// `MultiPolygon`, and any nested `GeometryCollection`s.
+    ///
+    /// Rings with no area, and geometries without any polygons, are always considered valid. Edges
+    /// spanning more than 180° of longitude are taken to cross the antimeridian.
+    ///
+    /// [GeoJSON Format Specification § 3.1.6](https://tools.ietf.org/html/rfc7946#section-3.1.6)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let counterclockwise = GeometryValue::new_polygon([[
+    ///     [0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0],
+    /// ]]);
+    /// assert!(counterclockwise.is_winding_valid());
+    ///
+    /// let clockwise = GeometryValue::new_polygon([[
+    ///     [0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0],
+    /// ]]);
+    /// assert!(!clockwise.is_winding_valid());
+    /// ```
+    pub fn is_winding_valid(&self) -> bool {
+        match self {
+            GeometryValue::Polygon { coordinates } => is_polygon_winding_valid(coordinates),
+            GeometryValue::MultiPolygon { coordinates } => coordinates
+                .iter()
+                .all(|polygon| is_polygon_winding_valid(polygon)),
+            GeometryValue::GeometryCollection { geometries } => geometries
+                .iter()
+                .all(|geometry| geometry.value.is_winding_valid()),
+            GeometryValue::Point { .. }
+            | GeometryValue::MultiPoint { .. }
+            | GeometryValue::LineString { .. }
+            | GeometryValue::MultiLineString { .. } => true,
+        }
+    }
+
+    /// Reverse any polygon rings which don't follow the right-hand rule, so that exterior rings
+    /// are counterclockwise, and holes are clockwise.
+    ///
+    /// Polygons converted from `geo_types` keep the orientation of their input, so may need
+    /// rewinding. To rewind polygons when writing output instead, see
+    /// [`WriteOptions::with_rewind`](crate::WriteOptions::with_rewind).
+    ///
+    /// [GeoJSON Format Specification § 3.1.6](https://tools.ietf.org/html/rfc7946#section-3.1.6)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let mut polygon = GeometryValue::new_polygon([[
+    ///     [0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0],
+    /// ]]);
+    /// polygon.enforce_winding();
+    /// assert!(polygon.is_winding_valid());
+    /// assert_eq!(
+    ///     polygon,
+    ///     GeometryValue::new_polygon([[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]])
+    /// );
+    /// ```
+    pub fn enforce_winding(&mut self) {
+        match self {
+            GeometryValue::Polygon { coordinates } => enforce_polygon_winding(coordinates),
+            GeometryValue::MultiPolygon { coordinates } => coordinates
+                .iter_mut()
+                .for_each(|polygon| enforce_polygon_winding(polygon)),
+            GeometryValue::GeometryCollection { geometries } => geometries
+                .iter_mut()
+                .for_each(|geometry| geometry.value.enforce_winding()),
+            GeometryValue::Point { .. }
+            | GeometryValue::MultiPoint { .. }
+            | GeometryValue::LineString { .. }
+            | GeometryValue::MultiLineString { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Geometry, GeometryValue, PolygonType, Position};
+
+    fn square(reversed: bool) -> Vec<Position> {
+        let mut ring: Vec<Position> = vec![
+            [0.0, 0.0].into(),
+            [10.0, 0.0].into(),
+            [10.0, 10.0].into(),
+            [0.0, 10.0].into(),
+            [0.0, 0.0].into(),
+        ];
+        if reversed {
+            ring.reverse();
+        }
+        ring
+    }
+
+    fn hole(reversed: bool) -> Vec<Position> {
+        let mut ring: Vec<Position> = vec![
+            [2.0, 2.0].into(),
+            [2.0, 4.0].into(),
+            [4.0, 4.0].into(),
+            [4.0, 2.0].into(),
+            [2.0, 2.0].into(),
+        ];
+        if reversed {
+            ring.reverse();
+        }
+        ring
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        let valid: PolygonType = vec![square(false), hole(false)];
+        assert!(GeometryValue::new_polygon(valid.clone()).is_winding_valid());
+
+        for invalid in [
+            vec![square(true), hole(false)],
+            vec![square(false), hole(true)],
+            vec![square(true), hole(true)],
+        ] {
+            let mut polygon = GeometryValue::new_polygon(invalid);
+            assert!(!polygon.is_winding_valid());
+            polygon.enforce_winding();
+            assert_eq!(polygon, GeometryValue::new_polygon(valid.clone()));
+        }
+    }
+
+    #[test]
+    fn multi_polygon_in_geometry_collection() {
+        let mut collection = GeometryValue::new_geometry_collection([
+            Geometry::new_point([1.0, 2.0]),
+            Geometry::new_multi_polygon([vec![square(false)], vec![square(true), hole(false)]]),
+        ]);
+        assert!(!collection.is_winding_valid());
+        collection.enforce_winding();
+        assert!(collection.is_winding_valid());
+        assert_eq!(
+            collection,
+            GeometryValue::new_geometry_collection([
+                Geometry::new_point([1.0, 2.0]),
+                Geometry::new_multi_polygon([
+                    vec![square(false)],
+                    vec![square(false), hole(false)]
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn degenerate_rings() {
+        let mut polygon =
+            GeometryValue::new_polygon([vec![[0.0, 0.0], [1.0, 1.0], [0.0, 0.0]], vec![]]);
+        assert!(polygon.is_winding_valid());
+        let before = polygon.clone();
+        polygon.enforce_winding();
+        assert_eq!(polygon, before);
+    }
+
+    #[test]
+    fn across_antimeridian() {
+        let counterclockwise = vec![
+            [170.0, -10.0],
+            [-170.0, -10.0],
+            [-170.0, 10.0],
+            [170.0, 10.0],
+            [170.0, -10.0],
+        ];
+        assert!(GeometryValue::new_polygon([counterclockwise.clone()]).is_winding_valid());
+
+        let mut clockwise = counterclockwise.clone();
+        clockwise.reverse();
+        let mut polygon = GeometryValue::new_polygon([clockwise]);
+        assert!(!polygon.is_winding_valid());
+        polygon.enforce_winding();
+        assert_eq!(polygon, GeometryValue::new_polygon([counterclockwise]));
+    }
+
+    #[cfg(feature = "geo-types")]
+    #[test]
+    fn from_geo_types() {
+        // geo_types doesn't enforce any orientation
+        let geo_polygon = geo_types::Polygon::new(
+            vec![(0.0, 0.0), (0.0, 10.0), (10.0, 10.0), (10.0, 0.0)].into(),
+            vec![],
+        );
+        let mut polygon = GeometryValue::from(&geo_polygon);
+        assert!(!polygon.is_winding_valid());
+        polygon.enforce_winding();
+        assert_eq!(polygon, GeometryValue::new_polygon([square(false)]));
+    }
+}
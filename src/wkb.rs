@@ -0,0 +1,799 @@
+//! [Well-known binary](https://libgeos.org/specifications/wkb/) for geometries.
+use crate::util::{MAX_COLLECTION_DEPTH, coordinate_dimensions};
+use crate::{Error, Geometry, GeometryValue, Position, Result};
+
+use std::fmt;
+
+/// Options controlling how [`Geometry::to_wkb_with_options`] writes well-known binary.
+///
+/// By default, geometries are written as little-endian ISO WKB.
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Geometry, WkbByteOrder, WkbFlavor, WkbOptions};
+///
+/// let point = Geometry::new_point([1.0, 2.0]);
+/// let options = WkbOptions::default()
+///     .with_flavor(WkbFlavor::Ewkb)
+///     .with_byte_order(WkbByteOrder::BigEndian)
+///     .with_srid(4326);
+/// let wkb = point.to_wkb_with_options(&options).unwrap();
+///
+/// let (geometry, srid) = Geometry::from_wkb_with_srid(&wkb).unwrap();
+/// assert_eq!(geometry, point);
+/// assert_eq!(srid, Some(4326));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct WkbOptions {
+    flavor: WkbFlavor,
+    byte_order: WkbByteOrder,
+    srid: Option<u32>,
+}
+
+/// The dialect of well-known binary to write.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WkbFlavor {
+    /// ISO/IEC 13249-3 WKB, as used by GeoPackage, where `Z`, `M` and `ZM` geometries add 1000,
+    /// 2000 and 3000 to the geometry type code.
+    #[default]
+    Iso,
+    /// PostGIS' extended WKB, which flags `Z` and `M` geometries in the high bits of the geometry
+    /// type code, and may include an SRID.
+    Ewkb,
+}
+
+/// The byte order of well-known binary.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WkbByteOrder {
+    BigEndian,
+    #[default]
+    LittleEndian,
+}
+
+/// What was wrong with the input of [`Geometry::from_wkb`], reported by [`Error::InvalidWkb`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WkbErrorKind {
+    /// The input ended in the middle of a geometry.
+    Truncated,
+    /// A byte order other than 0 (big-endian) or 1 (little-endian).
+    InvalidByteOrder(u8),
+    /// A geometry type code which isn't one of the seven ISO geometry types, in 2D, `Z`, `M` or
+    /// `ZM`, with any EWKB flags.
+    UnknownGeometryType(u32),
+    /// A member of a multi-geometry of the wrong type, e.g. a `LineString` in a `MultiPoint`.
+    UnexpectedMember {
+        expected: &'static str,
+        container: &'static str,
+    },
+    /// `GeometryCollection`s nested more than 128 deep.
+    NestedTooDeeply,
+    /// Bytes following the geometry.
+    TrailingBytes,
+}
+
+impl fmt::Display for WkbErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WkbErrorKind::Truncated => f.write_str("the input ended unexpectedly"),
+            WkbErrorKind::InvalidByteOrder(byte_order) => {
+                write!(f, "expected a byte order of 0 or 1, but got `{byte_order}`")
+            }
+            WkbErrorKind::UnknownGeometryType(code) => {
+                write!(f, "unknown geometry type code `{code}`")
+            }
+            WkbErrorKind::UnexpectedMember {
+                expected,
+                container,
+            } => write!(f, "expected a {expected} in a {container}"),
+            WkbErrorKind::NestedTooDeeply => f.write_str("GeometryCollection is nested too deeply"),
+            WkbErrorKind::TrailingBytes => f.write_str("expected the end of the input"),
+        }
+    }
+}
+
+impl WkbOptions {
+    /// Which dialect of WKB to write. Defaults to [`WkbFlavor::Iso`].
+    pub fn with_flavor(mut self, flavor: WkbFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    /// Which byte order to write. Defaults to [`WkbByteOrder::LittleEndian`].
+    pub fn with_byte_order(mut self, byte_order: WkbByteOrder) -> Self {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /// The spatial reference system identifier to write, e.g. `4326`. Defaults to `None`.
+    ///
+    /// ISO WKB has no place for an SRID, so it's only written with [`WkbFlavor::Ewkb`].
+    pub fn with_srid(mut self, srid: impl Into<Option<u32>>) -> Self {
+        self.srid = srid.into();
+        self
+    }
+}
+
+const POINT: u32 = 1;
+const LINE_STRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTI_POINT: u32 = 4;
+const MULTI_LINE_STRING: u32 = 5;
+const MULTI_POLYGON: u32 = 6;
+const GEOMETRY_COLLECTION: u32 = 7;
+
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+impl Geometry {
+    /// Write this geometry as little-endian ISO [well-known binary](https://libgeos.org/specifications/wkb/).
+    ///
+    /// Positions with three elements are written with a `Z` coordinate, and positions with four
+    /// with `ZM` coordinates. Every position of a geometry must have the same dimensions in WKB,
+    /// so positions are truncated to the fewest elements of any position in the geometry. A
+    /// `Point` whose position has no elements is written with `NaN` coordinates, which is how WKB
+    /// represents `POINT EMPTY`.
+    ///
+    /// The `bbox` and any foreign members aren't written.
+    ///
+    /// To write big-endian or EWKB, see [`Geometry::to_wkb_with_options`].
+    ///
+    /// # Errors
+    ///
+    /// WKB counts positions, rings and geometries with 32 bits, so a list with more than
+    /// `u32::MAX` elements is an [`Error::WkbTooManyElements`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::Geometry;
+    ///
+    /// let point = Geometry::new_point([1.0, 2.0]);
+    /// assert_eq!(
+    ///     point.to_wkb().unwrap(),
+    ///     [
+    ///         0x01, // Little-endian
+    ///         0x01, 0x00, 0x00, 0x00, // Point
+    ///         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f, // 1.0
+    ///         0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, // 2.0
+    ///     ]
+    /// );
+    /// ```
+    pub fn to_wkb(&self) -> Result<Vec<u8>> {
+        self.to_wkb_with_options(&WkbOptions::default())
+    }
+
+    /// Write this geometry as [well-known binary](https://libgeos.org/specifications/wkb/),
+    /// with the given [`WkbOptions`].
+    ///
+    /// See [`Geometry::to_wkb`].
+    pub fn to_wkb_with_options(&self, options: &WkbOptions) -> Result<Vec<u8>> {
+        let mut writer = Writer {
+            wkb: vec![],
+            options,
+            dimensions: coordinate_dimensions(&self.value),
+        };
+        let srid = match options.flavor {
+            WkbFlavor::Iso => None,
+            WkbFlavor::Ewkb => options.srid,
+        };
+        writer.geometry(&self.value, srid)?;
+        Ok(writer.wkb)
+    }
+
+    /// Parse a geometry from [well-known binary](https://libgeos.org/specifications/wkb/).
+    ///
+    /// ISO WKB and EWKB are both accepted, in either byte order, and may even be mixed within a
+    /// geometry. `Z` coordinates become the third element of each position, and `ZM` coordinates
+    /// the third and fourth. GeoJSON can't distinguish a measure from an altitude, so the `M`
+    /// coordinates of a geometry with only `M` become the third element of each position. A
+    /// `Point` with `NaN` coordinates is read as a `Point` whose position has no elements.
+    ///
+    /// Any SRID is ignored. To read it, see [`Geometry::from_wkb_with_srid`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Error, Geometry, WkbErrorKind};
+    ///
+    /// // POINT Z (1 2 3) in big-endian ISO WKB
+    /// let wkb = [
+    ///     0x00, 0x00, 0x00, 0x03, 0xe9,
+    ///     0x3f, 0xf0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ///     0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ///     0x40, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    /// ];
+    /// assert_eq!(Geometry::from_wkb(&wkb).unwrap(), Geometry::new_point([1.0, 2.0, 3.0]));
+    ///
+    /// assert!(matches!(
+    ///     Geometry::from_wkb(&wkb[..20]),
+    ///     Err(Error::InvalidWkb {
+    ///         kind: WkbErrorKind::Truncated,
+    ///         offset: 13
+    ///     })
+    /// ));
+    /// ```
+    pub fn from_wkb(wkb: &[u8]) -> Result<Geometry> {
+        Ok(Self::from_wkb_with_srid(wkb)?.0)
+    }
+
+    /// Parse a geometry from [well-known binary](https://libgeos.org/specifications/wkb/), along
+    /// with the SRID of the geometry, if it's EWKB with an SRID.
+    ///
+    /// See [`Geometry::from_wkb`].
+    pub fn from_wkb_with_srid(wkb: &[u8]) -> Result<(Geometry, Option<u32>)> {
+        let mut reader = Reader {
+            wkb,
+            offset: 0,
+            depth: 0,
+        };
+        let (header, value) = reader.geometry()?;
+        if reader.offset != wkb.len() {
+            return Err(Reader::error_at(reader.offset, WkbErrorKind::TrailingBytes));
+        }
+        Ok((Geometry::new(value), header.srid))
+    }
+}
+
+struct Writer<'a> {
+    wkb: Vec<u8>,
+    options: &'a WkbOptions,
+    dimensions: usize,
+}
+
+impl Writer<'_> {
+    fn geometry(&mut self, value: &GeometryValue, srid: Option<u32>) -> Result<()> {
+        let code = match value {
+            GeometryValue::Point { .. } => POINT,
+            GeometryValue::LineString { .. } => LINE_STRING,
+            GeometryValue::Polygon { .. } => POLYGON,
+            GeometryValue::MultiPoint { .. } => MULTI_POINT,
+            GeometryValue::MultiLineString { .. } => MULTI_LINE_STRING,
+            GeometryValue::MultiPolygon { .. } => MULTI_POLYGON,
+            GeometryValue::GeometryCollection { .. } => GEOMETRY_COLLECTION,
+        };
+        self.header(code, srid);
+        match value {
+            GeometryValue::Point { coordinates } => self.position(coordinates),
+            GeometryValue::LineString { coordinates } => self.line(coordinates)?,
+            GeometryValue::Polygon { coordinates } => self.polygon(coordinates)?,
+            GeometryValue::MultiPoint { coordinates } => {
+                self.count(coordinates.len())?;
+                for position in coordinates {
+                    self.header(POINT, None);
+                    self.position(position);
+                }
+            }
+            GeometryValue::MultiLineString { coordinates } => {
+                self.count(coordinates.len())?;
+                for line in coordinates {
+                    self.header(LINE_STRING, None);
+                    self.line(line)?;
+                }
+            }
+            GeometryValue::MultiPolygon { coordinates } => {
+                self.count(coordinates.len())?;
+                for polygon in coordinates {
+                    self.header(POLYGON, None);
+                    self.polygon(polygon)?;
+                }
+            }
+            GeometryValue::GeometryCollection { geometries } => {
+                self.count(geometries.len())?;
+                for geometry in geometries {
+                    self.geometry(&geometry.value, None)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn header(&mut self, code: u32, srid: Option<u32>) {
+        self.wkb.push(match self.options.byte_order {
+            WkbByteOrder::BigEndian => 0,
+            WkbByteOrder::LittleEndian => 1,
+        });
+        let code = match (self.options.flavor, self.dimensions) {
+            (WkbFlavor::Iso, 3) => code + 1000,
+            (WkbFlavor::Iso, 4) => code + 3000,
+            (WkbFlavor::Ewkb, 3) => code | EWKB_Z,
+            (WkbFlavor::Ewkb, 4) => code | EWKB_Z | EWKB_M,
+            _ => code,
+        };
+        match srid {
+            Some(srid) => {
+                self.u32(code | EWKB_SRID);
+                self.u32(srid);
+            }
+            None => self.u32(code),
+        }
+    }
+
+    fn polygon(&mut self, rings: &[Vec<Position>]) -> Result<()> {
+        self.count(rings.len())?;
+        for ring in rings {
+            self.line(ring)?;
+        }
+        Ok(())
+    }
+
+    fn line(&mut self, positions: &[Position]) -> Result<()> {
+        self.count(positions.len())?;
+        for position in positions {
+            self.position(position);
+        }
+        Ok(())
+    }
+
+    fn position(&mut self, position: &Position) {
+        for index in 0..self.dimensions {
+            self.f64(position.as_slice().get(index).copied().unwrap_or(f64::NAN));
+        }
+    }
+
+    fn count(&mut self, count: usize) -> Result<()> {
+        let count = u32::try_from(count).map_err(|_| Error::WkbTooManyElements(count))?;
+        self.u32(count);
+        Ok(())
+    }
+
+    fn u32(&mut self, value: u32) {
+        match self.options.byte_order {
+            WkbByteOrder::BigEndian => self.wkb.extend(value.to_be_bytes()),
+            WkbByteOrder::LittleEndian => self.wkb.extend(value.to_le_bytes()),
+        }
+    }
+
+    fn f64(&mut self, value: f64) {
+        match self.options.byte_order {
+            WkbByteOrder::BigEndian => self.wkb.extend(value.to_be_bytes()),
+            WkbByteOrder::LittleEndian => self.wkb.extend(value.to_le_bytes()),
+        }
+    }
+}
+
+/// The header preceding every geometry in WKB.
+struct Header {
+    byte_order: WkbByteOrder,
+    code: u32,
+    dimensions: usize,
+    srid: Option<u32>,
+}
+
+struct Reader<'a> {
+    wkb: &'a [u8],
+    offset: usize,
+    // The number of GeometryCollections being read
+    depth: usize,
+}
+
+impl Reader<'_> {
+    fn error_at(offset: usize, kind: WkbErrorKind) -> Error {
+        Error::InvalidWkb { kind, offset }
+    }
+
+    fn geometry(&mut self) -> Result<(Header, GeometryValue)> {
+        let offset = self.offset;
+        let header = self.header()?;
+        let value = match header.code {
+            POINT => GeometryValue::Point {
+                coordinates: self.position(&header)?,
+            },
+            LINE_STRING => GeometryValue::LineString {
+                coordinates: self.line(&header)?,
+            },
+            POLYGON => GeometryValue::Polygon {
+                coordinates: self.polygon(&header)?,
+            },
+            MULTI_POINT => GeometryValue::MultiPoint {
+                coordinates: self.members(&header, |reader| {
+                    let header = reader.member_header(POINT, "Point", "MultiPoint")?;
+                    reader.position(&header)
+                })?,
+            },
+            MULTI_LINE_STRING => GeometryValue::MultiLineString {
+                coordinates: self.members(&header, |reader| {
+                    let header =
+                        reader.member_header(LINE_STRING, "LineString", "MultiLineString")?;
+                    reader.line(&header)
+                })?,
+            },
+            MULTI_POLYGON => GeometryValue::MultiPolygon {
+                coordinates: self.members(&header, |reader| {
+                    let header = reader.member_header(POLYGON, "Polygon", "MultiPolygon")?;
+                    reader.polygon(&header)
+                })?,
+            },
+            _ => {
+                if self.depth == MAX_COLLECTION_DEPTH {
+                    return Err(Self::error_at(offset, WkbErrorKind::NestedTooDeeply));
+                }
+                self.depth += 1;
+                let geometries =
+                    self.members(&header, |reader| Ok(Geometry::new(reader.geometry()?.1)))?;
+                self.depth -= 1;
+                GeometryValue::GeometryCollection { geometries }
+            }
+        };
+        Ok((header, value))
+    }
+
+    /// Read the header of a member of a multi-geometry, which must have the type `code`.
+    fn member_header(
+        &mut self,
+        code: u32,
+        expected: &'static str,
+        container: &'static str,
+    ) -> Result<Header> {
+        let offset = self.offset;
+        let header = self.header()?;
+        if header.code != code {
+            return Err(Self::error_at(
+                offset,
+                WkbErrorKind::UnexpectedMember {
+                    expected,
+                    container,
+                },
+            ));
+        }
+        Ok(header)
+    }
+
+    fn header(&mut self) -> Result<Header> {
+        let byte_order = match self.bytes::<1>()? {
+            [0] => WkbByteOrder::BigEndian,
+            [1] => WkbByteOrder::LittleEndian,
+            [other] => {
+                return Err(Self::error_at(
+                    self.offset - 1,
+                    WkbErrorKind::InvalidByteOrder(other),
+                ));
+            }
+        };
+        let offset = self.offset;
+        let code = self.u32(byte_order)?;
+        // EWKB flags, which don't appear in ISO WKB
+        let mut has_z = code & EWKB_Z != 0;
+        let mut has_m = code & EWKB_M != 0;
+        let srid = if code & EWKB_SRID != 0 {
+            Some(self.u32(byte_order)?)
+        } else {
+            None
+        };
+        let iso_code = code & !(EWKB_Z | EWKB_M | EWKB_SRID);
+        match iso_code / 1000 {
+            0 => {}
+            1 => has_z = true,
+            2 => has_m = true,
+            3 => (has_z, has_m) = (true, true),
+            _ => {
+                return Err(Self::error_at(
+                    offset,
+                    WkbErrorKind::UnknownGeometryType(code),
+                ));
+            }
+        }
+        if !(POINT..=GEOMETRY_COLLECTION).contains(&(iso_code % 1000)) {
+            return Err(Self::error_at(
+                offset,
+                WkbErrorKind::UnknownGeometryType(code),
+            ));
+        }
+        Ok(Header {
+            byte_order,
+            code: iso_code % 1000,
+            dimensions: 2 + usize::from(has_z) + usize::from(has_m),
+            srid,
+        })
+    }
+
+    /// Read a count followed by that many items.
+    fn members<T>(
+        &mut self,
+        header: &Header,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let count = self.u32(header.byte_order)? as usize;
+        // Don't trust the count to allocate, as every item takes at least one byte
+        let mut items = Vec::with_capacity(count.min(self.wkb.len() - self.offset));
+        for _ in 0..count {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    fn polygon(&mut self, header: &Header) -> Result<Vec<Vec<Position>>> {
+        self.members(header, |reader| reader.line(header))
+    }
+
+    fn line(&mut self, header: &Header) -> Result<Vec<Position>> {
+        self.members(header, |reader| reader.position(header))
+    }
+
+    fn position(&mut self, header: &Header) -> Result<Position> {
+        let elements = (0..header.dimensions)
+            .map(|_| self.f64(header.byte_order))
+            .collect::<Result<Vec<f64>>>()?;
+        if elements.iter().all(|element| element.is_nan()) {
+            // `POINT EMPTY`
+            return Ok(Position::from(vec![]));
+        }
+        Ok(Position::from(elements))
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self
+            .wkb
+            .get(self.offset..self.offset + N)
+            .ok_or(Self::error_at(self.offset, WkbErrorKind::Truncated))?;
+        self.offset += N;
+        Ok(bytes.try_into().expect("a slice of N bytes"))
+    }
+
+    fn u32(&mut self, byte_order: WkbByteOrder) -> Result<u32> {
+        let bytes = self.bytes()?;
+        Ok(match byte_order {
+            WkbByteOrder::BigEndian => u32::from_be_bytes(bytes),
+            WkbByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+        })
+    }
+
+    fn f64(&mut self, byte_order: WkbByteOrder) -> Result<f64> {
+        let bytes = self.bytes()?;
+        Ok(match byte_order {
+            WkbByteOrder::BigEndian => f64::from_be_bytes(bytes),
+            WkbByteOrder::LittleEndian => f64::from_le_bytes(bytes),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn to_hex(wkb: &[u8]) -> String {
+        wkb.iter().map(|byte| format!("{byte:02X}")).collect()
+    }
+
+    #[test]
+    fn too_many_elements() {
+        let mut writer = Writer {
+            wkb: vec![],
+            options: &WkbOptions::default(),
+            dimensions: 2,
+        };
+        writer.count(u32::MAX as usize).unwrap();
+        assert!(matches!(
+            writer.count(u32::MAX as usize + 1),
+            Err(Error::WkbTooManyElements(count)) if count == u32::MAX as usize + 1
+        ));
+    }
+
+    fn geometries() -> Vec<Geometry> {
+        vec![
+            Geometry::new_point([1.0, 2.0]),
+            Geometry::new_point([1.0, 2.0, 3.0, 4.0]),
+            Geometry::new_point(Vec::<f64>::new()),
+            Geometry::new_multi_point([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]),
+            Geometry::new_line_string([[1.0, 2.0], [3.0, 4.0]]),
+            Geometry::new_multi_line_string([[[1.0, 2.0], [3.0, 4.0]], [[5.0, 6.0], [7.0, 8.0]]]),
+            Geometry::new_polygon([
+                [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 0.0]],
+                [[1.0, 1.0], [2.0, 2.0], [2.0, 1.0], [1.0, 1.0]],
+            ]),
+            Geometry::new_polygon(Vec::<Vec<[f64; 2]>>::new()),
+            Geometry::new_multi_polygon([[[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]]),
+            Geometry::new_geometry_collection([
+                Geometry::new_point([1.0, 2.0]),
+                Geometry::new_geometry_collection([Geometry::new_line_string([
+                    [1.0, 2.0],
+                    [3.0, 4.0],
+                ])]),
+            ]),
+        ]
+    }
+
+    #[test]
+    fn roundtrip() {
+        for flavor in [WkbFlavor::Iso, WkbFlavor::Ewkb] {
+            for byte_order in [WkbByteOrder::BigEndian, WkbByteOrder::LittleEndian] {
+                let options = WkbOptions::default()
+                    .with_flavor(flavor)
+                    .with_byte_order(byte_order);
+                for geometry in geometries() {
+                    let wkb = geometry.to_wkb_with_options(&options).unwrap();
+                    assert_eq!(
+                        Geometry::from_wkb(&wkb).unwrap(),
+                        geometry,
+                        "{flavor:?} {byte_order:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn known_encodings() {
+        // Generated by PostGIS, e.g. `ST_AsEWKB('SRID=4326;POINT Z (1 2 3)'::geometry, 'XDR')`
+        let point = Geometry::new_point([1.0, 2.0, 3.0]);
+        let cases = [
+            (
+                WkbOptions::default(),
+                "01E9030000000000000000F03F00000000000000400000000000000840",
+            ),
+            (
+                WkbOptions::default().with_byte_order(WkbByteOrder::BigEndian),
+                "00000003E93FF000000000000040000000000000004008000000000000",
+            ),
+            (
+                WkbOptions::default().with_flavor(WkbFlavor::Ewkb),
+                "0101000080000000000000F03F00000000000000400000000000000840",
+            ),
+            (
+                WkbOptions::default()
+                    .with_flavor(WkbFlavor::Ewkb)
+                    .with_byte_order(WkbByteOrder::BigEndian)
+                    .with_srid(4326),
+                "00A0000001000010E63FF000000000000040000000000000004008000000000000",
+            ),
+        ];
+        for (options, hex) in cases {
+            assert_eq!(to_hex(&point.to_wkb_with_options(&options).unwrap()), hex);
+            assert_eq!(Geometry::from_wkb(&from_hex(hex)).unwrap(), point);
+        }
+        assert_eq!(
+            to_hex(
+                &Geometry::new_line_string([[1.0, 2.0], [3.0, 4.0]])
+                    .to_wkb()
+                    .unwrap()
+            ),
+            "010200000002000000000000000000F03F000000000000004000000000000008400000000000001040"
+        );
+    }
+
+    #[test]
+    fn srid() {
+        let point = Geometry::new_point([1.0, 2.0]);
+        let ewkb = WkbOptions::default()
+            .with_flavor(WkbFlavor::Ewkb)
+            .with_srid(3857);
+        let (geometry, srid) =
+            Geometry::from_wkb_with_srid(&point.to_wkb_with_options(&ewkb).unwrap()).unwrap();
+        assert_eq!(geometry, point);
+        assert_eq!(srid, Some(3857));
+
+        // ISO WKB can't hold an SRID
+        let iso = WkbOptions::default().with_srid(3857);
+        assert_eq!(
+            point.to_wkb_with_options(&iso).unwrap(),
+            point.to_wkb().unwrap()
+        );
+        assert_eq!(
+            Geometry::from_wkb_with_srid(&point.to_wkb().unwrap())
+                .unwrap()
+                .1,
+            None
+        );
+    }
+
+    #[test]
+    fn dimensions() {
+        // ISO `POINT M (1 2 4)`
+        let wkb = from_hex("01D1070000000000000000F03F00000000000000400000000000001040");
+        assert_eq!(
+            Geometry::from_wkb(&wkb).unwrap(),
+            Geometry::new_point([1.0, 2.0, 4.0])
+        );
+        // A position without a Z drops every other position's Z
+        let line_string = Geometry::new_line_string([vec![1.0, 2.0, 3.0], vec![4.0, 5.0]]);
+        assert_eq!(
+            Geometry::from_wkb(&line_string.to_wkb().unwrap()).unwrap(),
+            Geometry::new_line_string([[1.0, 2.0], [4.0, 5.0]])
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let wkb = Geometry::new_line_string([[1.0, 2.0], [3.0, 4.0]])
+            .to_wkb()
+            .unwrap();
+        for len in 0..wkb.len() {
+            assert!(
+                matches!(
+                    Geometry::from_wkb(&wkb[..len]),
+                    Err(Error::InvalidWkb {
+                        kind: WkbErrorKind::Truncated,
+                        ..
+                    })
+                ),
+                "{len}"
+            );
+        }
+
+        let mut trailing = wkb.clone();
+        trailing.push(0);
+        assert!(matches!(
+            Geometry::from_wkb(&trailing),
+            Err(Error::InvalidWkb {
+                kind: WkbErrorKind::TrailingBytes,
+                offset: 41
+            })
+        ));
+
+        let mut unknown = wkb.clone();
+        unknown[1] = 8;
+        assert!(matches!(
+            Geometry::from_wkb(&unknown),
+            Err(Error::InvalidWkb {
+                kind: WkbErrorKind::UnknownGeometryType(8),
+                offset: 1
+            })
+        ));
+        unknown[1..5].copy_from_slice(&4002u32.to_le_bytes());
+        assert!(matches!(
+            Geometry::from_wkb(&unknown),
+            Err(Error::InvalidWkb {
+                kind: WkbErrorKind::UnknownGeometryType(4002),
+                ..
+            })
+        ));
+
+        let mut byte_order = wkb.clone();
+        byte_order[0] = 2;
+        assert!(matches!(
+            Geometry::from_wkb(&byte_order),
+            Err(Error::InvalidWkb {
+                kind: WkbErrorKind::InvalidByteOrder(2),
+                offset: 0
+            })
+        ));
+
+        // A MultiPoint containing a LineString
+        let mut multi_point = vec![1, 4, 0, 0, 0, 1, 0, 0, 0];
+        multi_point.extend(&wkb);
+        assert!(matches!(
+            Geometry::from_wkb(&multi_point),
+            Err(Error::InvalidWkb {
+                kind: WkbErrorKind::UnexpectedMember { .. },
+                offset: 9
+            })
+        ));
+
+        // A huge count doesn't allocate
+        let huge = from_hex("0102000000FFFFFFFF");
+        assert!(matches!(
+            Geometry::from_wkb(&huge),
+            Err(Error::InvalidWkb {
+                kind: WkbErrorKind::Truncated,
+                offset: 9
+            })
+        ));
+    }
+
+    #[test]
+    fn deeply_nested() {
+        // Little-endian GeometryCollections with one member each, around a point
+        let nested = |depth| {
+            let mut wkb = [1, 7, 0, 0, 0, 1, 0, 0, 0].repeat(depth);
+            wkb.extend(Geometry::new_point([1.0, 2.0]).to_wkb().unwrap());
+            wkb
+        };
+        assert!(Geometry::from_wkb(&nested(MAX_COLLECTION_DEPTH)).is_ok());
+        assert!(matches!(
+            Geometry::from_wkb(&nested(200_000)),
+            Err(Error::InvalidWkb {
+                kind: WkbErrorKind::NestedTooDeeply,
+                offset,
+            }) if offset == 9 * MAX_COLLECTION_DEPTH
+        ));
+    }
+}
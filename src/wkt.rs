@@ -0,0 +1,670 @@
+//! [Well-known text](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry)
+//! for geometries.
+use crate::util::{MAX_COLLECTION_DEPTH, coordinate_dimensions};
+use crate::{Error, Geometry, GeometryValue, Position, Result};
+
+use std::fmt::Write;
+
+impl GeometryValue {
+    /// Write this geometry as [well-known text](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry).
+    ///
+    /// Positions with three elements are written with a `Z` coordinate, and positions with four
+    /// with `ZM` coordinates. Every position of a geometry must have the same dimensions in WKT,
+    /// so positions are truncated to the fewest elements of any position in the geometry.
+    ///
+    /// Empty geometries are written as `EMPTY`, including a `Point` whose position has no
+    /// elements, and such a point within a `MultiPoint`.
+    ///
+    /// # Errors
+    ///
+    /// WKT can't represent any other position with fewer than two elements, which is an
+    /// [`Error::PositionTooShort`], or a coordinate that's NaN or infinite, which is an
+    /// [`Error::NonFiniteCoordinate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let line_string = GeometryValue::new_line_string([[1.0, 2.0, 3.0], [4.5, 5.0, 6.0]]);
+    /// assert_eq!(line_string.to_wkt().unwrap(), "LINESTRING Z (1 2 3, 4.5 5 6)");
+    ///
+    /// let polygon = GeometryValue::new_polygon(Vec::<Vec<[f64; 2]>>::new());
+    /// assert_eq!(polygon.to_wkt().unwrap(), "POLYGON EMPTY");
+    ///
+    /// assert!(GeometryValue::new_point([f64::NAN, 1.0]).to_wkt().is_err());
+    /// ```
+    pub fn to_wkt(&self) -> Result<String> {
+        let mut wkt = String::new();
+        write_geometry(&mut wkt, self, coordinate_dimensions(self))?;
+        Ok(wkt)
+    }
+
+    /// Parse a geometry from [well-known text](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry).
+    ///
+    /// Keywords are case-insensitive. `Z` coordinates become the third element of each position,
+    /// and `ZM` coordinates the third and fourth. GeoJSON can't distinguish a measure from an
+    /// altitude, so the `M` coordinates of a geometry with only `M` become the third element of
+    /// each position. When there's no dimension keyword, each position may have between two
+    /// and four coordinates.
+    ///
+    /// An [EWKT](https://postgis.net/docs/using_postgis_dbmanagement.html#EWKB_EWKT) `SRID=…;`
+    /// prefix, as written by PostGIS, is accepted and ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::GeometryValue;
+    ///
+    /// let point = GeometryValue::from_wkt("POINT ZM (1 2 3 4)").unwrap();
+    /// assert_eq!(point, GeometryValue::new_point([1.0, 2.0, 3.0, 4.0]));
+    ///
+    /// let collection = GeometryValue::from_wkt(
+    ///     "GEOMETRYCOLLECTION (POINT (1 2), LINESTRING EMPTY)"
+    /// ).unwrap();
+    /// assert_eq!(
+    ///     collection.to_wkt().unwrap(),
+    ///     "GEOMETRYCOLLECTION (POINT (1 2), LINESTRING EMPTY)"
+    /// );
+    ///
+    /// assert!(GeometryValue::from_wkt("POINT (1)").is_err());
+    /// ```
+    pub fn from_wkt(wkt: &str) -> Result<Self> {
+        let mut parser = Parser {
+            input: wkt,
+            offset: 0,
+            depth: 0,
+        };
+        parser.skip_srid();
+        let value = parser.geometry(None)?;
+        match parser.next_token()? {
+            None => Ok(value),
+            Some(_) => Err(parser.error("expected the end of the input")),
+        }
+    }
+}
+
+fn write_geometry(wkt: &mut String, value: &GeometryValue, dimensions: usize) -> Result<()> {
+    let (name, is_empty) = match value {
+        GeometryValue::Point { coordinates } => ("POINT", coordinates.is_empty()),
+        GeometryValue::MultiPoint { coordinates } => ("MULTIPOINT", coordinates.is_empty()),
+        GeometryValue::LineString { coordinates } => ("LINESTRING", coordinates.is_empty()),
+        GeometryValue::MultiLineString { coordinates } => {
+            ("MULTILINESTRING", coordinates.is_empty())
+        }
+        GeometryValue::Polygon { coordinates } => ("POLYGON", coordinates.is_empty()),
+        GeometryValue::MultiPolygon { coordinates } => ("MULTIPOLYGON", coordinates.is_empty()),
+        GeometryValue::GeometryCollection { geometries } => {
+            ("GEOMETRYCOLLECTION", geometries.is_empty())
+        }
+    };
+    wkt.push_str(name);
+    match dimensions {
+        3 => wkt.push_str(" Z"),
+        4 => wkt.push_str(" ZM"),
+        _ => {}
+    }
+    if is_empty {
+        wkt.push_str(" EMPTY");
+        return Ok(());
+    }
+    wkt.push(' ');
+    match value {
+        GeometryValue::Point { coordinates } => write_list(wkt, [coordinates], |wkt, position| {
+            write_position(wkt, position, dimensions)
+        }),
+        GeometryValue::MultiPoint { coordinates } => {
+            write_list(wkt, coordinates, |wkt, position| {
+                if position.is_empty() {
+                    wkt.push_str("EMPTY");
+                    Ok(())
+                } else {
+                    write_list(wkt, [position], |wkt, position| {
+                        write_position(wkt, position, dimensions)
+                    })
+                }
+            })
+        }
+        GeometryValue::LineString { coordinates } => write_line(wkt, coordinates, dimensions),
+        GeometryValue::MultiLineString { coordinates } | GeometryValue::Polygon { coordinates } => {
+            write_list(wkt, coordinates, |wkt, line| {
+                write_line(wkt, line, dimensions)
+            })
+        }
+        GeometryValue::MultiPolygon { coordinates } => {
+            write_list(wkt, coordinates, |wkt, polygon| {
+                write_list(wkt, polygon, |wkt, ring| write_line(wkt, ring, dimensions))
+            })
+        }
+        GeometryValue::GeometryCollection { geometries } => {
+            write_list(wkt, geometries, |wkt, geometry| {
+                write_geometry(wkt, &geometry.value, dimensions)
+            })
+        }
+    }
+}
+
+/// Write `items` as a parenthesized, comma-separated list.
+fn write_list<T>(
+    wkt: &mut String,
+    items: impl IntoIterator<Item = T>,
+    mut write_item: impl FnMut(&mut String, T) -> Result<()>,
+) -> Result<()> {
+    wkt.push('(');
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            wkt.push_str(", ");
+        }
+        write_item(wkt, item)?;
+    }
+    wkt.push(')');
+    Ok(())
+}
+
+fn write_line(wkt: &mut String, line: &[Position], dimensions: usize) -> Result<()> {
+    write_list(wkt, line, |wkt, position| {
+        write_position(wkt, position, dimensions)
+    })
+}
+
+fn write_position(wkt: &mut String, position: &Position, dimensions: usize) -> Result<()> {
+    if position.len() < 2 {
+        return Err(Error::PositionTooShort(position.len()));
+    }
+    for (index, &element) in position.as_slice().iter().take(dimensions).enumerate() {
+        // `NaN` and `inf` aren't numbers in WKT
+        if !element.is_finite() {
+            return Err(Error::NonFiniteCoordinate(element));
+        }
+        if index > 0 {
+            wkt.push(' ');
+        }
+        // Writing to a String can't fail
+        _ = write!(wkt, "{element}");
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token<'a> {
+    Word(&'a str),
+    Number(f64),
+    OpenParen,
+    CloseParen,
+    Comma,
+}
+
+/// The coordinates declared for a geometry beyond X and Y, e.g. `Z` in `POINT Z (1 2 3)`.
+#[derive(Clone, Copy, PartialEq)]
+enum Dimensions {
+    Xyz,
+    Xym,
+    Xyzm,
+}
+
+impl Dimensions {
+    fn len(self) -> usize {
+        match self {
+            Dimensions::Xyz | Dimensions::Xym => 3,
+            Dimensions::Xyzm => 4,
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+    // The number of GeometryCollections being parsed
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: impl Into<String>) -> Error {
+        Error::InvalidWkt {
+            message: message.into(),
+            offset: self.offset,
+        }
+    }
+
+    fn remaining(&self) -> &'a str {
+        &self.input[self.offset..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let remaining = self.remaining();
+        self.offset += remaining.len() - remaining.trim_start().len();
+    }
+
+    fn skip_srid(&mut self) {
+        self.skip_whitespace();
+        let remaining = self.remaining();
+        if remaining
+            .get(..5)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("SRID="))
+            && let Some(end) = remaining.find(';')
+        {
+            self.offset += end + 1;
+        }
+    }
+
+    fn peek_token(&mut self) -> Result<Option<Token<'a>>> {
+        let offset = self.offset;
+        let token = self.next_token();
+        self.offset = offset;
+        token
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token<'a>>> {
+        self.skip_whitespace();
+        let remaining = self.remaining();
+        let Some(first) = remaining.chars().next() else {
+            return Ok(None);
+        };
+        let token = match first {
+            '(' => Token::OpenParen,
+            ')' => Token::CloseParen,
+            ',' => Token::Comma,
+            first if first.is_ascii_alphabetic() => {
+                let len = remaining
+                    .find(|c: char| !c.is_ascii_alphanumeric())
+                    .unwrap_or(remaining.len());
+                self.offset += len;
+                return Ok(Some(Token::Word(&remaining[..len])));
+            }
+            first if first.is_ascii_digit() || matches!(first, '-' | '+' | '.') => {
+                let len = remaining
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.')))
+                    .unwrap_or(remaining.len());
+                let number = remaining[..len]
+                    .parse()
+                    .map_err(|_| self.error(format!("invalid number `{}`", &remaining[..len])))?;
+                self.offset += len;
+                return Ok(Some(Token::Number(number)));
+            }
+            other => return Err(self.error(format!("unexpected character `{other}`"))),
+        };
+        self.offset += 1;
+        Ok(Some(token))
+    }
+
+    fn expect(&mut self, expected: Token, description: &str) -> Result<()> {
+        let offset = self.offset;
+        if self.next_token()? == Some(expected) {
+            Ok(())
+        } else {
+            self.offset = offset;
+            Err(self.error(format!("expected {description}")))
+        }
+    }
+
+    /// Parse a tagged geometry. A member of a GeometryCollection inherits the collection's
+    /// `dimensions` unless it declares its own.
+    fn geometry(&mut self, dimensions: Option<Dimensions>) -> Result<GeometryValue> {
+        let Some(Token::Word(word)) = self.next_token()? else {
+            return Err(self.error("expected a geometry type"));
+        };
+        let word = word.to_ascii_uppercase();
+        // PostGIS writes the dimensions as a suffix, e.g. `POINTZ`
+        let (name, mut declared) = [
+            ("ZM", Dimensions::Xyzm),
+            ("Z", Dimensions::Xyz),
+            ("M", Dimensions::Xym),
+        ]
+        .into_iter()
+        .find_map(|(suffix, dimensions)| {
+            let name = word.strip_suffix(suffix)?;
+            is_geometry_type(name).then_some((name, Some(dimensions)))
+        })
+        .unwrap_or((&word, None));
+        if !is_geometry_type(name) {
+            return Err(self.error(format!("unknown geometry type `{word}`")));
+        }
+        if declared.is_none()
+            && let Some(Token::Word(tag)) = self.peek_token()?
+        {
+            declared = match tag.to_ascii_uppercase().as_str() {
+                "Z" => Some(Dimensions::Xyz),
+                "M" => Some(Dimensions::Xym),
+                "ZM" => Some(Dimensions::Xyzm),
+                _ => None,
+            };
+            if declared.is_some() {
+                self.next_token()?;
+            }
+        }
+        let dimensions = declared.or(dimensions);
+
+        let is_empty = match self.peek_token()? {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("EMPTY") => {
+                self.next_token()?;
+                true
+            }
+            _ => false,
+        };
+        Ok(match name {
+            "POINT" => GeometryValue::Point {
+                coordinates: if is_empty {
+                    Position::from(vec![])
+                } else {
+                    self.expect(Token::OpenParen, "`(`")?;
+                    let position = self.position(dimensions)?;
+                    self.expect(Token::CloseParen, "`)`")?;
+                    position
+                },
+            },
+            "MULTIPOINT" => GeometryValue::MultiPoint {
+                coordinates: self.list(is_empty, |parser| {
+                    // Each point may be empty, and may or may not be parenthesized
+                    let next = parser.peek_token()?;
+                    if let Some(Token::Word(word)) = next
+                        && word.eq_ignore_ascii_case("EMPTY")
+                    {
+                        parser.next_token()?;
+                        Ok(Position::from(vec![]))
+                    } else if next == Some(Token::OpenParen) {
+                        parser.next_token()?;
+                        let position = parser.position(dimensions)?;
+                        parser.expect(Token::CloseParen, "`)`")?;
+                        Ok(position)
+                    } else {
+                        parser.position(dimensions)
+                    }
+                })?,
+            },
+            "LINESTRING" => GeometryValue::LineString {
+                coordinates: self.line(is_empty, dimensions)?,
+            },
+            "MULTILINESTRING" => GeometryValue::MultiLineString {
+                coordinates: self.list(is_empty, |parser| parser.line(false, dimensions))?,
+            },
+            "POLYGON" => GeometryValue::Polygon {
+                coordinates: self.list(is_empty, |parser| parser.line(false, dimensions))?,
+            },
+            "MULTIPOLYGON" => GeometryValue::MultiPolygon {
+                coordinates: self.list(is_empty, |parser| {
+                    parser.list(false, |parser| parser.line(false, dimensions))
+                })?,
+            },
+            _ => {
+                if self.depth == MAX_COLLECTION_DEPTH {
+                    return Err(self.error("GEOMETRYCOLLECTION is nested too deeply"));
+                }
+                self.depth += 1;
+                let geometries = self.list(is_empty, |parser| {
+                    Ok(Geometry::new(parser.geometry(dimensions)?))
+                })?;
+                self.depth -= 1;
+                GeometryValue::GeometryCollection { geometries }
+            }
+        })
+    }
+
+    /// Parse a parenthesized, comma-separated list, or nothing if the geometry is empty.
+    fn list<T>(
+        &mut self,
+        is_empty: bool,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        if is_empty {
+            return Ok(vec![]);
+        }
+        self.expect(Token::OpenParen, "`(`")?;
+        let mut items = vec![item(self)?];
+        loop {
+            match self.next_token()? {
+                Some(Token::Comma) => items.push(item(self)?),
+                Some(Token::CloseParen) => return Ok(items),
+                _ => return Err(self.error("expected `,` or `)`")),
+            }
+        }
+    }
+
+    fn line(&mut self, is_empty: bool, dimensions: Option<Dimensions>) -> Result<Vec<Position>> {
+        self.list(is_empty, |parser| parser.position(dimensions))
+    }
+
+    fn position(&mut self, dimensions: Option<Dimensions>) -> Result<Position> {
+        let mut elements = vec![];
+        while let Some(Token::Number(element)) = self.peek_token()? {
+            self.next_token()?;
+            elements.push(element);
+        }
+        let valid = match dimensions {
+            Some(dimensions) => elements.len() == dimensions.len(),
+            None => (2..=4).contains(&elements.len()),
+        };
+        if !valid {
+            return Err(self.error(format!(
+                "unexpected number of coordinates: {}",
+                elements.len()
+            )));
+        }
+        Ok(Position::from(elements))
+    }
+}
+
+fn is_geometry_type(name: &str) -> bool {
+    matches!(
+        name,
+        "POINT"
+            | "MULTIPOINT"
+            | "LINESTRING"
+            | "MULTILINESTRING"
+            | "POLYGON"
+            | "MULTIPOLYGON"
+            | "GEOMETRYCOLLECTION"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(wkt: &str, expected: GeometryValue) {
+        let value = GeometryValue::from_wkt(wkt).unwrap();
+        assert_eq!(value, expected, "{wkt}");
+        assert_eq!(value.to_wkt().unwrap(), wkt);
+    }
+
+    #[test]
+    fn every_type() {
+        roundtrip("POINT (1 2)", GeometryValue::new_point([1.0, 2.0]));
+        roundtrip(
+            "MULTIPOINT ((1 2), (-3.5 4))",
+            GeometryValue::new_multi_point([[1.0, 2.0], [-3.5, 4.0]]),
+        );
+        roundtrip(
+            "LINESTRING (1 2, 3 4)",
+            GeometryValue::new_line_string([[1.0, 2.0], [3.0, 4.0]]),
+        );
+        roundtrip(
+            "MULTILINESTRING ((1 2, 3 4), (5 6, 7 8))",
+            GeometryValue::new_multi_line_string([
+                [[1.0, 2.0], [3.0, 4.0]],
+                [[5.0, 6.0], [7.0, 8.0]],
+            ]),
+        );
+        roundtrip(
+            "POLYGON ((0 0, 10 0, 10 10, 0 0), (1 1, 2 2, 2 1, 1 1))",
+            GeometryValue::new_polygon([
+                [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 0.0]],
+                [[1.0, 1.0], [2.0, 2.0], [2.0, 1.0], [1.0, 1.0]],
+            ]),
+        );
+        roundtrip(
+            "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((5 5, 6 5, 6 6, 5 5)))",
+            GeometryValue::new_multi_polygon([
+                [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]],
+                [[[5.0, 5.0], [6.0, 5.0], [6.0, 6.0], [5.0, 5.0]]],
+            ]),
+        );
+        roundtrip(
+            "GEOMETRYCOLLECTION (POINT (1 2), GEOMETRYCOLLECTION (LINESTRING (1 2, 3 4)))",
+            GeometryValue::new_geometry_collection([
+                Geometry::new_point([1.0, 2.0]),
+                Geometry::new_geometry_collection([Geometry::new_line_string([
+                    [1.0, 2.0],
+                    [3.0, 4.0],
+                ])]),
+            ]),
+        );
+    }
+
+    #[test]
+    fn empty() {
+        roundtrip(
+            "POINT EMPTY",
+            GeometryValue::Point {
+                coordinates: Position::from(vec![]),
+            },
+        );
+        roundtrip(
+            "LINESTRING EMPTY",
+            GeometryValue::LineString {
+                coordinates: vec![],
+            },
+        );
+        roundtrip(
+            "POLYGON EMPTY",
+            GeometryValue::Polygon {
+                coordinates: vec![],
+            },
+        );
+        roundtrip(
+            "MULTIPOLYGON EMPTY",
+            GeometryValue::MultiPolygon {
+                coordinates: vec![],
+            },
+        );
+        roundtrip(
+            "GEOMETRYCOLLECTION EMPTY",
+            GeometryValue::GeometryCollection { geometries: vec![] },
+        );
+        roundtrip(
+            "GEOMETRYCOLLECTION Z (POINT Z (1 2 3), LINESTRING Z EMPTY)",
+            GeometryValue::new_geometry_collection([
+                Geometry::new_point([1.0, 2.0, 3.0]),
+                Geometry::new_line_string(Vec::<Position>::new()),
+            ]),
+        );
+    }
+
+    #[test]
+    fn empty_point_in_multi_point() {
+        roundtrip(
+            "MULTIPOINT ((1 2), EMPTY)",
+            GeometryValue::MultiPoint {
+                coordinates: vec![Position::from([1.0, 2.0]), Position::from(vec![])],
+            },
+        );
+    }
+
+    #[test]
+    fn unwritable() {
+        let short = GeometryValue::Point {
+            coordinates: Position::from(vec![1.0]),
+        };
+        assert!(matches!(short.to_wkt(), Err(Error::PositionTooShort(1))));
+        let short = GeometryValue::LineString {
+            coordinates: vec![Position::from([1.0, 2.0]), Position::from(vec![])],
+        };
+        assert!(matches!(short.to_wkt(), Err(Error::PositionTooShort(0))));
+
+        for element in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let value = GeometryValue::new_multi_point([[1.0, 2.0], [3.0, element]]);
+            assert!(matches!(
+                value.to_wkt(),
+                Err(Error::NonFiniteCoordinate(e)) if e.to_bits() == element.to_bits()
+            ));
+        }
+    }
+
+    #[test]
+    fn dimensions() {
+        roundtrip("POINT Z (1 2 3)", GeometryValue::new_point([1.0, 2.0, 3.0]));
+        roundtrip(
+            "LINESTRING ZM (1 2 3 4, 5 6 7 8)",
+            GeometryValue::new_line_string([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]),
+        );
+        assert_eq!(
+            GeometryValue::from_wkt("POINT M (1 2 4)").unwrap(),
+            GeometryValue::new_point([1.0, 2.0, 4.0])
+        );
+        // Dimensions may be left to the number of coordinates
+        assert_eq!(
+            GeometryValue::from_wkt("POINT (1 2 3)").unwrap(),
+            GeometryValue::new_point([1.0, 2.0, 3.0])
+        );
+        // A position without a Z drops every other position's Z
+        assert_eq!(
+            GeometryValue::new_line_string([vec![1.0, 2.0, 3.0], vec![4.0, 5.0]])
+                .to_wkt()
+                .unwrap(),
+            "LINESTRING (1 2, 4 5)"
+        );
+    }
+
+    #[test]
+    fn lenient_syntax() {
+        assert_eq!(
+            GeometryValue::from_wkt("SRID=4326;pointz(1 2 3)").unwrap(),
+            GeometryValue::new_point([1.0, 2.0, 3.0])
+        );
+        assert_eq!(
+            GeometryValue::from_wkt("  MultiPoint(1 2,3 4) ").unwrap(),
+            GeometryValue::new_multi_point([[1.0, 2.0], [3.0, 4.0]])
+        );
+        assert_eq!(
+            GeometryValue::from_wkt("POINT(1e3 -2.5E-1)").unwrap(),
+            GeometryValue::new_point([1000.0, -0.25])
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for wkt in [
+            "",
+            "POINT",
+            "POINT ()",
+            "POINT (1)",
+            "POINT (1 2 3 4 5)",
+            "POINT Z (1 2)",
+            "POINT (1 2",
+            "POINT (1 2) POINT (3 4)",
+            "CIRCLE (1 2)",
+            "LINESTRING (1 2, 3 4,)",
+            "POLYGON (1 2, 3 4)",
+            "POINT (1 x)",
+            "POINT (1..2 3)",
+        ] {
+            assert!(
+                matches!(GeometryValue::from_wkt(wkt), Err(Error::InvalidWkt { .. })),
+                "{wkt}"
+            );
+        }
+        let Err(Error::InvalidWkt { offset, .. }) = GeometryValue::from_wkt("POINT (1 2,") else {
+            unreachable!()
+        };
+        assert_eq!(offset, 10);
+    }
+
+    #[test]
+    fn deeply_nested() {
+        let nested = |depth| {
+            format!(
+                "{}POINT (1 2){}",
+                "GEOMETRYCOLLECTION (".repeat(depth),
+                ")".repeat(depth)
+            )
+        };
+        assert!(GeometryValue::from_wkt(&nested(MAX_COLLECTION_DEPTH)).is_ok());
+
+        let Err(Error::InvalidWkt { offset, .. }) = GeometryValue::from_wkt(&nested(100_000))
+        else {
+            unreachable!()
+        };
+        // Just after the type of the first collection too many
+        let expected = "GEOMETRYCOLLECTION (".len() * MAX_COLLECTION_DEPTH;
+        assert_eq!(offset, expected + "GEOMETRYCOLLECTION".len());
+    }
+}
@@ -0,0 +1,660 @@
+use crate::winding::is_ring_reversed;
+use crate::{
+    Bbox, Feature, FeatureCollection, GeoJson, Geometry, GeometryValue, JsonObject, Position,
+    Result,
+};
+
+use serde::Serialize;
+use serde::ser::{SerializeMap, Serializer};
+use std::{fmt, io};
+
+/// Options controlling how GeoJSON output is written.
+///
+/// By default, values are written exactly as they are. Enabling an option transforms coordinates
+/// as they're written, leaving the original value untouched, and without copying it.
+///
+/// `WriteOptions` can write any [`GeoJson`], [`FeatureCollection`], [`Feature`], [`Geometry`]
+/// or [`GeometryValue`]. To stream features with options, see
+/// [`FeatureWriter::with_options`](crate::FeatureWriter::with_options).
+///
+/// # Examples
+///
+/// ```
+/// use geojson::{Geometry, WriteOptions};
+///
+/// // A clockwise exterior ring
+/// let polygon = Geometry::new_polygon([[[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]]);
+///
+/// let options = WriteOptions::default().with_rewind(true);
+/// assert_eq!(
+///     options.to_string(&polygon).unwrap(),
+///     r#"{"type":"Polygon","coordinates":[[[0.0,0.0],[1.0,0.0],[1.0,1.0],[0.0,0.0]]]}"#
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct WriteOptions {
+    rewind: bool,
+    precision: Option<Precision>,
+}
+
+/// How precisely to write coordinates.
+///
+/// [RFC 7946 § 11.2](https://tools.ietf.org/html/rfc7946#section-11.2) notes that 6 decimal
+/// places of longitude and latitude (about 10cm) is usually enough.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Precision {
+    /// Round to this many digits after the decimal point.
+    DecimalPlaces(u8),
+    /// Round to this many significant digits. At least one digit is always kept.
+    SignificantDigits(u8),
+}
+
+impl Precision {
+    fn round(self, value: f64) -> f64 {
+        if !value.is_finite() || value == 0.0 {
+            // Don't write `-0.0`
+            return if value == 0.0 { 0.0 } else { value };
+        }
+        let exponent = decimal_exponent(value);
+        let places = match self {
+            Precision::DecimalPlaces(places) => i32::from(places),
+            Precision::SignificantDigits(digits) => i32::from(digits.max(1)) - 1 - exponent,
+        };
+        if exponent + places + 1 >= MAX_DIGITS {
+            return value;
+        }
+        let rounded = round_to_places(value, places);
+        if rounded == 0.0 { 0.0 } else { rounded }
+    }
+}
+
+/// Every f64 survives being written with this many significant digits and parsed back.
+const MAX_DIGITS: i32 = 17;
+
+/// Values at least this large have no fractional part.
+const MIN_INTEGRAL: f64 = 4503599627370496.0; // 2^52
+
+/// `10^exponent`, which is exact for exponents between -22 and 22.
+fn power_of_ten(exponent: i32) -> f64 {
+    let power = 10f64.powi(exponent.abs());
+    if exponent < 0 { 1.0 / power } else { power }
+}
+
+/// The exponent of `value` in scientific notation, e.g. `2` for `123.4`.
+fn decimal_exponent(value: f64) -> i32 {
+    let value = value.abs();
+    // `log10` can be off by one close to a power of 10
+    let exponent = value.log10().floor() as i32;
+    if power_of_ten(exponent) > value {
+        exponent - 1
+    } else if power_of_ten(exponent + 1) <= value {
+        exponent + 1
+    } else {
+        exponent
+    }
+}
+
+/// Round `value` to `places` digits after the decimal point, or to a multiple of `10^-places` if
+/// `places` is negative. Ties are rounded to even.
+fn round_to_places(value: f64, places: i32) -> f64 {
+    let scale = 10f64.powi(places.abs());
+    // `error` is how far the scaled value was rounded to fit in an f64
+    let (scaled, error) = if places >= 0 {
+        let scaled = value * scale;
+        (scaled, value.mul_add(scale, -scaled))
+    } else {
+        let scaled = value / scale;
+        (scaled, (-scaled).mul_add(scale, value))
+    };
+    if !scaled.is_finite() || scaled.abs() >= MIN_INTEGRAL {
+        return value;
+    }
+    let rounded = if (scaled - scaled.trunc()).abs() == 0.5 && error != 0.0 {
+        // Scaling rounded onto a tie, which the exact value wasn't
+        if error > 0.0 {
+            scaled.ceil()
+        } else {
+            scaled.floor()
+        }
+    } else {
+        scaled.round_ties_even()
+    };
+    // Dividing by an exact power of 10 gives the closest f64 to the rounded decimal
+    if places >= 0 {
+        rounded / scale
+    } else {
+        rounded * scale
+    }
+}
+
+impl WriteOptions {
+    /// Whether to rewind polygon rings to follow the right-hand rule when writing. Defaults to
+    /// `false`.
+    ///
+    /// See [`GeometryValue::enforce_winding`].
+    pub fn with_rewind(mut self, rewind: bool) -> Self {
+        self.rewind = rewind;
+        self
+    }
+
+    /// Round every coordinate, and any `bbox`, when writing. Defaults to `None`, writing every
+    /// digit.
+    ///
+    /// The in-memory values are never modified, so this only affects the output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use geojson::{Geometry, Precision, WriteOptions};
+    ///
+    /// let point = Geometry::new_point([-0.12345678, 51.98765432]);
+    ///
+    /// let options = WriteOptions::default().with_precision(Precision::DecimalPlaces(3));
+    /// assert_eq!(
+    ///     options.to_string(&point).unwrap(),
+    ///     r#"{"type":"Point","coordinates":[-0.123,51.988]}"#
+    /// );
+    ///
+    /// let options = WriteOptions::default().with_precision(Precision::SignificantDigits(3));
+    /// assert_eq!(
+    ///     options.to_string(&point).unwrap(),
+    ///     r#"{"type":"Point","coordinates":[-0.123,52.0]}"#
+    /// );
+    /// ```
+    pub fn with_precision(mut self, precision: impl Into<Option<Precision>>) -> Self {
+        self.precision = precision.into();
+        self
+    }
+
+    /// Serialize `value` as a String of GeoJSON.
+    pub fn to_string<T: Writable>(&self, value: &T) -> Result<String> {
+        Ok(serde_json::to_string(&self.wrap(value))?)
+    }
+
+    /// Serialize `value` as a pretty-printed String of GeoJSON.
+    pub fn to_string_pretty<T: Writable>(&self, value: &T) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.wrap(value))?)
+    }
+
+    /// Serialize `value` as a GeoJSON byte vector.
+    pub fn to_vec<T: Writable>(&self, value: &T) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&self.wrap(value))?)
+    }
+
+    /// Serialize `value` as GeoJSON into the IO stream.
+    pub fn to_writer<W: io::Write, T: Writable>(&self, writer: W, value: &T) -> Result<()> {
+        Ok(serde_json::to_writer(writer, &self.wrap(value))?)
+    }
+
+    /// Whether writing with these options leaves values unchanged.
+    pub(crate) fn is_identity(&self) -> bool {
+        !self.rewind && self.precision.is_none()
+    }
+
+    /// `value`, serialized with these options.
+    pub(crate) fn wrap<'a, T: Writable>(&'a self, value: &'a T) -> WithOptions<'a, T> {
+        WithOptions {
+            value,
+            options: self,
+        }
+    }
+
+    /// `values`, such as a bbox, serialized with this precision.
+    pub(crate) fn rounded<'a>(&self, values: &'a [f64]) -> Rounded<'a> {
+        Rounded {
+            values,
+            precision: self.precision,
+        }
+    }
+}
+
+/// Display `value` as GeoJSON, rounding coordinates to the formatter's precision, if any, as
+/// [`Precision::DecimalPlaces`].
+pub(crate) fn fmt_with_precision<T: Writable>(
+    value: &T,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let string = match f.precision() {
+        Some(places) => WriteOptions::default()
+            .with_precision(Precision::DecimalPlaces(
+                u8::try_from(places).unwrap_or(u8::MAX),
+            ))
+            .to_string(value),
+        None => serde_json::to_string(value).map_err(Into::into),
+    };
+    string.map_err(|_| fmt::Error).and_then(|s| f.write_str(&s))
+}
+
+/// A GeoJSON type which can be written with [`WriteOptions`].
+///
+/// This trait is sealed, and can't be implemented outside of this crate.
+pub trait Writable: Serialize + private::Sealed {
+    #[doc(hidden)]
+    fn serialize_with_options<S: Serializer>(
+        &self,
+        options: &WriteOptions,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>;
+}
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for crate::GeoJson {}
+    impl Sealed for crate::FeatureCollection {}
+    impl Sealed for crate::Feature {}
+    impl Sealed for crate::Geometry {}
+    impl Sealed for crate::GeometryValue {}
+}
+
+/// A value serialized with [`WriteOptions`], in the same layout as its own `Serialize`.
+pub(crate) struct WithOptions<'a, T> {
+    value: &'a T,
+    options: &'a WriteOptions,
+}
+
+impl<T: Writable> Serialize for WithOptions<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if self.options.is_identity() {
+            self.value.serialize(serializer)
+        } else {
+            self.value.serialize_with_options(self.options, serializer)
+        }
+    }
+}
+
+/// A sequence of values, each serialized with [`WriteOptions`].
+struct EachWithOptions<'a, T> {
+    values: &'a [T],
+    options: &'a WriteOptions,
+}
+
+impl<T: Writable> Serialize for EachWithOptions<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.values.iter().map(|value| self.options.wrap(value)))
+    }
+}
+
+/// Coordinates of a position, or a bbox, rounded as they're written.
+pub(crate) struct Rounded<'a> {
+    values: &'a [f64],
+    precision: Option<Precision>,
+}
+
+impl Serialize for Rounded<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.values.iter().map(|&value| match self.precision {
+            Some(precision) => precision.round(value),
+            None => value,
+        }))
+    }
+}
+
+/// Positions written in order, or in reverse to rewind a ring.
+struct Positions<'a> {
+    positions: &'a [Position],
+    reversed: bool,
+    precision: Option<Precision>,
+}
+
+impl<'a> Serialize for Positions<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let precision = self.precision;
+        let rounded = |position: &'a Position| Rounded {
+            values: position.as_slice(),
+            precision,
+        };
+        if self.reversed {
+            serializer.collect_seq(self.positions.iter().rev().map(rounded))
+        } else {
+            serializer.collect_seq(self.positions.iter().map(rounded))
+        }
+    }
+}
+
+/// Lines, or the rings of a polygon, which are rewound as they're written if `rewind` is set.
+struct Lines<'a> {
+    lines: &'a [Vec<Position>],
+    rewind: bool,
+    precision: Option<Precision>,
+}
+
+impl Serialize for Lines<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.lines
+                .iter()
+                .enumerate()
+                .map(|(index, line)| Positions {
+                    positions: line,
+                    reversed: self.rewind && is_ring_reversed(index, line),
+                    precision: self.precision,
+                }),
+        )
+    }
+}
+
+/// The polygons of a MultiPolygon.
+struct Polygons<'a> {
+    polygons: &'a [Vec<Vec<Position>>],
+    rewind: bool,
+    precision: Option<Precision>,
+}
+
+impl Serialize for Polygons<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.polygons.iter().map(|polygon| Lines {
+            lines: polygon,
+            rewind: self.rewind,
+            precision: self.precision,
+        }))
+    }
+}
+
+/// Write the `type` and `coordinates` (or `geometries`) members of a geometry.
+fn serialize_geometry_value<M: SerializeMap>(
+    map: &mut M,
+    value: &GeometryValue,
+    options: &WriteOptions,
+) -> std::result::Result<(), M::Error> {
+    map.serialize_entry("type", value.type_name())?;
+    let precision = options.precision;
+    let lines = |lines, rewind| Lines {
+        lines,
+        rewind,
+        precision,
+    };
+    match value {
+        GeometryValue::Point { coordinates } => {
+            map.serialize_entry("coordinates", &options.rounded(coordinates.as_slice()))
+        }
+        GeometryValue::MultiPoint { coordinates } | GeometryValue::LineString { coordinates } => {
+            let positions = Positions {
+                positions: coordinates,
+                reversed: false,
+                precision,
+            };
+            map.serialize_entry("coordinates", &positions)
+        }
+        GeometryValue::MultiLineString { coordinates } => {
+            map.serialize_entry("coordinates", &lines(coordinates, false))
+        }
+        GeometryValue::Polygon { coordinates } => {
+            map.serialize_entry("coordinates", &lines(coordinates, options.rewind))
+        }
+        GeometryValue::MultiPolygon { coordinates } => {
+            let polygons = Polygons {
+                polygons: coordinates,
+                rewind: options.rewind,
+                precision,
+            };
+            map.serialize_entry("coordinates", &polygons)
+        }
+        GeometryValue::GeometryCollection { geometries } => {
+            let geometries = EachWithOptions {
+                values: geometries,
+                options,
+            };
+            map.serialize_entry("geometries", &geometries)
+        }
+    }
+}
+
+fn serialize_bbox<M: SerializeMap>(
+    map: &mut M,
+    bbox: &Option<Bbox>,
+    options: &WriteOptions,
+) -> std::result::Result<(), M::Error> {
+    match bbox {
+        Some(bbox) => map.serialize_entry("bbox", &options.rounded(bbox)),
+        None => Ok(()),
+    }
+}
+
+fn serialize_foreign_members<M: SerializeMap>(
+    map: &mut M,
+    foreign_members: &Option<JsonObject>,
+) -> std::result::Result<(), M::Error> {
+    for (key, value) in foreign_members.iter().flatten() {
+        map.serialize_entry(key, value)?;
+    }
+    Ok(())
+}
+
+impl Writable for GeometryValue {
+    fn serialize_with_options<S: Serializer>(
+        &self,
+        options: &WriteOptions,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        serialize_geometry_value(&mut map, self, options)?;
+        map.end()
+    }
+}
+
+impl Writable for Geometry {
+    fn serialize_with_options<S: Serializer>(
+        &self,
+        options: &WriteOptions,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        serialize_bbox(&mut map, &self.bbox, options)?;
+        serialize_geometry_value(&mut map, &self.value, options)?;
+        serialize_foreign_members(&mut map, &self.foreign_members)?;
+        map.end()
+    }
+}
+
+impl Writable for Feature {
+    fn serialize_with_options<S: Serializer>(
+        &self,
+        options: &WriteOptions,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "Feature")?;
+        serialize_bbox(&mut map, &self.bbox, options)?;
+        let geometry = self
+            .geometry
+            .as_ref()
+            .map(|geometry| options.wrap(geometry));
+        map.serialize_entry("geometry", &geometry)?;
+        if let Some(id) = &self.id {
+            map.serialize_entry("id", id)?;
+        }
+        map.serialize_entry("properties", &self.properties)?;
+        serialize_foreign_members(&mut map, &self.foreign_members)?;
+        map.end()
+    }
+}
+
+impl Writable for FeatureCollection {
+    fn serialize_with_options<S: Serializer>(
+        &self,
+        options: &WriteOptions,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "FeatureCollection")?;
+        serialize_bbox(&mut map, &self.bbox, options)?;
+        let features = EachWithOptions {
+            values: &self.features,
+            options,
+        };
+        map.serialize_entry("features", &features)?;
+        serialize_foreign_members(&mut map, &self.foreign_members)?;
+        map.end()
+    }
+}
+
+impl Writable for GeoJson {
+    fn serialize_with_options<S: Serializer>(
+        &self,
+        options: &WriteOptions,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            GeoJson::Geometry(geometry) => geometry.serialize_with_options(options, serializer),
+            GeoJson::Feature(feature) => feature.serialize_with_options(options, serializer),
+            GeoJson::FeatureCollection(collection) => {
+                collection.serialize_with_options(options, serializer)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JsonValue;
+    use serde_json::json;
+
+    #[test]
+    fn round() {
+        let places = Precision::DecimalPlaces(2);
+        assert_eq!(places.round(1.005), 1.0);
+        assert_eq!(places.round(1.0051), 1.01);
+        assert_eq!(places.round(-0.001), 0.0);
+        assert_eq!(places.round(123456.789), 123456.79);
+        assert_eq!(Precision::DecimalPlaces(0).round(2.5), 2.0);
+        // Scaling these by 100 rounds them up onto a tie
+        assert_eq!(places.round(2.675), 2.67);
+        assert_eq!(places.round(1.115), 1.11);
+        assert_eq!(Precision::DecimalPlaces(u8::MAX).round(0.1), 0.1);
+
+        let digits = Precision::SignificantDigits(2);
+        assert_eq!(digits.round(123456.789), 120000.0);
+        assert_eq!(digits.round(0.00012345), 0.00012);
+        assert_eq!(Precision::SignificantDigits(0).round(0.987), 1.0);
+        assert_eq!(digits.round(999.0), 1000.0);
+        assert_eq!(digits.round(-0.001), -0.001);
+        assert_eq!(Precision::SignificantDigits(3).round(4.35e-10), 4.35e-10);
+        assert!(digits.round(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn precision() {
+        let mut feature = Feature::from(Geometry::new_line_string([
+            [1.23456789, 2.3456789, 100.123456],
+            [3.0, 4.0, 5.0],
+        ]));
+        feature.bbox = feature.compute_bbox();
+        feature.set_property("accuracy", 0.123456789);
+        let original = feature.clone();
+
+        let options = WriteOptions::default().with_precision(Precision::DecimalPlaces(2));
+        let output: JsonValue =
+            serde_json::from_str(&options.to_string(&feature).unwrap()).unwrap();
+        assert_eq!(
+            output["geometry"]["coordinates"],
+            json!([[1.23, 2.35, 100.12], [3.0, 4.0, 5.0]])
+        );
+        assert_eq!(output["bbox"], json!([1.23, 2.35, 5.0, 3.0, 4.0, 100.12]));
+        // Properties aren't coordinates
+        assert_eq!(output["properties"]["accuracy"], json!(0.123456789));
+        assert_eq!(feature, original);
+    }
+
+    #[test]
+    fn display_precision() {
+        let geometry = Geometry::new_point([1.23456789, 2.0]);
+        assert_eq!(
+            format!("{geometry:.3}"),
+            r#"{"type":"Point","coordinates":[1.235,2.0]}"#
+        );
+        assert_eq!(
+            format!("{geometry}"),
+            r#"{"type":"Point","coordinates":[1.23456789,2.0]}"#
+        );
+        assert_eq!(
+            format!("{:.1}", GeoJson::from(geometry.clone())),
+            r#"{"type":"Point","coordinates":[1.2,2.0]}"#
+        );
+        assert_eq!(
+            format!("{:.0}", geometry.value),
+            r#"{"type":"Point","coordinates":[1.0,2.0]}"#
+        );
+    }
+
+    #[test]
+    fn same_layout_as_serialize() {
+        let mut feature = Feature::from(Geometry::new(GeometryValue::GeometryCollection {
+            geometries: vec![
+                Geometry::new_point([1.0, 2.0]),
+                Geometry::new_multi_line_string([[[1.0, 2.0], [3.0, 4.0]]]),
+                Geometry::new_multi_polygon([[[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]]),
+            ],
+        }));
+        feature.bbox = feature.compute_bbox();
+        feature.id = Some(crate::feature::Id::Number(7.into()));
+        feature.set_property("name", "a");
+        feature
+            .foreign_members
+            .get_or_insert_default()
+            .insert("extra".to_string(), json!(true));
+        let mut collection = FeatureCollection::from_iter([feature, Feature::default()]);
+        collection.bbox = collection.compute_bbox();
+        let geojson = GeoJson::from(collection);
+
+        // These polygons are already wound correctly, and have no digits to round
+        let options = WriteOptions::default()
+            .with_rewind(true)
+            .with_precision(Precision::DecimalPlaces(1));
+        assert_eq!(options.to_string(&geojson).unwrap(), geojson.to_string());
+    }
+
+    fn clockwise_polygon() -> Geometry {
+        Geometry::new_polygon([[[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]])
+    }
+
+    #[test]
+    fn default_leaves_input_unchanged() {
+        let geojson = GeoJson::from(clockwise_polygon());
+        assert_eq!(
+            WriteOptions::default().to_string(&geojson).unwrap(),
+            geojson.to_string()
+        );
+    }
+
+    #[test]
+    fn rewind() {
+        let geojson = GeoJson::from(FeatureCollection::from_iter([
+            Feature::from(clockwise_polygon()),
+            Feature::default(),
+            Feature::from(Geometry::new_multi_polygon([[[
+                [0.0, 0.0],
+                [1.0, 1.0],
+                [1.0, 0.0],
+                [0.0, 0.0],
+            ]]])),
+        ]));
+        let options = WriteOptions::default().with_rewind(true);
+        let output: JsonValue = serde_json::from_slice(&options.to_vec(&geojson).unwrap()).unwrap();
+        assert_eq!(
+            output["features"][0]["geometry"],
+            json!({
+                "type": "Polygon",
+                "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]
+            })
+        );
+        assert_eq!(output["features"][1]["geometry"], JsonValue::Null);
+        assert_eq!(
+            output["features"][2]["geometry"]["coordinates"],
+            json!([[[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]])
+        );
+
+        // The input isn't modified
+        let GeoJson::FeatureCollection(collection) = &geojson else {
+            unreachable!()
+        };
+        assert!(
+            !collection.features[0]
+                .geometry
+                .as_ref()
+                .unwrap()
+                .value
+                .is_winding_valid()
+        );
+    }
+}